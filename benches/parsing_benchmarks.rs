@@ -1,7 +1,10 @@
 //! Performance benchmarks for FLEX parsing
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
-use ib_flex::parse_activity_flex;
+use ib_flex::{
+    parse_activity_flex, parse_activity_flex_all, parse_activity_flex_all_borrowed,
+    parse_activity_flex_all_with_options, ParseOptions, SectionSet,
+};
 
 fn benchmark_minimal_parsing(c: &mut Criterion) {
     let xml = include_str!("../tests/fixtures/activity_minimal.xml");
@@ -111,6 +114,116 @@ fn benchmark_scalability(c: &mut Criterion) {
     group.finish();
 }
 
+/// Backfill of `days` statements with `rows` trades, positions and cash
+/// transactions each, plus large funds and position value sections if
+/// `other_sections` is set
+fn large_backfill(days: usize, rows: usize, other_sections: bool) -> String {
+    let mut xml = format!(
+        "<FlexQueryResponse queryName=\"Backfill\" type=\"AF\">\n<FlexStatements count=\"{}\">\n",
+        days
+    );
+    let first = chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    for day in 1..=days {
+        let date = (first + chrono::Duration::days(day as i64 - 1))
+            .format("%Y%m%d")
+            .to_string();
+        xml.push_str(&format!(
+            "<FlexStatement accountId=\"U1234567\" fromDate=\"{d}\" toDate=\"{d}\" whenGenerated=\"{d};235959\">\n<Trades>\n",
+            d = date
+        ));
+        for i in 0..rows {
+            xml.push_str(&format!(
+                "<Trade accountId=\"U1234567\" currency=\"USD\" assetCategory=\"STK\" symbol=\"SYM{i}\" conid=\"{i}\" transactionID=\"{day}{i}\" tradeDate=\"{date}\" dateTime=\"{date};100000\" buySell=\"BUY\" quantity=\"10\" tradePrice=\"100.25\" proceeds=\"-1002.50\" ibCommission=\"-1\" levelOfDetail=\"EXECUTION\" />\n"
+            ));
+        }
+        xml.push_str("</Trades>\n<OpenPositions>\n");
+        for i in 0..rows {
+            xml.push_str(&format!(
+                "<OpenPosition accountId=\"U1234567\" currency=\"USD\" assetCategory=\"STK\" symbol=\"SYM{i}\" conid=\"{i}\" reportDate=\"{date}\" position=\"100\" markPrice=\"101.5\" positionValue=\"10150\" side=\"Long\" />\n"
+            ));
+        }
+        xml.push_str("</OpenPositions>\n<CashTransactions>\n");
+        for i in 0..rows {
+            xml.push_str(&format!(
+                "<CashTransaction accountId=\"U1234567\" currency=\"USD\" assetCategory=\"STK\" symbol=\"SYM{i}\" conid=\"{i}\" description=\"SYM{i} CASH DIVIDEND USD 0.25 PER SHARE\" dateTime=\"{date}\" settleDate=\"{date}\" amount=\"25\" type=\"Dividends\" transactionID=\"{day}{i}\" />\n"
+            ));
+        }
+        xml.push_str("</CashTransactions>\n");
+        if !other_sections {
+            xml.push_str("</FlexStatement>\n");
+            continue;
+        }
+        xml.push_str("<StmtFunds>\n");
+        for i in 0..500 {
+            xml.push_str(&format!(
+                "<StatementOfFundsLine accountId=\"U1234567\" currency=\"USD\" reportDate=\"{date}\" date=\"{date}\" activityCode=\"BUY\" activityDescription=\"Buy 10 SYM{i}\" transactionID=\"{day}{i}\" symbol=\"SYM{i}\" conid=\"{i}\" debit=\"-1002.50\" amount=\"-1002.50\" balance=\"50000\" />\n"
+            ));
+        }
+        xml.push_str("</StmtFunds>\n<ChangeInPositionValues>\n");
+        for i in 0..200 {
+            xml.push_str(&format!(
+                "<ChangeInPositionValue accountId=\"U1234567\" currency=\"USD\" reportDate=\"{date}\" symbol=\"SYM{i}\" conid=\"{i}\" assetCategory=\"STK\" priorPeriodValue=\"10000\" transactions=\"1002.50\" mtmPriorPeriodPositions=\"50\" />\n"
+            ));
+        }
+        xml.push_str("</ChangeInPositionValues>\n</FlexStatement>\n");
+    }
+    xml.push_str("</FlexStatements>\n</FlexQueryResponse>\n");
+    xml
+}
+
+fn benchmark_selective_sections(c: &mut Criterion) {
+    let mut group = c.benchmark_group("selective_sections");
+    let xml = large_backfill(30, 20, true);
+
+    let all = ParseOptions::default();
+    group.bench_function("all_sections", |b| {
+        b.iter(|| parse_activity_flex_all_with_options(black_box(&xml), &all).unwrap())
+    });
+
+    let selected = ParseOptions::default()
+        .with_sections(SectionSet::from_names(["Trades", "OpenPositions"]).unwrap());
+    group.bench_function("trades_and_positions", |b| {
+        b.iter(|| parse_activity_flex_all_with_options(black_box(&xml), &selected).unwrap())
+    });
+
+    group.finish();
+}
+
+fn benchmark_borrowed_parsing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("borrowed_parsing");
+    let xml = large_backfill(30, 200, false);
+
+    group.bench_function("owned", |b| {
+        b.iter(|| parse_activity_flex_all(black_box(&xml)).unwrap())
+    });
+    group.bench_function("borrowed", |b| {
+        b.iter(|| parse_activity_flex_all_borrowed(black_box(&xml)).unwrap())
+    });
+
+    group.finish();
+}
+
+/// Sequential vs parallel parsing of a year of daily statements; run with
+/// `cargo bench --features rayon`
+#[cfg(feature = "rayon")]
+fn benchmark_parallel_parsing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel_parsing");
+    group.sample_size(10);
+    let xml = large_backfill(250, 20, true);
+
+    group.bench_function("sequential", |b| {
+        b.iter(|| parse_activity_flex_all(black_box(&xml)).unwrap())
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| ib_flex::parse_activity_flex_all_par(black_box(&xml)).unwrap())
+    });
+
+    group.finish();
+}
+
+#[cfg(not(feature = "rayon"))]
+fn benchmark_parallel_parsing(_c: &mut Criterion) {}
+
 criterion_group!(
     benches,
     benchmark_minimal_parsing,
@@ -120,6 +233,9 @@ criterion_group!(
     benchmark_bonds_parsing,
     benchmark_corporate_actions_parsing,
     benchmark_cash_transactions_parsing,
-    benchmark_scalability
+    benchmark_scalability,
+    benchmark_selective_sections,
+    benchmark_borrowed_parsing,
+    benchmark_parallel_parsing
 );
 criterion_main!(benches);
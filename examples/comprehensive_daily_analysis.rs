@@ -171,7 +171,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         let mut by_asset: HashMap<AssetCategory, (usize, Decimal)> = HashMap::new();
         for pos in positions.iter() {
             let entry = by_asset
-                .entry(pos.asset_category)
+                .entry(pos.asset_category.clone())
                 .or_insert((0, Decimal::ZERO));
             entry.0 += 1;
             entry.1 += pos.position_value;
@@ -221,7 +221,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         // By asset class
         let mut by_asset: HashMap<AssetCategory, TradeStats> = HashMap::new();
         for trade in &all_trades {
-            let stats = by_asset.entry(trade.asset_category).or_default();
+            let stats = by_asset.entry(trade.asset_category.clone()).or_default();
             stats.count += 1;
             if let Some(pnl) = trade.fifo_pnl_realized {
                 stats.realized_pnl += pnl;
@@ -155,19 +155,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     let is_long_term = if let Some(orig_date) = trade.orig_trade_date {
                         let holding_period = trade_date - orig_date;
                         holding_period > Duration::days(365)
-                    } else if let Some(hpdt) = &trade.holding_period_date_time {
-                        // Parse holding period datetime if available
-                        if hpdt.len() >= 10 {
-                            if let Ok(hp_date) = NaiveDate::parse_from_str(&hpdt[..10], "%Y-%m-%d")
-                            {
-                                let holding_period = trade_date - hp_date;
-                                holding_period > Duration::days(365)
-                            } else {
-                                false // Default to short-term if can't determine
-                            }
-                        } else {
-                            false
-                        }
+                    } else if let Some(hp) = trade.holding_period_datetime(statement.date_format) {
+                        let holding_period = trade_date - hp.date();
+                        holding_period > Duration::days(365)
                     } else {
                         false // Default to short-term if no original date
                     };
@@ -209,21 +199,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Use positions from last statement (most recent)
     for position in &last.positions.items {
         // Parse acquisition date from open_date_time or holding_period_date_time
-        let acquisition_date = if let Some(odt) = &position.open_date_time {
-            if odt.len() >= 10 {
-                NaiveDate::parse_from_str(&odt[..10], "%Y-%m-%d").ok()
-            } else {
-                None
-            }
-        } else if let Some(hpdt) = &position.holding_period_date_time {
-            if hpdt.len() >= 10 {
-                NaiveDate::parse_from_str(&hpdt[..10], "%Y-%m-%d").ok()
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+        let acquisition_date = position
+            .open_datetime(last.date_format)
+            .or_else(|| position.holding_period_datetime(last.date_format))
+            .map(|dt| dt.date());
 
         if let Some(acq_date) = acquisition_date {
             // Check if this symbol had any loss sales within wash sale window
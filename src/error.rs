@@ -1,5 +1,7 @@
 //! Error types for FLEX parsing
 
+use std::fmt;
+
 use thiserror::Error;
 
 /// Result type alias for FLEX parsing operations
@@ -9,29 +11,41 @@ pub type Result<T> = std::result::Result<T, ParseError>;
 #[derive(Error, Debug)]
 pub enum ParseError {
     /// XML deserialization error
-    #[error("XML deserialization error: {message}")]
+    #[error("XML deserialization error: {message}{}", at(.location))]
     XmlError {
         /// Error message from XML parser
         message: String,
-        /// Optional location in XML where error occurred
-        location: Option<String>,
+        /// Location in XML where error occurred, if known
+        location: Option<Box<ErrorLocation>>,
     },
 
     /// Invalid date format
-    #[error("Invalid date format: {0}")]
-    InvalidDate(String),
+    #[error("Invalid date format: {message}{}", at(.location))]
+    InvalidDate {
+        /// Why the value was rejected
+        message: String,
+        /// Location of the offending attribute, if known
+        location: Option<Box<ErrorLocation>>,
+    },
 
     /// Invalid decimal format
-    #[error("Invalid decimal format: {0}")]
-    InvalidDecimal(String),
+    #[error("Invalid decimal format: {message}{}", at(.location))]
+    InvalidDecimal {
+        /// Why the value was rejected
+        message: String,
+        /// Location of the offending attribute, if known
+        location: Option<Box<ErrorLocation>>,
+    },
 
     /// Missing required field
-    #[error("Missing required field: {field} in {context}")]
+    #[error("Missing required field: {field} in {context}{}", at(.location))]
     MissingField {
         /// Name of the missing field
         field: String,
         /// Context where field was expected
         context: String,
+        /// Location of the element lacking the field, if known
+        location: Option<Box<ErrorLocation>>,
     },
 
     /// Unknown enum variant
@@ -47,7 +61,87 @@ pub enum ParseError {
     #[error("Unsupported FLEX schema version: {0}")]
     UnsupportedSchemaVersion(String),
 
+    /// Statement count declared by the response does not match its content
+    #[error("FlexStatements declares {declared} statement(s) but {parsed} were parsed")]
+    StatementCountMismatch {
+        /// Value of the `count` attribute
+        declared: usize,
+        /// Number of statements actually parsed
+        parsed: usize,
+    },
+
+    /// Section name not known to [`SectionSet`](crate::parsers::SectionSet)
+    #[error("Unknown FlexStatement section: {0}")]
+    UnknownSection(String),
+
     /// IO error
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
+
+impl ParseError {
+    /// Where in the XML the error occurred, if known
+    pub fn location(&self) -> Option<&ErrorLocation> {
+        match self {
+            ParseError::XmlError { location, .. }
+            | ParseError::InvalidDate { location, .. }
+            | ParseError::InvalidDecimal { location, .. }
+            | ParseError::MissingField { location, .. } => location.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Position of a parse error in the FLEX XML
+///
+/// Displayed as e.g.
+/// `line 42, column 9 (byte 1873) in FlexStatement[3]/Trades/Trade[17] @tradePrice="12.3.4"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorLocation {
+    /// Zero-based byte offset from the start of the document
+    pub byte_offset: usize,
+
+    /// One-based line number
+    pub line: usize,
+
+    /// One-based column number, counted in characters
+    pub column: usize,
+
+    /// Path of the failing element, e.g. `FlexStatement[3]/Trades/Trade[17]`
+    ///
+    /// Indices count same-named siblings from zero. Section elements directly
+    /// below a statement appear without an index.
+    pub element_path: String,
+
+    /// Name of the failing attribute, if the error concerns one
+    pub attribute: Option<String>,
+
+    /// Raw value of the failing attribute as it appeared in the XML
+    pub raw_value: Option<String>,
+}
+
+impl fmt::Display for ErrorLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {} (byte {})",
+            self.line, self.column, self.byte_offset
+        )?;
+        if !self.element_path.is_empty() {
+            write!(f, " in {}", self.element_path)?;
+        }
+        match (&self.attribute, &self.raw_value) {
+            (Some(name), Some(value)) => write!(f, " @{}=\"{}\"", name, value),
+            (Some(name), None) => write!(f, " @{}", name),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Suffix appended to error messages when a location is known
+fn at(location: &Option<Box<ErrorLocation>>) -> String {
+    location
+        .as_ref()
+        .map(|l| format!(" at {}", l))
+        .unwrap_or_default()
+}
@@ -4,7 +4,8 @@
 //!
 //! ## Features
 //!
-//! - 🚀 **Zero-copy parsing** with quick-xml and serde
+//! - 🚀 **Borrowed parsing** of trades, positions and cash transactions via
+//!   [`parse_activity_flex_borrowed`], with text fields pointing into the input
 //! - 💰 **Financial precision** with rust_decimal for all monetary values
 //! - 📅 **Correct datetime handling** with chrono
 //! - ✅ **Type-safe** enums for asset categories, order types, etc.
@@ -47,8 +48,13 @@
 //!
 //! ## Known Limitations
 //!
-//! 1. **Date formats**: Only ISO-8601 (yyyy-MM-dd) and yyyyMMdd supported
-//! 2. **Schema versions**: Tested with FLEX schema version 3
+//! 1. **Date formats**: yyyy-MM-dd and yyyyMMdd are always supported. The other
+//!    Flex Query date formats (MM/dd/yyyy, MM/dd/yy, dd/MM/yyyy, dd/MM/yy,
+//!    dd-MMM-yy) are detected from the statement; if the day/month order cannot
+//!    be inferred, parsing fails and a [`DateFormatProfile`] must be passed via
+//!    [`ParseOptions::with_date_format`]
+//! 2. **Schema versions**: Only FLEX schema version 3 is registered; documents
+//!    declaring another `version` fail with [`ParseError::UnsupportedSchemaVersion`]
 
 #![warn(missing_docs)]
 #![warn(clippy::all)]
@@ -62,13 +68,65 @@ pub mod version;
 pub mod api;
 
 // Re-export commonly used types
-pub use error::{ParseError, Result};
+pub use error::{ErrorLocation, ParseError, Result};
+pub use parsers::{
+    ActivityFlexStream, DateFormat, DateFormatProfile, FlexDocument, FlexEnvelope,
+    LazyActivityStatement, ParseMode, ParseOptions, ParseWarning, ParsedStatement, SchemaReport,
+    SectionSet, StatementResult, TimeFormat,
+};
 pub use types::{
-    ActivityFlexStatement, AssetCategory, BuySell, CashTransaction, CorporateAction, OpenClose,
-    OrderType, Position, PutCall, Trade, TradeConfirmationStatement,
+    ActivityFlexStatement, ActivityFlexStatementRef, AssetCategory, BuySell, CashTransaction,
+    CashTransactionRef, CorporateAction, OpenClose, OrderType, Position, PositionRef, PutCall,
+    Trade, TradeConfirmationStatement, TradeRef,
 };
 pub use version::FlexSchemaVersion;
 
+/// Parse a FLEX document, detecting its statement type
+///
+/// Single entry point for both Activity and Trade Confirmation FLEX. The
+/// `FlexQueryResponse` envelope (query name, type and declared statement
+/// count) is kept, and the declared count is checked against the number of
+/// statements parsed.
+///
+/// # Arguments
+///
+/// * `xml` - XML string from any IB FLEX query
+///
+/// # Returns
+///
+/// * `Ok(FlexDocument)` - All statements with the response metadata
+/// * `Err(ParseError)` - Parse error with detailed context
+///
+/// # Errors
+///
+/// Returns [`ParseError::StatementCountMismatch`] if the declared count is
+/// wrong, and `ParseError` for the same reasons as [`parse_activity_flex`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ib_flex::{parse_flex, FlexDocument};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let xml = std::fs::read_to_string("statement.xml")?;
+/// let document = parse_flex(&xml)?;
+///
+/// println!("Query: {:?}", document.query_name());
+/// match document {
+///     FlexDocument::Activity { statements, .. } => {
+///         println!("{} activity statement(s)", statements.len())
+///     }
+///     FlexDocument::TradeConfirmation { statements, .. } => {
+///         println!("{} confirmation statement(s)", statements.len())
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_flex(xml: &str) -> Result<FlexDocument> {
+    parsers::parse_flex(xml)
+}
+
 /// Parse an Activity FLEX XML statement
 ///
 /// Parses Interactive Brokers Activity FLEX XML into a structured
@@ -125,6 +183,201 @@ pub fn parse_activity_flex_all(xml: &str) -> Result<Vec<ActivityFlexStatement>>
     parsers::parse_activity_flex_all(xml)
 }
 
+/// Parse all Activity FLEX statements from XML in parallel
+///
+/// Splits the document at statement boundaries and deserializes the
+/// statements on rayon's thread pool. The result is identical to
+/// [`parse_activity_flex_all`], in document order. Requires the `rayon`
+/// feature.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ib_flex::parse_activity_flex_all_par;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let xml = std::fs::read_to_string("backfill.xml")?;
+/// let statements = parse_activity_flex_all_par(&xml)?;
+///
+/// println!("Statements: {}", statements.len());
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "rayon")]
+pub fn parse_activity_flex_all_par(xml: &str) -> Result<Vec<ActivityFlexStatement>> {
+    parsers::parse_activity_flex_all_par(xml)
+}
+
+/// Parse an Activity FLEX XML statement without copying its text
+///
+/// Trades, open positions and cash transactions are deserialized into
+/// borrowed rows ([`TradeRef`], [`PositionRef`], [`CashTransactionRef`])
+/// whose text fields point into `xml`; other sections are skipped. Rows
+/// convert into the owned types with `into_owned`.
+///
+/// # Arguments
+///
+/// * `xml` - XML string from IB FLEX query (Activity type)
+///
+/// # Returns
+///
+/// * `Ok(ActivityFlexStatementRef)` - The first statement, borrowing from `xml`
+/// * `Err(ParseError)` - Parse error with detailed context
+pub fn parse_activity_flex_borrowed(xml: &str) -> Result<ActivityFlexStatementRef<'_>> {
+    parsers::parse_activity_flex_borrowed(xml)
+}
+
+/// Parse all Activity FLEX statements without copying their text
+///
+/// Multi-statement counterpart of [`parse_activity_flex_borrowed`].
+///
+/// # Arguments
+///
+/// * `xml` - XML string from IB Activity FLEX query
+///
+/// # Returns
+///
+/// * `Ok(Vec<ActivityFlexStatementRef>)` - All statements, borrowing from `xml`
+/// * `Err(ParseError)` - Parse error with context
+pub fn parse_activity_flex_all_borrowed(xml: &str) -> Result<Vec<ActivityFlexStatementRef<'_>>> {
+    parsers::parse_activity_flex_all_borrowed(xml)
+}
+
+/// Parse an Activity FLEX XML statement with explicit options
+///
+/// In [`ParseMode::Lenient`], a value that fails to deserialize (a malformed
+/// decimal, date or Y/N flag) does not fail the statement: the field becomes
+/// `None` and a [`ParseWarning`] records the section, element index,
+/// attribute, raw value and reason. Required fields still fail. In both modes,
+/// non-fatal findings such as unknown transaction codes are returned as
+/// warnings instead of being printed.
+///
+/// # Arguments
+///
+/// * `xml` - XML string from IB FLEX query (Activity type)
+/// * `options` - Parse options, e.g. [`ParseOptions::lenient`]
+///
+/// # Returns
+///
+/// * `Ok(ParsedStatement)` - The first statement and its warnings
+/// * `Err(ParseError)` - Parse error with detailed context
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ib_flex::{parse_activity_flex_with_options, ParseOptions};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let xml = std::fs::read_to_string("statement.xml")?;
+/// let parsed = parse_activity_flex_with_options(&xml, &ParseOptions::lenient())?;
+///
+/// for warning in &parsed.warnings {
+///     println!("skipped {}", warning);
+/// }
+/// println!("Trades: {}", parsed.statement.trades.items.len());
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_activity_flex_with_options(
+    xml: &str,
+    options: &ParseOptions,
+) -> Result<ParsedStatement> {
+    parsers::parse_activity_flex_with_options(xml, options)
+}
+
+/// Parse all Activity FLEX statements with explicit options
+///
+/// Multi-statement counterpart of [`parse_activity_flex_with_options`].
+///
+/// # Arguments
+///
+/// * `xml` - XML string from IB Activity FLEX query
+/// * `options` - Parse options, e.g. [`ParseOptions::lenient`]
+///
+/// # Returns
+///
+/// * `Ok(Vec<ParsedStatement>)` - All statements with their warnings
+/// * `Err(ParseError)` - Parse error with context
+pub fn parse_activity_flex_all_with_options(
+    xml: &str,
+    options: &ParseOptions,
+) -> Result<Vec<ParsedStatement>> {
+    parsers::parse_activity_flex_all_with_options(xml, options)
+}
+
+/// Parse each Activity FLEX statement independently
+///
+/// Returns one [`StatementResult`] per `<FlexStatement>`, tagged with its
+/// index, `accountId` and from/to dates. One malformed statement no longer
+/// discards the rest of a multi-statement backfill.
+///
+/// # Arguments
+///
+/// * `xml` - XML string from IB Activity FLEX query
+///
+/// # Returns
+///
+/// * `Vec<StatementResult>` - One result per statement, in document order
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ib_flex::parse_activity_flex_isolated;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let xml = std::fs::read_to_string("backfill.xml")?;
+///
+/// for outcome in parse_activity_flex_isolated(&xml) {
+///     match outcome.result {
+///         Ok(statement) => println!("Loaded {}", statement.to_date),
+///         Err(e) => eprintln!(
+///             "Quarantined statement #{} ({:?} {:?}): {}",
+///             outcome.index, outcome.account_id, outcome.to_date, e
+///         ),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_activity_flex_isolated(xml: &str) -> Vec<StatementResult> {
+    parsers::parse_activity_flex_isolated(xml)
+}
+
+/// Stream Activity FLEX statements from a reader
+///
+/// Returns an iterator that deserializes one `<FlexStatement>` at a time,
+/// so memory use stays near the size of a single statement no matter how
+/// large the backfill file is.
+///
+/// # Arguments
+///
+/// * `reader` - Buffered reader over IB Activity FLEX XML
+///
+/// # Returns
+///
+/// An [`ActivityFlexStream`] yielding `Result<ActivityFlexStatement>` per statement
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ib_flex::stream_activity_flex;
+/// use std::fs::File;
+/// use std::io::BufReader;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let reader = BufReader::new(File::open("backfill.xml")?);
+///
+/// for statement in stream_activity_flex(reader) {
+///     let statement = statement?;
+///     println!("{}: {} trades", statement.to_date, statement.trades.items.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn stream_activity_flex<R: std::io::BufRead>(reader: R) -> ActivityFlexStream<R> {
+    ActivityFlexStream::new(reader)
+}
+
 /// Parse a Trade Confirmation FLEX XML statement
 ///
 /// Parses Interactive Brokers Trade Confirmation FLEX XML into a structured
@@ -163,10 +416,29 @@ pub fn parse_trade_confirmation(xml: &str) -> Result<TradeConfirmationStatement>
     parsers::parse_trade_confirmation(xml)
 }
 
+/// Parse all Trade Confirmation FLEX statements from XML
+///
+/// A `<FlexQueryResponse type="TCF">` document holds one statement per
+/// account; each statement's executions are in
+/// [`TradeConfirmationStatement::trade_confirms`].
+///
+/// # Arguments
+///
+/// * `xml` - XML string from IB FLEX query (Trade Confirmation type)
+///
+/// # Returns
+///
+/// * `Ok(Vec<TradeConfirmationStatement>)` - All parsed statements
+/// * `Err(ParseError)` - Parse error with context
+pub fn parse_trade_confirmation_all(xml: &str) -> Result<Vec<TradeConfirmationStatement>> {
+    parsers::parse_trade_confirmation_all(xml)
+}
+
 /// Detect FLEX statement type from XML
 ///
 /// Examines the XML structure to determine whether it's an Activity FLEX
-/// or Trade Confirmation FLEX statement.
+/// or Trade Confirmation FLEX statement, using the `type` attribute (`AF` or
+/// `TCF`) of a `FlexQueryResponse` root.
 ///
 /// # Arguments
 ///
@@ -196,6 +468,39 @@ pub fn detect_statement_type(xml: &str) -> Result<StatementType> {
     version::detect_statement_type(xml)
 }
 
+/// Report which elements and attributes of a document the typed model covers
+///
+/// Lists, per element type, the attributes that map to typed fields and those
+/// that do not, plus sections and rows with no typed counterpart, all with
+/// occurrence counts. Values are not parsed.
+///
+/// # Arguments
+///
+/// * `xml` - XML string from IB Activity FLEX query
+///
+/// # Returns
+///
+/// * `Ok(SchemaReport)` - Coverage of the document by the typed model
+/// * `Err(ParseError)` - If the XML is malformed
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ib_flex::schema_report;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let xml = std::fs::read_to_string("statement.xml")?;
+/// let report = schema_report(&xml)?;
+///
+/// // Fail a CI job when IB adds columns or sections
+/// assert!(report.is_clean(), "schema drift:\n{}", report);
+/// # Ok(())
+/// # }
+/// ```
+pub fn schema_report(xml: &str) -> Result<SchemaReport> {
+    parsers::schema_report(xml)
+}
+
 /// FLEX statement type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StatementType {
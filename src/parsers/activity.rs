@@ -1,8 +1,14 @@
 //! Activity FLEX parser
 
 use crate::error::{ParseError, Result};
+use crate::parsers::date_format;
+use crate::parsers::location::{self, Origin};
+use crate::parsers::options::{ParseOptions, ParsedStatement};
+use crate::parsers::stream::{ActivityFlexStream, StatementResult};
 use crate::types::activity::FlexQueryResponse;
-use crate::types::ActivityFlexStatement;
+use crate::types::borrowed::FlexQueryResponseRef;
+use crate::types::{ActivityFlexStatement, ActivityFlexStatementRef};
+use crate::version;
 
 /// Parse an Activity FLEX XML statement
 ///
@@ -17,15 +23,14 @@ use crate::types::ActivityFlexStatement;
 ///
 /// # Errors
 ///
-/// Returns `ParseError` if XML is malformed, required fields are missing,
-/// or date/decimal formats are invalid.
+/// Returns `ParseError` if XML is malformed, the schema version is not
+/// supported, required fields are missing, or date/decimal formats are invalid.
 pub fn parse_activity_flex(xml: &str) -> Result<ActivityFlexStatement> {
     // Parse XML using quick-xml with serde
-    let response: FlexQueryResponse =
-        quick_xml::de::from_str(xml).map_err(|e| ParseError::XmlError {
-            message: format!("Failed to parse FLEX XML: {}", e),
-            location: None,
-        })?;
+    version::check_version(xml)?;
+    let response: FlexQueryResponse = date_format::parse_with(xml, None, || {
+        location::deserialize(xml, &Origin::default(), "FLEX XML")
+    })?;
 
     // Extract the first statement
     let statement = response
@@ -36,6 +41,7 @@ pub fn parse_activity_flex(xml: &str) -> Result<ActivityFlexStatement> {
         .ok_or_else(|| ParseError::MissingField {
             field: "FlexStatement".to_string(),
             context: "FlexQueryResponse".to_string(),
+            location: None,
         })?;
 
     Ok(statement)
@@ -57,19 +63,158 @@ pub fn parse_activity_flex(xml: &str) -> Result<ActivityFlexStatement> {
 ///
 /// # Errors
 ///
-/// Returns `ParseError` if XML is malformed, required fields are missing,
-/// or date/decimal formats are invalid.
+/// Returns `ParseError` if XML is malformed, the schema version is not
+/// supported, required fields are missing, or date/decimal formats are invalid.
 pub fn parse_activity_flex_all(xml: &str) -> Result<Vec<ActivityFlexStatement>> {
     // Parse XML using quick-xml with serde
-    let response: FlexQueryResponse =
-        quick_xml::de::from_str(xml).map_err(|e| ParseError::XmlError {
-            message: format!("Failed to parse FLEX XML: {}", e),
+    version::check_version(xml)?;
+    let response: FlexQueryResponse = date_format::parse_with(xml, None, || {
+        location::deserialize(xml, &Origin::default(), "FLEX XML")
+    })?;
+
+    Ok(response.statements.statements)
+}
+
+/// Parse the first Activity FLEX statement, borrowing text from `xml`
+///
+/// # Arguments
+///
+/// * `xml` - XML string from IB Activity FLEX query
+///
+/// # Returns
+///
+/// * `Ok(ActivityFlexStatementRef)` - Trades, positions and cash transactions
+///   of the first statement
+/// * `Err(ParseError)` - Parse error with context
+///
+/// # Errors
+///
+/// Returns `ParseError` for the same reasons as [`parse_activity_flex`],
+/// limited to the sections the borrowed statement holds.
+pub fn parse_activity_flex_borrowed(xml: &str) -> Result<ActivityFlexStatementRef<'_>> {
+    parse_activity_flex_all_borrowed(xml)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| ParseError::MissingField {
+            field: "FlexStatement".to_string(),
+            context: "FlexQueryResponse".to_string(),
             location: None,
-        })?;
+        })
+}
+
+/// Parse all Activity FLEX statements, borrowing text from `xml`
+///
+/// # Arguments
+///
+/// * `xml` - XML string from IB Activity FLEX query
+///
+/// # Returns
+///
+/// * `Ok(Vec<ActivityFlexStatementRef>)` - All statements
+/// * `Err(ParseError)` - Parse error with context
+///
+/// # Errors
+///
+/// Returns `ParseError` for the same reasons as [`parse_activity_flex_all`],
+/// limited to the sections the borrowed statements hold.
+pub fn parse_activity_flex_all_borrowed(xml: &str) -> Result<Vec<ActivityFlexStatementRef<'_>>> {
+    version::check_version(xml)?;
+    let response: FlexQueryResponseRef<'_> = date_format::parse_with(xml, None, || {
+        location::deserialize(xml, &Origin::default(), "FLEX XML")
+    })?;
 
     Ok(response.statements.statements)
 }
 
+/// Parse the first Activity FLEX statement with explicit options
+///
+/// # Arguments
+///
+/// * `xml` - XML string from IB Activity FLEX query
+/// * `options` - Strict or lenient handling of invalid values
+///
+/// # Returns
+///
+/// * `Ok(ParsedStatement)` - The statement and any warnings raised
+/// * `Err(ParseError)` - Parse error with context
+///
+/// # Errors
+///
+/// Returns `ParseError` if XML is malformed, the document has no statement,
+/// a required field is missing or invalid, or (in strict mode) any value is
+/// invalid.
+pub fn parse_activity_flex_with_options(
+    xml: &str,
+    options: &ParseOptions,
+) -> Result<ParsedStatement> {
+    let mut stream = ActivityFlexStream::with_options(xml.as_bytes(), options.clone());
+    let outcome = stream
+        .next_result()
+        .ok_or_else(|| ParseError::MissingField {
+            field: "FlexStatement".to_string(),
+            context: "FlexQueryResponse".to_string(),
+            location: None,
+        })?;
+
+    outcome.result.map(|statement| ParsedStatement {
+        statement,
+        warnings: outcome.warnings,
+    })
+}
+
+/// Parse all Activity FLEX statements with explicit options
+///
+/// # Arguments
+///
+/// * `xml` - XML string from IB Activity FLEX query
+/// * `options` - Strict or lenient handling of invalid values
+///
+/// # Returns
+///
+/// * `Ok(Vec<ParsedStatement>)` - All statements with their warnings
+/// * `Err(ParseError)` - The first error encountered
+///
+/// # Errors
+///
+/// Returns `ParseError` if XML is malformed, a required field is missing or
+/// invalid, or (in strict mode) any value is invalid.
+pub fn parse_activity_flex_all_with_options(
+    xml: &str,
+    options: &ParseOptions,
+) -> Result<Vec<ParsedStatement>> {
+    let mut stream = ActivityFlexStream::with_options(xml.as_bytes(), options.clone());
+    std::iter::from_fn(|| stream.next_result())
+        .map(|outcome| {
+            outcome.result.map(|statement| ParsedStatement {
+                statement,
+                warnings: outcome.warnings,
+            })
+        })
+        .collect()
+}
+
+/// Parse each Activity FLEX statement independently
+///
+/// Returns one [`StatementResult`] per `<FlexStatement>`, tagged with its
+/// index, account and date range. A statement that fails to parse does not
+/// affect the others, so a single bad day in a long backfill can be
+/// quarantined while the rest are loaded.
+///
+/// # Arguments
+///
+/// * `xml` - XML string from IB Activity FLEX query
+///
+/// # Returns
+///
+/// * `Vec<StatementResult>` - One entry per statement, in document order
+///
+/// Malformed XML that hides the remaining statement boundaries produces a
+/// final entry holding the error, after which no further statements are read.
+pub fn parse_activity_flex_isolated(xml: &str) -> Vec<StatementResult> {
+    let mut stream = ActivityFlexStream::new(xml.as_bytes());
+    std::iter::from_fn(|| stream.next_result()).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,4 +236,17 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_parse_isolated_backfill() {
+        let xml = include_str!("../../tests/fixtures/activity_backfill.xml");
+        let results = parse_activity_flex_isolated(xml);
+
+        assert_eq!(results.len(), 3);
+        for (i, outcome) in results.iter().enumerate() {
+            assert_eq!(outcome.index, i);
+            assert_eq!(outcome.account_id.as_deref(), Some("U1234567"));
+            assert!(outcome.result.is_ok());
+        }
+    }
 }
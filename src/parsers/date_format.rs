@@ -0,0 +1,567 @@
+//! Date and time format profiles
+//!
+//! A Flex Query definition chooses how dates and times are written
+//! ("Date Format" and "Time Format" in Client Portal). The default
+//! `yyyyMMdd`/`yyyy-MM-dd` formats are always understood. Queries using one of
+//! the other formats need a [`DateFormatProfile`], either passed explicitly via
+//! [`ParseOptions::with_date_format`](crate::parsers::ParseOptions::with_date_format)
+//! or detected from the statement when parsing with the default formats fails.
+//!
+//! The serde deserializers in [`xml_utils`](crate::parsers::xml_utils) have no
+//! access to parse options, so the profile is made available to them for the
+//! duration of a parse through a thread-local.
+
+use std::fmt;
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+use crate::error::{ParseError, Result};
+use crate::parsers::location;
+use crate::parsers::scope::Scope;
+
+/// Date format of a Flex Query ("Date Format" setting)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DateFormat {
+    /// `yyyyMMdd`
+    #[default]
+    Compact,
+    /// `yyyy-MM-dd`
+    Iso,
+    /// `MM/dd/yyyy`
+    MonthDayYear,
+    /// `MM/dd/yy`
+    MonthDayShortYear,
+    /// `dd/MM/yyyy`
+    DayMonthYear,
+    /// `dd/MM/yy`
+    DayMonthShortYear,
+    /// `dd-MMM-yy` (e.g. `15-Jan-25`)
+    DayMonthNameShortYear,
+}
+
+impl DateFormat {
+    /// The format as written in the Flex Query settings
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            DateFormat::Compact => "yyyyMMdd",
+            DateFormat::Iso => "yyyy-MM-dd",
+            DateFormat::MonthDayYear => "MM/dd/yyyy",
+            DateFormat::MonthDayShortYear => "MM/dd/yy",
+            DateFormat::DayMonthYear => "dd/MM/yyyy",
+            DateFormat::DayMonthShortYear => "dd/MM/yy",
+            DateFormat::DayMonthNameShortYear => "dd-MMM-yy",
+        }
+    }
+
+    /// Equivalent chrono format string
+    fn chrono_format(&self) -> &'static str {
+        match self {
+            DateFormat::Compact => "%Y%m%d",
+            DateFormat::Iso => "%Y-%m-%d",
+            DateFormat::MonthDayYear => "%m/%d/%Y",
+            DateFormat::MonthDayShortYear => "%m/%d/%y",
+            DateFormat::DayMonthYear => "%d/%m/%Y",
+            DateFormat::DayMonthShortYear => "%d/%m/%y",
+            DateFormat::DayMonthNameShortYear => "%d-%b-%y",
+        }
+    }
+
+    /// Parse a date written in this format
+    pub fn parse(&self, s: &str) -> std::result::Result<NaiveDate, chrono::ParseError> {
+        NaiveDate::parse_from_str(s, self.chrono_format())
+    }
+}
+
+impl fmt::Display for DateFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.pattern())
+    }
+}
+
+/// Time format of a Flex Query ("Time Format" setting)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TimeFormat {
+    /// `HHmmss`
+    #[default]
+    Compact,
+    /// `HH:mm:ss`
+    Colon,
+    /// `HH:mm`
+    HourMinute,
+}
+
+impl TimeFormat {
+    /// The format as written in the Flex Query settings
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            TimeFormat::Compact => "HHmmss",
+            TimeFormat::Colon => "HH:mm:ss",
+            TimeFormat::HourMinute => "HH:mm",
+        }
+    }
+
+    /// Parse a time written in this format
+    pub fn parse(&self, s: &str) -> std::result::Result<NaiveTime, chrono::ParseError> {
+        let format = match self {
+            TimeFormat::Compact => "%H%M%S",
+            TimeFormat::Colon => "%H:%M:%S",
+            TimeFormat::HourMinute => "%H:%M",
+        };
+        NaiveTime::parse_from_str(s, format)
+    }
+}
+
+impl fmt::Display for TimeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.pattern())
+    }
+}
+
+/// Date and time formats used by a Flex Query
+///
+/// The default `yyyyMMdd`/`yyyy-MM-dd` dates and `HHmmss`/`HH:mm:ss` times
+/// are accepted under every profile, so a profile only has to describe the
+/// query's non-default formats.
+///
+/// # Example
+///
+/// ```rust
+/// use ib_flex::parsers::{DateFormat, DateFormatProfile, ParseOptions};
+///
+/// let profile = DateFormatProfile::new(DateFormat::DayMonthYear);
+/// assert_eq!(profile.parse_date("15/01/2025").unwrap().to_string(), "2025-01-15");
+///
+/// let options = ParseOptions::default().with_date_format(profile);
+/// assert_eq!(options.date_format, Some(profile));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DateFormatProfile {
+    /// Date format
+    pub date: DateFormat,
+    /// Time format
+    pub time: TimeFormat,
+}
+
+impl DateFormatProfile {
+    /// Profile with the given date format and the default time format
+    pub fn new(date: DateFormat) -> Self {
+        DateFormatProfile {
+            date,
+            time: TimeFormat::default(),
+        }
+    }
+
+    /// Set the time format
+    pub fn with_time(mut self, time: TimeFormat) -> Self {
+        self.time = time;
+        self
+    }
+
+    /// Parse a date in this profile's format or one of the default formats
+    pub fn parse_date(&self, s: &str) -> std::result::Result<NaiveDate, chrono::ParseError> {
+        self.date.parse(s).or_else(|e| {
+            DateFormat::Iso
+                .parse(s)
+                .or_else(|_| DateFormat::Compact.parse(s))
+                .map_err(|_| e)
+        })
+    }
+
+    /// Parse a time in this profile's format or one of the default formats
+    pub fn parse_time(&self, s: &str) -> std::result::Result<NaiveTime, chrono::ParseError> {
+        self.time.parse(s).or_else(|e| {
+            TimeFormat::Compact
+                .parse(s)
+                .or_else(|_| TimeFormat::Colon.parse(s))
+                .map_err(|_| e)
+        })
+    }
+
+    /// Parse an IB timestamp such as `01/15/2025;093015`
+    ///
+    /// Date and time may be separated by `;`, a space or `T`. A date without
+    /// a time is midnight.
+    pub fn parse_datetime(
+        &self,
+        s: &str,
+    ) -> std::result::Result<NaiveDateTime, chrono::ParseError> {
+        let (date, time) = split_datetime(s.trim());
+        let date = self.parse_date(date)?;
+        if time.is_empty() {
+            return Ok(date.and_time(NaiveTime::MIN));
+        }
+        Ok(date.and_time(self.parse_time(time)?))
+    }
+
+    /// Detect the date format of a FLEX document from its date attributes
+    ///
+    /// Returns `Ok(None)` when the document only uses the default formats.
+    /// `MM/dd` and `dd/MM` orders are told apart by day numbers above 12, or
+    /// else by which order keeps the statement dates consistent (`fromDate` ≤
+    /// `toDate` ≤ `whenGenerated`, trade and report dates inside the period).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError::InvalidDate`] if both orders fit the document
+    /// equally well, or if the document mixes them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ib_flex::parsers::{DateFormat, DateFormatProfile};
+    ///
+    /// let xml = r#"<FlexStatement fromDate="01/02/2025" toDate="28/02/2025" />"#;
+    /// let profile = DateFormatProfile::detect(xml).unwrap().unwrap();
+    /// assert_eq!(profile.date, DateFormat::DayMonthYear);
+    /// ```
+    pub fn detect(xml: &str) -> Result<Option<Self>> {
+        let mut samples = DateSamples::default();
+        location::for_each_element(xml, |ctx, e| {
+            if ctx.name == "FlexStatement" {
+                samples.statements.push(StatementDates::default());
+            }
+            for attr in e.attributes().with_checks(false).filter_map(|a| a.ok()) {
+                let name = String::from_utf8_lossy(attr.key.as_ref());
+                if !is_date_attribute(&name) {
+                    continue;
+                }
+                if let Ok(value) = attr.unescape_value() {
+                    samples.add(&name, &value);
+                }
+            }
+        });
+
+        samples.resolve()
+    }
+}
+
+impl fmt::Display for DateFormatProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.date, self.time)
+    }
+}
+
+/// Profile of the running [`Scope`]
+pub(crate) fn active() -> Option<DateFormatProfile> {
+    Scope::current().profile
+}
+
+/// Run `f` in a scope with `profile` available to the date deserializers
+pub(crate) fn with_profile<T>(profile: Option<DateFormatProfile>, f: impl FnOnce() -> T) -> T {
+    Scope {
+        profile,
+        ..Scope::current()
+    }
+    .run(f)
+    .0
+}
+
+/// Run `parse` with `profile`, detecting a profile from `xml` if none was
+/// given and parsing with the default formats fails on a date
+///
+/// Any other failure, or a date failure no profile can be detected for, is
+/// returned as is.
+pub(crate) fn parse_with<T>(
+    xml: &str,
+    profile: Option<DateFormatProfile>,
+    mut parse: impl FnMut() -> Result<T>,
+) -> Result<T> {
+    let result = with_profile(profile, &mut parse);
+    if profile.is_some() || !matches!(result, Err(ParseError::InvalidDate { .. })) {
+        return result;
+    }
+
+    match DateFormatProfile::detect(xml) {
+        Ok(Some(detected)) => with_profile(Some(detected), parse),
+        Ok(None) | Err(_) => result,
+    }
+}
+
+/// Split an IB timestamp into date and time parts
+///
+/// `T` only counts as a separator after a digit so that month names such as
+/// `OCT` in `dd-MMM-yy` dates are left alone.
+pub(crate) fn split_datetime(s: &str) -> (&str, &str) {
+    if let Some(split) = s.split_once([';', ' ']) {
+        return split;
+    }
+    let bytes = s.as_bytes();
+    match s.find('T') {
+        Some(i) if i > 0 && bytes[i - 1].is_ascii_digit() => (&s[..i], &s[i + 1..]),
+        _ => (s, ""),
+    }
+}
+
+/// Whether an attribute holds a date or timestamp
+fn is_date_attribute(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    lower.contains("date")
+        || lower.contains("time")
+        || lower.starts_with("when")
+        || lower == "expiry"
+}
+
+/// A `a/b/y` date whose day/month order is not yet known
+#[derive(Debug, Clone, Copy)]
+struct SlashDate {
+    first: u32,
+    second: u32,
+    year: i32,
+}
+
+impl SlashDate {
+    fn parse(s: &str) -> Option<(Self, bool)> {
+        let mut parts = s.split('/');
+        let (first, second, year) = (parts.next()?, parts.next()?, parts.next()?);
+        if parts.next().is_some() || first.len() > 2 || second.len() > 2 {
+            return None;
+        }
+        let short_year = match year.len() {
+            2 => true,
+            4 => false,
+            _ => return None,
+        };
+        let year: i32 = year.parse().ok()?;
+        Some((
+            SlashDate {
+                first: first.parse().ok()?,
+                second: second.parse().ok()?,
+                // Same pivot as chrono's %y
+                year: match (short_year, year) {
+                    (true, 0..=68) => 2000 + year,
+                    (true, _) => 1900 + year,
+                    (false, _) => year,
+                },
+            },
+            short_year,
+        ))
+    }
+
+    fn date(&self, month_first: bool) -> Option<NaiveDate> {
+        let (month, day) = match month_first {
+            true => (self.first, self.second),
+            false => (self.second, self.first),
+        };
+        NaiveDate::from_ymd_opt(self.year, month, day)
+    }
+}
+
+/// Slash dates of one statement, grouped by role
+#[derive(Debug, Default)]
+struct StatementDates {
+    from: Option<SlashDate>,
+    to: Option<SlashDate>,
+    generated: Option<SlashDate>,
+    /// `tradeDate` and `reportDate` values, which fall inside the period
+    in_period: Vec<SlashDate>,
+    other: Vec<SlashDate>,
+}
+
+impl StatementDates {
+    fn all(&self) -> impl Iterator<Item = &SlashDate> {
+        self.from
+            .iter()
+            .chain(&self.to)
+            .chain(&self.generated)
+            .chain(&self.in_period)
+            .chain(&self.other)
+    }
+
+    /// Number of inconsistencies when reading the dates in the given order,
+    /// or `None` if some value is not a valid date in that order
+    fn violations(&self, month_first: bool) -> Option<usize> {
+        let date = |d: &SlashDate| d.date(month_first);
+        for d in self.all() {
+            date(d)?;
+        }
+
+        let from = self.from.as_ref().and_then(date);
+        let to = self.to.as_ref().and_then(date);
+        let generated = self.generated.as_ref().and_then(date);
+
+        let mut violations = 0;
+        if let (Some(from), Some(to)) = (from, to) {
+            violations += usize::from(from > to);
+        }
+        if let (Some(to), Some(generated)) = (to, generated) {
+            violations += usize::from(to > generated);
+        }
+        for d in self.in_period.iter().filter_map(date) {
+            violations += usize::from(from.is_some_and(|f| d < f) || to.is_some_and(|t| d > t));
+        }
+        Some(violations)
+    }
+}
+
+/// Date values collected from a document
+#[derive(Debug, Default)]
+struct DateSamples {
+    statements: Vec<StatementDates>,
+    month_names: bool,
+    short_year: Option<bool>,
+}
+
+impl DateSamples {
+    fn add(&mut self, name: &str, value: &str) {
+        let (date, _) = split_datetime(value.trim());
+        if DateFormat::DayMonthNameShortYear.parse(date).is_ok() {
+            self.month_names = true;
+            return;
+        }
+        let Some((slash, short_year)) = SlashDate::parse(date) else {
+            return;
+        };
+        self.short_year.get_or_insert(short_year);
+
+        if self.statements.is_empty() {
+            self.statements.push(StatementDates::default());
+        }
+        let dates = self.statements.last_mut().expect("at least one statement");
+        match name {
+            "fromDate" if dates.from.is_none() => dates.from = Some(slash),
+            "toDate" if dates.to.is_none() => dates.to = Some(slash),
+            "whenGenerated" if dates.generated.is_none() => dates.generated = Some(slash),
+            "tradeDate" | "reportDate" => dates.in_period.push(slash),
+            _ => dates.other.push(slash),
+        }
+    }
+
+    fn resolve(&self) -> Result<Option<DateFormatProfile>> {
+        let Some(short_year) = self.short_year else {
+            return Ok(self
+                .month_names
+                .then(|| DateFormatProfile::new(DateFormat::DayMonthNameShortYear)));
+        };
+        let format = |month_first| match (month_first, short_year) {
+            (true, false) => DateFormat::MonthDayYear,
+            (true, true) => DateFormat::MonthDayShortYear,
+            (false, false) => DateFormat::DayMonthYear,
+            (false, true) => DateFormat::DayMonthShortYear,
+        };
+
+        let score = |month_first| {
+            self.statements
+                .iter()
+                .map(|s| s.violations(month_first))
+                .sum::<Option<usize>>()
+        };
+        let month_first = match (score(true), score(false)) {
+            (Some(mdy), Some(dmy)) if mdy < dmy => true,
+            (Some(mdy), Some(dmy)) if dmy < mdy => false,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (Some(_), Some(_)) if self.order_irrelevant() => true,
+            (Some(_), Some(_)) => {
+                return Err(ambiguous(format!(
+                    "dates fit both {} and {}",
+                    format(true),
+                    format(false)
+                )))
+            }
+            (None, None) => {
+                return Err(ambiguous(format!(
+                    "dates fit neither {} nor {}",
+                    format(true),
+                    format(false)
+                )))
+            }
+        };
+
+        Ok(Some(DateFormatProfile::new(format(month_first))))
+    }
+
+    /// Whether every slash date reads the same in both orders
+    fn order_irrelevant(&self) -> bool {
+        self.statements
+            .iter()
+            .flat_map(StatementDates::all)
+            .all(|d| d.first == d.second)
+    }
+}
+
+fn ambiguous(reason: String) -> ParseError {
+    ParseError::InvalidDate {
+        message: format!(
+            "cannot determine the statement's date format: {}; pass a DateFormatProfile",
+            reason
+        ),
+        location: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(attrs: &str, rows: &str) -> Result<Option<DateFormatProfile>> {
+        DateFormatProfile::detect(&format!(
+            "<FlexStatement {}><Trades>{}</Trades></FlexStatement>",
+            attrs, rows
+        ))
+    }
+
+    #[test]
+    fn test_detect_default_formats() {
+        let found = detect(r#"fromDate="2025-01-02" toDate="20250103""#, "").unwrap();
+        assert_eq!(found, None);
+    }
+
+    #[test]
+    fn test_detect_day_above_twelve() {
+        let found = detect(r#"fromDate="01/02/2025" toDate="13/02/2025""#, "").unwrap();
+        assert_eq!(found.unwrap().date, DateFormat::DayMonthYear);
+
+        let found = detect(r#"fromDate="02/13/25" toDate="02/14/25""#, "").unwrap();
+        assert_eq!(found.unwrap().date, DateFormat::MonthDayShortYear);
+    }
+
+    #[test]
+    fn test_detect_by_statement_consistency() {
+        // 01/02 - 03/02: as MM/dd that is Jan 2 - Mar 2, a trade on 02/02 fits;
+        // as dd/MM it is Feb 1 - Feb 3 and 02/02 also fits, but generation on
+        // 04/01 (Apr 1 vs Jan 4) only works month-first.
+        let found = detect(
+            r#"fromDate="01/02/2025" toDate="03/02/2025" whenGenerated="04/01/2025;120000""#,
+            r#"<Trade tradeDate="02/02/2025" />"#,
+        )
+        .unwrap();
+        assert_eq!(found.unwrap().date, DateFormat::MonthDayYear);
+    }
+
+    #[test]
+    fn test_detect_undecidable() {
+        let err = detect(r#"fromDate="01/02/2025" toDate="01/02/2025""#, "").unwrap_err();
+        assert!(matches!(err, ParseError::InvalidDate { .. }), "{:?}", err);
+
+        // Same day and month reads identically either way
+        let found = detect(r#"fromDate="03/03/2025" toDate="03/03/2025""#, "").unwrap();
+        assert_eq!(found.unwrap().date, DateFormat::MonthDayYear);
+    }
+
+    #[test]
+    fn test_detect_month_names() {
+        let found = detect(r#"fromDate="15-Oct-24" toDate="16-OCT-24""#, "").unwrap();
+        assert_eq!(found.unwrap().date, DateFormat::DayMonthNameShortYear);
+    }
+
+    #[test]
+    fn test_profile_parse_datetime() {
+        let profile =
+            DateFormatProfile::new(DateFormat::DayMonthNameShortYear).with_time(TimeFormat::Colon);
+        assert_eq!(
+            profile
+                .parse_datetime("15-OCT-24;09:30:00")
+                .unwrap()
+                .to_string(),
+            "2024-10-15 09:30:00"
+        );
+        // Default formats are still accepted
+        assert_eq!(
+            profile
+                .parse_datetime("2024-10-15T093000")
+                .unwrap()
+                .to_string(),
+            "2024-10-15 09:30:00"
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! Auto-detecting FLEX document parser
+
+use crate::error::{ParseError, Result};
+use crate::parsers::date_format;
+use crate::parsers::location::{self, Origin};
+use crate::parsers::scope;
+use crate::types::activity::FlexQueryResponse;
+use crate::types::trade_confirmation::TradeConfirmationResponse;
+use crate::types::{ActivityFlexStatement, TradeConfirmationStatement};
+use crate::version;
+use crate::StatementType;
+
+/// Metadata of the `<FlexQueryResponse>` envelope
+///
+/// Documents rooted directly at a statement element have no envelope, so all
+/// fields are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FlexEnvelope {
+    /// Name of the Flex Query (`queryName`)
+    pub query_name: Option<String>,
+
+    /// Query type (`type`), e.g. `AF` or `TCF`
+    pub query_type: Option<String>,
+
+    /// Number of statements declared by `<FlexStatements count="...">`
+    pub declared_count: Option<usize>,
+}
+
+/// A parsed FLEX document of either statement type
+#[derive(Debug, Clone, PartialEq)]
+pub enum FlexDocument {
+    /// Activity FLEX (`type="AF"`)
+    Activity {
+        /// Response metadata
+        envelope: FlexEnvelope,
+        /// All statements, in document order
+        statements: Vec<ActivityFlexStatement>,
+    },
+    /// Trade Confirmation FLEX (`type="TCF"`)
+    TradeConfirmation {
+        /// Response metadata
+        envelope: FlexEnvelope,
+        /// All statements, in document order
+        statements: Vec<TradeConfirmationStatement>,
+    },
+}
+
+impl FlexDocument {
+    /// Statement type of the document
+    pub fn statement_type(&self) -> StatementType {
+        match self {
+            FlexDocument::Activity { .. } => StatementType::Activity,
+            FlexDocument::TradeConfirmation { .. } => StatementType::TradeConfirmation,
+        }
+    }
+
+    /// Response metadata
+    pub fn envelope(&self) -> &FlexEnvelope {
+        match self {
+            FlexDocument::Activity { envelope, .. }
+            | FlexDocument::TradeConfirmation { envelope, .. } => envelope,
+        }
+    }
+
+    /// Name of the Flex Query, if the document has an envelope
+    pub fn query_name(&self) -> Option<&str> {
+        self.envelope().query_name.as_deref()
+    }
+
+    /// Query type (`AF` or `TCF`), if the document has an envelope
+    pub fn query_type(&self) -> Option<&str> {
+        self.envelope().query_type.as_deref()
+    }
+
+    /// Statement count declared by the envelope
+    pub fn declared_count(&self) -> Option<usize> {
+        self.envelope().declared_count
+    }
+
+    /// Number of statements parsed
+    pub fn len(&self) -> usize {
+        match self {
+            FlexDocument::Activity { statements, .. } => statements.len(),
+            FlexDocument::TradeConfirmation { statements, .. } => statements.len(),
+        }
+    }
+
+    /// Whether the document holds no statements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Parse a FLEX document of either type
+///
+/// Detects the statement type with [`crate::detect_statement_type`] and keeps
+/// the `FlexQueryResponse` envelope. The declared statement count is checked
+/// against the number of statements parsed.
+///
+/// # Arguments
+///
+/// * `xml` - XML string from any IB FLEX query
+///
+/// # Returns
+///
+/// * `Ok(FlexDocument)` - All statements with the response metadata
+/// * `Err(ParseError)` - Parse error with context
+///
+/// # Errors
+///
+/// Returns [`ParseError::StatementCountMismatch`] if `<FlexStatements count>`
+/// disagrees with the statements found, and `ParseError` for the same reasons
+/// as the type-specific parsers.
+pub fn parse_flex(xml: &str) -> Result<FlexDocument> {
+    version::check_version(xml)?;
+    let has_envelope = version::root_element(xml)?
+        .is_some_and(|root| root.name().as_ref() == b"FlexQueryResponse");
+
+    let document = match version::detect_statement_type(xml)? {
+        StatementType::Activity if has_envelope => {
+            let response: FlexQueryResponse = deserialize(xml, "FLEX XML")?;
+            FlexDocument::Activity {
+                envelope: envelope(
+                    response.query_name,
+                    response.query_type,
+                    response.statements.count,
+                )?,
+                statements: response.statements.statements,
+            }
+        }
+        StatementType::Activity => {
+            let (statement, unknown_values) =
+                scope::collecting(|| deserialize::<ActivityFlexStatement>(xml, "FLEX XML"));
+            let mut statement = statement?;
+            statement.unknown_values = unknown_values;
+            FlexDocument::Activity {
+                envelope: FlexEnvelope::default(),
+                statements: vec![statement],
+            }
+        }
+        StatementType::TradeConfirmation if has_envelope => {
+            let response: TradeConfirmationResponse =
+                deserialize(xml, "Trade Confirmation FLEX XML")?;
+            FlexDocument::TradeConfirmation {
+                envelope: envelope(
+                    response.query_name,
+                    response.query_type,
+                    response.statements.count,
+                )?,
+                statements: response.statements.statements,
+            }
+        }
+        StatementType::TradeConfirmation => FlexDocument::TradeConfirmation {
+            envelope: FlexEnvelope::default(),
+            statements: vec![deserialize(xml, "Trade Confirmation FLEX XML")?],
+        },
+    };
+
+    match document.declared_count() {
+        Some(declared) if declared != document.len() => Err(ParseError::StatementCountMismatch {
+            declared,
+            parsed: document.len(),
+        }),
+        _ => Ok(document),
+    }
+}
+
+/// Deserialize the whole document with date-format detection
+fn deserialize<T: serde::de::DeserializeOwned>(xml: &str, document: &str) -> Result<T> {
+    date_format::parse_with(xml, None, || {
+        location::deserialize(xml, &Origin::default(), document)
+    })
+}
+
+/// Build the envelope, validating the `count` attribute
+fn envelope(
+    query_name: Option<String>,
+    query_type: Option<String>,
+    count: Option<String>,
+) -> Result<FlexEnvelope> {
+    let declared_count = count
+        .map(|c| {
+            c.trim().parse::<usize>().map_err(|_| ParseError::XmlError {
+                message: format!("Invalid FlexStatements count: '{}'", c),
+                location: None,
+            })
+        })
+        .transpose()?;
+
+    Ok(FlexEnvelope {
+        query_name,
+        query_type,
+        declared_count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_flex_activity_envelope() {
+        let xml = include_str!("../../tests/fixtures/activity_backfill.xml");
+        let document = parse_flex(xml).unwrap();
+
+        assert_eq!(document.statement_type(), StatementType::Activity);
+        assert_eq!(document.declared_count(), Some(3));
+        assert_eq!(document.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_flex_count_mismatch() {
+        let xml = r#"<FlexQueryResponse queryName="Confirms" type="TCF">
+    <FlexStatements count="2">
+        <FlexStatement accountId="U1234567"><TradeConfirms /></FlexStatement>
+    </FlexStatements>
+</FlexQueryResponse>"#;
+
+        match parse_flex(xml) {
+            Err(ParseError::StatementCountMismatch { declared, parsed }) => {
+                assert_eq!((declared, parsed), (2, 1));
+            }
+            other => panic!("expected StatementCountMismatch, got {:?}", other),
+        }
+
+        let document = parse_flex(&xml.replace(r#"count="2""#, r#"count="1""#)).unwrap();
+        assert_eq!(document.query_name(), Some("Confirms"));
+        assert_eq!(document.query_type(), Some("TCF"));
+        assert!(matches!(
+            document,
+            FlexDocument::TradeConfirmation { ref statements, .. } if statements[0].account_id == "U1234567"
+        ));
+    }
+
+    #[test]
+    fn test_parse_flex_without_envelope() {
+        let xml = r#"<TradeConfirmationStatement accountId="U1234567"><Trades /></TradeConfirmationStatement>"#;
+        let document = parse_flex(xml).unwrap();
+
+        assert_eq!(document.envelope(), &FlexEnvelope::default());
+        assert_eq!(document.len(), 1);
+    }
+}
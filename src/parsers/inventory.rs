@@ -0,0 +1,144 @@
+//! Inventory of unrecognized enum values and rejected values
+//!
+//! The enum deserializers in [`crate::types::common`] call [`record`] for every
+//! value they do not recognize. Values are noted in the running
+//! [`Scope`](crate::parsers::scope::Scope), collected per statement with
+//! [`scope::collecting`] and stored in
+//! [`ActivityFlexStatement::unknown_values`].
+//!
+//! In lenient scopes the optional value deserializers in
+//! [`crate::parsers::xml_utils`] call [`reject`] for values they cannot parse
+//! and yield `None`; the rejected values are noted for the warnings.
+
+use serde::{Deserialize, Deserializer};
+
+use crate::parsers::scope;
+use crate::types::{ActivityFlexStatement, UnknownValue};
+
+/// A value a lenient deserializer turned into `None`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Rejected {
+    /// The value as it appeared in the XML, unescaped
+    pub value: String,
+    /// Why it was rejected
+    pub reason: String,
+}
+
+/// Record an unrecognized `value` of `enum_name`, if a scope is running
+pub(crate) fn record(enum_name: &'static str, value: &str) {
+    if value.is_empty() {
+        return;
+    }
+    scope::noting(|_, noted| {
+        let values = &mut noted.unknown_values;
+        match values
+            .iter_mut()
+            .find(|v| v.enum_name == enum_name && v.value == value)
+        {
+            Some(v) => v.occurrences += 1,
+            None => values.push(UnknownValue {
+                enum_name,
+                value: value.to_string(),
+                occurrences: 1,
+            }),
+        }
+    });
+}
+
+/// Add `values` to `into`, summing the occurrences of values already listed
+pub(crate) fn merge(into: &mut Vec<UnknownValue>, values: Vec<UnknownValue>) {
+    for value in values {
+        match into
+            .iter_mut()
+            .find(|v| v.enum_name == value.enum_name && v.value == value.value)
+        {
+            Some(v) => v.occurrences += value.occurrences,
+            None => into.push(value),
+        }
+    }
+}
+
+/// Record `value` as rejected for `reason`; returns `false`, meaning the
+/// value must fail, unless the running scope is lenient
+pub(crate) fn reject(value: &str, reason: &str) -> bool {
+    scope::noting(|scope, noted| {
+        if scope.lenient {
+            noted.rejected.push(Rejected {
+                value: value.to_string(),
+                reason: reason.to_string(),
+            });
+        }
+        scope.lenient
+    })
+    .unwrap_or(false)
+}
+
+/// Deserialize `ActivityFlexStatement`s, filling in each statement's
+/// `unknown_values`
+pub(crate) fn deserialize_statements<'de, D>(
+    deserializer: D,
+) -> Result<Vec<ActivityFlexStatement>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    /// A statement with its unknown values collected
+    struct Collected(ActivityFlexStatement);
+
+    impl<'de> Deserialize<'de> for Collected {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let (statement, values) =
+                scope::collecting(|| ActivityFlexStatement::deserialize(deserializer));
+            let mut statement = statement?;
+            statement.unknown_values = values;
+            Ok(Collected(statement))
+        }
+    }
+
+    let statements = Vec::<Collected>::deserialize(deserializer)?;
+    Ok(statements.into_iter().map(|c| c.0).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::scope::{collecting, Scope};
+
+    #[test]
+    fn test_record_outside_collecting_is_ignored() {
+        record("AssetCategory", "XYZ");
+        let ((), values) = collecting(|| {
+            record("AssetCategory", "XYZ");
+            record("AssetCategory", "XYZ");
+            record("AssetCategory", "");
+            let ((), inner) = collecting(|| record("BuySell", "HOLD"));
+            assert_eq!(inner.len(), 1);
+        });
+
+        assert_eq!(
+            values,
+            vec![UnknownValue {
+                enum_name: "AssetCategory",
+                value: "XYZ".to_string(),
+                occurrences: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_reject_only_while_lenient() {
+        assert!(!reject("abc", "not a number"));
+        let lenient = Scope {
+            lenient: true,
+            ..Scope::default()
+        };
+        let ((), noted) = lenient.run(|| {
+            assert!(reject("abc", "not a number"));
+        });
+
+        assert_eq!(noted.rejected.len(), 1);
+        assert_eq!(noted.rejected[0].value, "abc");
+        assert!(!reject("abc", "not a number"));
+        let ((), noted) = Scope::default().run(|| assert!(!reject("abc", "not a number")));
+        assert!(noted.rejected.is_empty());
+    }
+}
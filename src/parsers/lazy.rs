@@ -0,0 +1,577 @@
+//! Lazy per-section Activity FLEX parsing
+//!
+//! [`LazyActivityStatement`] indexes the byte range of every section of a
+//! `<FlexStatement>` in one pass over the XML, without deserializing any rows.
+//! Each section is deserialized into its wrapper type on first access and
+//! cached, so callers that look at a few sections only pay for those.
+
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::ops::Range;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::de::DeserializeOwned;
+
+use crate::error::{ParseError, Result};
+use crate::parsers::date_format::DateFormatProfile;
+use crate::parsers::location::{self, Origin};
+use crate::parsers::scope::{self, Scope};
+use crate::parsers::{date_format, inventory, raw};
+use crate::types::activity::*;
+use crate::types::extended::{AccountInformation, ChangeInNAV};
+use crate::types::{RawElement, UnknownValue};
+use crate::version;
+
+/// An Activity FLEX statement whose sections are deserialized on first access
+///
+/// Borrows the XML it was built from. Statement attributes are parsed up
+/// front and available through [`LazyActivityStatement::header`]; every
+/// section accessor deserializes its section once and returns the cached
+/// value afterwards. A section missing from the XML yields its empty default,
+/// as with eager parsing. Errors are not cached, so a failing section fails
+/// again on the next access.
+///
+/// # Example
+///
+/// ```rust
+/// use ib_flex::parsers::LazyActivityStatement;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let xml = std::fs::read_to_string("tests/fixtures/activity_minimal.xml")?;
+/// let statement = LazyActivityStatement::parse(&xml)?;
+///
+/// println!("{}: {} trades", statement.header().account_id, statement.trades()?.items.len());
+/// # Ok(())
+/// # }
+/// ```
+pub struct LazyActivityStatement<'a> {
+    /// The whole document
+    xml: &'a str,
+    /// Position of the statement in the document
+    index: usize,
+    /// Statement attributes, with every section empty
+    header: ActivityFlexStatement,
+    /// Date format detected from the whole statement, shared by its sections
+    date_format: Option<DateFormatProfile>,
+    /// Sections in document order, by element name
+    sections: SectionRanges<'a>,
+    /// Position in `sections` of the first section with each name
+    by_name: HashMap<&'a str, usize>,
+    cache: Cache,
+    unknown_sections: OnceCell<Vec<RawElement>>,
+}
+
+/// Element names and byte ranges of the sections of a statement
+type SectionRanges<'a> = Vec<(&'a str, Range<usize>)>;
+
+/// A deserialized section with the unknown enum values seen in it
+struct Section<T> {
+    value: T,
+    unknown_values: Vec<UnknownValue>,
+}
+
+impl<'a> LazyActivityStatement<'a> {
+    /// Index the first statement of an Activity FLEX document
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the XML is malformed, the schema version is not
+    /// supported, the document has no statement, or the statement attributes
+    /// are missing or invalid.
+    pub fn parse(xml: &'a str) -> Result<Self> {
+        index(xml, Some(1))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| ParseError::MissingField {
+                field: "FlexStatement".to_string(),
+                context: "FlexQueryResponse".to_string(),
+                location: None,
+            })
+    }
+
+    /// Index every statement of an Activity FLEX document
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the XML is malformed, the schema version is not
+    /// supported, or the attributes of any statement are missing or invalid.
+    pub fn parse_all(xml: &'a str) -> Result<Vec<Self>> {
+        index(xml, None)
+    }
+
+    /// Statement attributes (account, dates, period); its sections are empty
+    pub fn header(&self) -> &ActivityFlexStatement {
+        &self.header
+    }
+
+    /// Element names of the sections present, in document order
+    pub fn section_names(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.sections.iter().map(|(name, _)| *name)
+    }
+
+    /// Sections this crate does not model, as raw elements in document order
+    pub fn unknown_sections(&self) -> &[RawElement] {
+        self.unknown_sections.get_or_init(|| {
+            let mut statement = self.header.clone();
+            for (name, range) in &self.sections {
+                if !is_known(name) {
+                    raw::capture_section(&self.xml[range.clone()], &mut statement);
+                }
+            }
+            statement.unknown_sections
+        })
+    }
+
+    /// Unknown enum values of the statement attributes and of the sections
+    /// deserialized so far
+    pub fn unknown_values(&self) -> Vec<UnknownValue> {
+        let mut values = self.header.unknown_values.clone();
+        for (name, _) in self.distinct_sections() {
+            inventory::merge(&mut values, self.cache.unknown_values(name));
+        }
+        values
+    }
+
+    /// Deserialize the remaining sections into a complete statement
+    ///
+    /// The result equals what [`crate::parse_activity_flex`] returns for the
+    /// same statement.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of a section that fails to deserialize.
+    pub fn into_statement(mut self) -> Result<ActivityFlexStatement> {
+        let mut cache = std::mem::take(&mut self.cache);
+        let mut statement = self.header.clone();
+        for (name, _) in self.distinct_sections() {
+            cache.take_into(&self, name, &mut statement)?;
+        }
+        Ok(statement)
+    }
+
+    /// The first section with each name, in document order
+    fn distinct_sections(&self) -> impl Iterator<Item = (&'a str, &Range<usize>)> + '_ {
+        self.sections
+            .iter()
+            .enumerate()
+            .filter(|(position, (name, _))| self.by_name.get(name) == Some(position))
+            .map(|(_, (name, range))| (*name, range))
+    }
+
+    /// Cached section, deserialized on first access
+    fn section<'s, T: DeserializeOwned + Default>(
+        &self,
+        cell: &'s OnceCell<Section<T>>,
+        name: &str,
+    ) -> Result<&'s T> {
+        if let Some(section) = cell.get() {
+            return Ok(&section.value);
+        }
+        let section = self.load(name)?;
+        Ok(&cell.get_or_init(|| section).value)
+    }
+
+    /// Deserialize section `name`
+    fn load<T: DeserializeOwned + Default>(&self, name: &str) -> Result<Section<T>> {
+        let Some(range) = self
+            .by_name
+            .get(name)
+            .map(|&position| self.sections[position].1.clone())
+        else {
+            return Ok(Section {
+                value: T::default(),
+                unknown_values: Vec::new(),
+            });
+        };
+
+        let xml = &self.xml[range.clone()];
+        let origin = Origin::at(self.xml, range.start, Some(self.index));
+        let scope = Scope {
+            profile: self.date_format,
+            ..Scope::default()
+        };
+        let (value, noted) = scope.run(|| location::deserialize::<T>(xml, &origin, "FLEX XML"));
+        Ok(Section {
+            value: value?,
+            unknown_values: noted.unknown_values,
+        })
+    }
+}
+
+/// Index the statements of `xml`, stopping after `limit` statements if given
+fn index(xml: &str, limit: Option<usize>) -> Result<Vec<LazyActivityStatement<'_>>> {
+    version::check_version(xml)?;
+    let mut reader = Reader::from_str(xml);
+    let mut statements = Vec::new();
+    // Start tag of the statement being indexed, and its sections so far
+    let mut current: Option<(Range<usize>, SectionRanges<'_>)> = None;
+
+    loop {
+        if limit.is_some_and(|limit| statements.len() >= limit) {
+            break;
+        }
+        let event = reader.read_event().map_err(|e| ParseError::XmlError {
+            message: format!("Failed to read FLEX XML: {}", e),
+            location: Some(Box::new(location::syntax_error_location(
+                xml,
+                &Origin::default(),
+            ))),
+        })?;
+        let end = reader.buffer_position() as usize;
+
+        let (e, empty) = match event {
+            Event::Start(e) => (e, false),
+            Event::Empty(e) => (e, true),
+            Event::End(e) if e.name().as_ref() == b"FlexStatement" => {
+                if let Some((tag, sections)) = current.take() {
+                    let range = tag.start..end;
+                    statements.push(statement(xml, statements.len(), range, tag, sections)?);
+                }
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+        let start = end - e.len() - if empty { 3 } else { 2 };
+        let name = &xml[start + 1..start + 1 + e.name().as_ref().len()];
+
+        match &mut current {
+            None if name == "FlexStatement" && empty => {
+                let tag = start..end;
+                statements.push(statement(
+                    xml,
+                    statements.len(),
+                    tag.clone(),
+                    tag,
+                    Vec::new(),
+                )?);
+            }
+            None if name == "FlexStatement" => current = Some((start..end, Vec::new())),
+            None => {}
+            Some((_, sections)) => {
+                if !empty {
+                    reader
+                        .read_to_end(e.name())
+                        .map_err(|e| ParseError::XmlError {
+                            message: format!("Failed to read FLEX XML: {}", e),
+                            location: None,
+                        })?;
+                }
+                sections.push((name, start..reader.buffer_position() as usize));
+            }
+        }
+    }
+
+    Ok(statements)
+}
+
+/// Parse the attributes of the statement spanning `range`, whose start tag
+/// spans `tag`
+///
+/// The date format is detected from the whole statement, as eager parsing
+/// does, so that every section is read with the same one.
+fn statement<'a>(
+    xml: &'a str,
+    index: usize,
+    range: Range<usize>,
+    tag: Range<usize>,
+    sections: SectionRanges<'a>,
+) -> Result<LazyActivityStatement<'a>> {
+    let tag_xml = &xml[tag.clone()];
+    let empty_tag = match tag_xml.strip_suffix("/>") {
+        Some(_) => tag_xml.to_string(),
+        None => format!("{}/>", &tag_xml[..tag_xml.len() - 1]),
+    };
+    let origin = Origin::at(xml, tag.start, Some(index));
+    let header = date_format::parse_with(&xml[range], None, || {
+        let (statement, unknown_values) = scope::collecting(|| {
+            location::deserialize::<ActivityFlexStatement>(&empty_tag, &origin, "FLEX XML")
+        });
+        statement.map(|mut statement| {
+            statement.unknown_values = unknown_values;
+            statement
+        })
+    })?;
+
+    let mut by_name = HashMap::new();
+    for (position, (name, _)) in sections.iter().enumerate() {
+        by_name.entry(*name).or_insert(position);
+    }
+
+    Ok(LazyActivityStatement {
+        xml,
+        index,
+        date_format: header.date_format,
+        header,
+        sections,
+        by_name,
+        cache: Cache::default(),
+        unknown_sections: OnceCell::new(),
+    })
+}
+
+/// Declare the lazily deserialized sections of [`LazyActivityStatement`]
+///
+/// Each entry names the accessor (and `ActivityFlexStatement` field), its
+/// type, and the section's XML element.
+macro_rules! lazy_sections {
+    ($( $(#[$doc:meta])* $field:ident: $ty:ty = $name:literal, )+) => {
+        /// Sections deserialized so far
+        #[derive(Default)]
+        struct Cache {
+            $( $field: OnceCell<Section<$ty>>, )+
+        }
+
+        impl Cache {
+            /// Unknown enum values of section `name`, if deserialized
+            fn unknown_values(&self, name: &str) -> Vec<UnknownValue> {
+                match name {
+                    $( $name => self
+                        .$field
+                        .get()
+                        .map(|section| section.unknown_values.clone())
+                        .unwrap_or_default(), )+
+                    _ => Vec::new(),
+                }
+            }
+
+            /// Move section `name` into `statement`, deserializing it if needed
+            fn take_into(
+                &mut self,
+                lazy: &LazyActivityStatement<'_>,
+                name: &str,
+                statement: &mut ActivityFlexStatement,
+            ) -> Result<()> {
+                match name {
+                    $( $name => {
+                        let section = match self.$field.take() {
+                            Some(section) => section,
+                            None => lazy.load(name)?,
+                        };
+                        statement.$field = section.value;
+                        inventory::merge(&mut statement.unknown_values, section.unknown_values);
+                    } )+
+                    _ => {}
+                }
+                Ok(())
+            }
+        }
+
+        /// Whether section `name` has a typed counterpart
+        fn is_known(name: &str) -> bool {
+            matches!(name, $( $name )|+)
+        }
+
+        impl LazyActivityStatement<'_> {
+            $(
+                $(#[$doc])*
+                ///
+                /// # Errors
+                ///
+                /// Returns `ParseError` if the section fails to deserialize.
+                pub fn $field(&self) -> Result<&$ty> {
+                    self.section(&self.cache.$field, $name)
+                }
+            )+
+        }
+    };
+}
+
+lazy_sections! {
+    /// Trades (`<Trades>`)
+    trades: TradesWrapper = "Trades",
+    /// Open positions (`<OpenPositions>`)
+    positions: PositionsWrapper = "OpenPositions",
+    /// Cash transactions (`<CashTransactions>`)
+    cash_transactions: CashTransactionsWrapper = "CashTransactions",
+    /// Corporate actions (`<CorporateActions>`)
+    corporate_actions: CorporateActionsWrapper = "CorporateActions",
+    /// Securities information (`<SecuritiesInfo>`)
+    securities_info: SecuritiesInfoWrapper = "SecuritiesInfo",
+    /// Conversion rates (`<ConversionRates>`)
+    conversion_rates: ConversionRatesWrapper = "ConversionRates",
+    /// Account information (`<AccountInformation>`)
+    account_information: Option<AccountInformation> = "AccountInformation",
+    /// Change in NAV (`<ChangeInNAV>`)
+    change_in_nav: Option<ChangeInNAV> = "ChangeInNAV",
+    /// Equity summary by report date (`<EquitySummaryInBase>`)
+    equity_summary: EquitySummaryWrapper = "EquitySummaryInBase",
+    /// Cash report (`<CashReport>`)
+    cash_report: CashReportWrapper = "CashReport",
+    /// Deposits on hold (`<DepositsOnHold>`)
+    deposits_on_hold: DepositsOnHoldWrapper = "DepositsOnHold",
+    /// FDIC insured deposits (`<FdicInsuredDepositsByBank>`)
+    fdic_insured_deposits: FdicInsuredDepositsWrapper = "FdicInsuredDepositsByBank",
+    /// IBG note transactions (`<IBGNoteTransactions>`)
+    ibg_note_transactions: IBGNoteTransactionsWrapper = "IBGNoteTransactions",
+    /// Trade confirmations (`<TradeConfirms>`)
+    trade_confirms: TradeConfirmsWrapper = "TradeConfirms",
+    /// Option exercises, assignments and expirations (`<OptionEAE>`)
+    option_eae: OptionEAEWrapper = "OptionEAE",
+    /// FX transactions (`<FxTransactions>`)
+    fx_transactions: FxTransactionsWrapper = "FxTransactions",
+    /// Change in dividend accruals (`<ChangeInDividendAccruals>`)
+    change_in_dividend_accruals: ChangeInDividendAccrualsWrapper = "ChangeInDividendAccruals",
+    /// Open dividend accruals (`<OpenDividendAccruals>`)
+    open_dividend_accruals: OpenDividendAccrualsWrapper = "OpenDividendAccruals",
+    /// Interest accruals (`<InterestAccruals>`)
+    interest_accruals: InterestAccrualsWrapper = "InterestAccruals",
+    /// Transfers (`<Transfers>`)
+    transfers: TransfersWrapper = "Transfers",
+    /// MTM performance summary (`<MTMPerformanceSummaryInBase>`)
+    mtm_performance_summary: MTMPerformanceSummaryWrapper = "MTMPerformanceSummaryInBase",
+    /// FIFO performance summary (`<FIFOPerformanceSummaryInBase>`)
+    fifo_performance_summary: FIFOPerformanceSummaryWrapper = "FIFOPerformanceSummaryInBase",
+    /// MTD/YTD performance summary (`<MTDYTDPerformanceSummary>`)
+    mtd_ytd_performance_summary: MTDYTDPerformanceSummaryWrapper = "MTDYTDPerformanceSummary",
+    /// Statement of funds (`<StmtFunds>`)
+    statement_of_funds: StatementOfFundsWrapper = "StmtFunds",
+    /// Change in position values (`<ChangeInPositionValues>`)
+    change_in_position_values: ChangeInPositionValueWrapper = "ChangeInPositionValues",
+    /// Unbundled commission details (`<UnbundledCommissionDetails>`)
+    unbundled_commission_details: UnbundledCommissionDetailWrapper = "UnbundledCommissionDetails",
+    /// Client fees (`<ClientFees>`)
+    client_fees: ClientFeesWrapper = "ClientFees",
+    /// Client fee details (`<ClientFeesDetails>`)
+    client_fees_detail: ClientFeesDetailWrapper = "ClientFeesDetails",
+    /// Securities lending activities (`<SLBActivities>`)
+    slb_activities: SLBActivitiesWrapper = "SLBActivities",
+    /// Securities lending fees (`<SLBFees>`)
+    slb_fees: SLBFeesWrapper = "SLBFees",
+    /// Securities lending open contracts (`<SLBOpenContracts>`)
+    slb_open_contracts: SLBOpenContractsWrapper = "SLBOpenContracts",
+    /// Securities lending collaterals (`<SLBCollaterals>`)
+    slb_collaterals: SLBCollateralsWrapper = "SLBCollaterals",
+    /// Hard to borrow details (`<HardToBorrowDetails>`)
+    hard_to_borrow_details: HardToBorrowDetailsWrapper = "HardToBorrowDetails",
+    /// FX lots (`<FxLots>`)
+    fx_lots: FxLotsWrapper = "FxLots",
+    /// Unsettled transfers (`<UnsettledTransfers>`)
+    unsettled_transfers: UnsettledTransfersWrapper = "UnsettledTransfers",
+    /// Trade transfers (`<TradeTransfers>`)
+    trade_transfers: TradeTransfersWrapper = "TradeTransfers",
+    /// Prior period positions (`<PriorPeriodPositions>`)
+    prior_period_positions: PriorPeriodPositionsWrapper = "PriorPeriodPositions",
+    /// Tiered interest details (`<TierInterestDetails>`)
+    tier_interest_details: TierInterestDetailsWrapper = "TierInterestDetails",
+    /// Debit card activities (`<DebitCardActivities>`)
+    debit_card_activities: DebitCardActivitiesWrapper = "DebitCardActivities",
+    /// Sales taxes (`<SalesTaxes>`)
+    sales_tax: SalesTaxWrapper = "SalesTaxes",
+    /// Net stock positions (`<NetStockPositions>`)
+    net_stock_positions: NetStockPositionsWrapper = "NetStockPositions",
+    /// Net stock position summary (`<NetStockPositionSummary>`)
+    net_stock_position_summary: NetStockPositionSummaryWrapper = "NetStockPositionSummary",
+    /// Complex positions (`<ComplexPositions>`)
+    complex_positions: ComplexPositionsWrapper = "ComplexPositions",
+    /// FX positions (`<FxPositions>`)
+    fx_positions: FxPositionsWrapper = "FxPositions",
+    /// Mutual fund dividends (`<MutualFundDividendDetails>`)
+    mutual_fund_dividends: MutualFundDividendDetailsWrapper = "MutualFundDividendDetails",
+    /// Incentive coupon accruals (`<IncentiveCouponAccrualDetails>`)
+    incentive_coupon_accruals: IncentiveCouponAccrualDetailsWrapper = "IncentiveCouponAccrualDetails",
+    /// Hong Kong IPO open subscriptions (`<HKIPOOpenSubscriptions>`)
+    hk_ipo_open_subscriptions: HKIPOOpenSubscriptionsWrapper = "HKIPOOpenSubscriptions",
+    /// Hong Kong IPO subscription activity (`<HKIPOSubscriptionActivity>`)
+    hk_ipo_subscription_activity: HKIPOSubscriptionActivityWrapper = "HKIPOSubscriptionActivity",
+    /// Stock grant activities (`<StockGrantActivities>`)
+    stock_grant_activities: StockGrantActivitiesWrapper = "StockGrantActivities",
+    /// Pending exercises (`<PendingExcercises>`)
+    pending_exercises: PendingExercisesWrapper = "PendingExcercises",
+    /// Unbooked trades (`<UnbookedTrades>`)
+    unbooked_trades: UnbookedTradesWrapper = "UnbookedTrades",
+    /// Transaction taxes (`<TransactionTaxes>`)
+    transaction_taxes: TransactionTaxesWrapper = "TransactionTaxes",
+    /// CFD charges (`<CFDCharges>`)
+    cfd_charges: CFDChargesWrapper = "CFDCharges",
+    /// Routing commissions (`<RoutingCommissions>`)
+    routing_commissions: RoutingCommissionsWrapper = "RoutingCommissions",
+    /// Commission credits (`<CommissionCredits>`)
+    commission_credits: CommissionCreditsWrapper = "CommissionCredits",
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn test_lazy_matches_eager_parsing() {
+        let fixtures = [
+            include_str!("../../tests/fixtures/activity_backfill.xml"),
+            include_str!("../../tests/fixtures/activity_daily_portfolio.xml"),
+            include_str!("../../tests/fixtures/activity_unknown_data.xml"),
+            include_str!("../../tests/fixtures/activity_unknown_values.xml"),
+            include_str!("../../tests/fixtures/activity_stock_grants.xml"),
+        ];
+
+        for xml in fixtures {
+            let eager = crate::parsers::parse_activity_flex_all(xml).unwrap();
+            let lazy = LazyActivityStatement::parse_all(xml)
+                .unwrap()
+                .into_iter()
+                .map(LazyActivityStatement::into_statement)
+                .collect::<Result<Vec<_>>>()
+                .unwrap();
+            assert_eq!(lazy, eager);
+        }
+    }
+
+    #[test]
+    fn test_lazy_sections_are_independent() {
+        let xml = r#"<FlexQueryResponse><FlexStatements count="1">
+<FlexStatement accountId="U1" fromDate="20250101" toDate="20250101" whenGenerated="20250102;000000">
+<StmtFunds>
+<StatementOfFundsLine accountId="U1" currency="USD" date="20250101" amount="oops" />
+</StmtFunds>
+<Trades>
+<Trade accountId="U1" currency="USD" assetCategory="BOND2" symbol="X" conid="1" tradeDate="20250101" quantity="5" />
+</Trades>
+</FlexStatement>
+</FlexStatements></FlexQueryResponse>"#;
+
+        let statement = LazyActivityStatement::parse(xml).unwrap();
+        assert_eq!(statement.header().account_id, "U1");
+        assert_eq!(
+            statement.section_names().collect::<Vec<_>>(),
+            ["StmtFunds", "Trades"]
+        );
+        assert!(statement.unknown_values().is_empty());
+
+        let trades = statement.trades().unwrap();
+        assert_eq!(trades.items[0].quantity, Some(5.into()));
+        assert!(std::ptr::eq(trades, statement.trades().unwrap()));
+        assert_eq!(statement.unknown_values()[0].value, "BOND2");
+        assert!(statement.positions().unwrap().items.is_empty());
+
+        let err = statement.statement_of_funds().unwrap_err();
+        let location = err.location().unwrap();
+        assert_eq!(
+            location.element_path,
+            "FlexStatement[0]/StmtFunds/StatementOfFundsLine[0]"
+        );
+        assert_eq!(location.line, 4);
+        assert_eq!(location.attribute.as_deref(), Some("amount"));
+    }
+
+    #[test]
+    fn test_lazy_sections_share_statement_date_format() {
+        // The trade date alone fits both orders; the header settles dd/MM/yyyy
+        let xml = r#"<FlexQueryResponse><FlexStatements count="1">
+<FlexStatement accountId="U1" fromDate="13/01/2025" toDate="31/01/2025" whenGenerated="01/02/2025;080000">
+<Trades>
+<Trade accountId="U1" currency="USD" assetCategory="STK" symbol="X" conid="1" tradeDate="02/01/2025" />
+</Trades>
+</FlexStatement>
+</FlexStatements></FlexQueryResponse>"#;
+
+        let eager = crate::parsers::parse_activity_flex(xml).unwrap();
+        let lazy = LazyActivityStatement::parse(xml).unwrap();
+        let trade_date = lazy.trades().unwrap().items[0].trade_date;
+        assert_eq!(trade_date, NaiveDate::from_ymd_opt(2025, 1, 2));
+        assert_eq!(trade_date, eager.trades.items[0].trade_date);
+        assert_eq!(lazy.into_statement().unwrap(), eager);
+    }
+}
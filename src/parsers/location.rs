@@ -0,0 +1,733 @@
+//! Mapping deserialization failures back to positions in the FLEX XML
+//!
+//! serde reports *where* in the data model a value failed (map keys, sequence
+//! indices, enum variants). This module turns such a path into element steps,
+//! then walks the XML with quick-xml's pull reader to find the matching element
+//! and attribute by byte position, and finally into a [`ParseError`] carrying
+//! an [`ErrorLocation`].
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use quick_xml::de::DeError;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::{de, Deserialize};
+use serde_path_to_error::{Path, Segment};
+
+use crate::error::{ErrorLocation, ParseError, Result};
+use crate::parsers::noting::Noting;
+use crate::parsers::scope::{self, Scope};
+
+/// Where a fragment of XML starts within the original document
+///
+/// Statements handed out by the streaming parser are deserialized on their
+/// own; the origin maps positions inside them back to the full document.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Origin {
+    /// Byte offset of the fragment's first byte
+    pub offset: usize,
+    /// Zero-based line of the fragment's first byte
+    pub line: usize,
+    /// Zero-based column (in characters) of the fragment's first byte
+    pub column: usize,
+    /// Index of the statement when the fragment is a single `<FlexStatement>`
+    /// or one of its sections
+    pub statement_index: Option<usize>,
+}
+
+impl Origin {
+    /// Origin of the fragment starting at byte `offset` of `document`
+    pub(crate) fn at(document: &str, offset: usize, statement_index: Option<usize>) -> Origin {
+        let prefix = &document[..offset];
+        let (line, column) = match prefix.rfind('\n') {
+            Some(newline) => (
+                prefix.matches('\n').count(),
+                prefix[newline + 1..].chars().count(),
+            ),
+            None => (0, prefix.chars().count()),
+        };
+        Origin {
+            offset,
+            line,
+            column,
+            statement_index,
+        }
+    }
+}
+
+/// Kind of value a FLEX value deserializer failed to parse
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ValueKind {
+    /// A date or timestamp
+    Date,
+    /// A decimal number
+    Decimal,
+}
+
+/// A deserialization failure noted apart from its error message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Failure {
+    /// A FLEX value deserializer failed to parse a value of this kind
+    Value(ValueKind),
+    /// A required field was absent (noted by [`Noting`](crate::parsers::noting::Noting))
+    MissingField(&'static str),
+}
+
+/// Note `failure` in the running [`Scope`]
+pub(crate) fn note_failure(failure: Failure) {
+    scope::noting(|_, noted| noted.failure = Some(failure));
+}
+
+/// Error for a value of `kind` that failed to parse
+///
+/// The kind is remembered so that the failure is reported as the matching
+/// [`ParseError`] variant, whatever the message says.
+pub(crate) fn value_error<E: de::Error>(kind: ValueKind, message: String) -> E {
+    note_failure(Failure::Value(kind));
+    E::custom(message)
+}
+
+/// Deserialize `xml`, attaching an [`ErrorLocation`] to any failure
+///
+/// The fast path is a plain serde deserialization; only a failing document is
+/// deserialized a second time with path tracking. `document` names the kind of
+/// XML in generic error messages (e.g. `"FLEX XML"`).
+pub(crate) fn deserialize<'de, T: Deserialize<'de>>(
+    xml: &'de str,
+    origin: &Origin,
+    document: &str,
+) -> Result<T> {
+    let err = match quick_xml::de::from_str(xml) {
+        Ok(value) => return Ok(value),
+        Err(err) => err,
+    };
+
+    let mut de = quick_xml::de::Deserializer::from_str(xml);
+    let (result, noted) = Scope::current()
+        .run(|| serde_path_to_error::deserialize::<_, T>(Noting(&mut de)).map(drop));
+    let path = result.err().map(|e| e.path().clone());
+    Err(to_parse_error(
+        xml,
+        &err,
+        path.as_ref(),
+        noted.failure,
+        origin,
+        document,
+    ))
+}
+
+/// Convert a deserialization failure into a located [`ParseError`]
+///
+/// `path` is where the path-tracked retry of the deserialization failed.
+/// Values that failed to parse become [`ParseError::InvalidDate`] or
+/// [`ParseError::InvalidDecimal`], missing fields
+/// [`ParseError::MissingField`]; anything else is reported as
+/// [`ParseError::XmlError`].
+pub(crate) fn to_parse_error(
+    xml: &str,
+    err: &DeError,
+    path: Option<&Path>,
+    failed: Option<Failure>,
+    origin: &Origin,
+    document: &str,
+) -> ParseError {
+    let location = match (err, path) {
+        (DeError::InvalidXml(_) | DeError::UnexpectedEof, _) => {
+            Some(syntax_error_location(xml, origin))
+        }
+        (_, None) => None,
+        (_, Some(path)) => {
+            let path = FieldPath::from_serde(path);
+            locate(xml, &path).map(|located| {
+                let offset = located
+                    .attribute
+                    .as_ref()
+                    .map_or(located.offset, |attr| attr.range.start);
+                error_location(
+                    xml,
+                    offset,
+                    &located.elements,
+                    located.attribute.as_ref(),
+                    origin,
+                )
+            })
+        }
+    };
+
+    classify(err.to_string(), failed, location, document)
+}
+
+/// Pick the [`ParseError`] variant for the `failed` failure, or for a serde
+/// error when no failure was noted
+fn classify(
+    message: String,
+    failed: Option<Failure>,
+    location: Option<ErrorLocation>,
+    document: &str,
+) -> ParseError {
+    let location = location.map(Box::new);
+    match failed {
+        Some(Failure::Value(ValueKind::Date)) => ParseError::InvalidDate { message, location },
+        Some(Failure::Value(ValueKind::Decimal)) => {
+            ParseError::InvalidDecimal { message, location }
+        }
+        Some(Failure::MissingField(field)) => {
+            let context = location
+                .as_ref()
+                .map(|l| l.element_path.clone())
+                .filter(|path| !path.is_empty())
+                .unwrap_or_else(|| document.to_string());
+            ParseError::MissingField {
+                field: field.trim_start_matches('@').to_string(),
+                context,
+                location,
+            }
+        }
+        None => ParseError::XmlError {
+            message: format!("Failed to parse {}: {}", document, message),
+            location,
+        },
+    }
+}
+
+/// Locate an XML syntax error (or premature end of input) in `xml`
+///
+/// Returns the position of the offending markup together with the elements
+/// that were open at that point.
+pub(crate) fn syntax_error_location(xml: &str, origin: &Origin) -> ErrorLocation {
+    let mut reader = Reader::from_str(xml);
+    let mut open: Vec<(String, usize)> = Vec::new();
+    let mut counters: Vec<HashMap<String, usize>> = vec![HashMap::new()];
+
+    let offset = loop {
+        let (e, empty) = match reader.read_event() {
+            Ok(Event::Start(e)) => (e, false),
+            Ok(Event::Empty(e)) => (e, true),
+            Ok(Event::End(_)) => {
+                open.pop();
+                counters.pop();
+                continue;
+            }
+            Ok(Event::Eof) => break xml.len(),
+            Err(_) => break reader.error_position() as usize,
+            Ok(_) => continue,
+        };
+
+        let name = element_name(&e);
+        let count = counters
+            .last_mut()
+            .expect("counter per open element")
+            .entry(name.clone())
+            .or_insert(0);
+        let index = *count;
+        *count += 1;
+
+        // Entity errors only surface when the deserializer unescapes a value
+        if let Some(attr) = unescape_error(xml, &e) {
+            open.push((name, index));
+            return error_location(xml, attr.range.start, &open, Some(&attr), origin);
+        }
+
+        if !empty {
+            open.push((name, index));
+            counters.push(HashMap::new());
+        }
+    };
+
+    error_location(xml, offset, &open, None, origin)
+}
+
+/// First attribute of an element whose value contains an invalid escape
+fn unescape_error(xml: &str, element: &BytesStart) -> Option<AttributeSpan> {
+    let attr = element
+        .attributes()
+        .with_checks(false)
+        .filter_map(|a| a.ok())
+        .find(|a| a.unescape_value().is_err())?;
+
+    let start = offset_in(xml, attr.key.as_ref())?;
+    let value_start = offset_in(xml, &attr.value)?;
+    Some(AttributeSpan {
+        name: String::from_utf8_lossy(attr.key.as_ref()).into_owned(),
+        value: String::from_utf8_lossy(&attr.value).into_owned(),
+        range: start..value_start + attr.value.len() + 1,
+    })
+}
+
+/// Build an [`ErrorLocation`] for a byte offset inside `xml`
+pub(crate) fn error_location(
+    xml: &str,
+    offset: usize,
+    elements: &[(String, usize)],
+    attribute: Option<&AttributeSpan>,
+    origin: &Origin,
+) -> ErrorLocation {
+    let mut offset = offset.min(xml.len());
+    while !xml.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    let prefix = &xml[..offset];
+    let (line, column) = match prefix.rfind('\n') {
+        Some(newline) => (
+            origin.line + prefix.matches('\n').count(),
+            prefix[newline + 1..].chars().count(),
+        ),
+        None => (origin.line, origin.column + prefix.chars().count()),
+    };
+
+    ErrorLocation {
+        byte_offset: origin.offset + offset,
+        line: line + 1,
+        column: column + 1,
+        element_path: element_path(elements, origin.statement_index),
+        attribute: attribute.map(|a| a.name.clone()),
+        raw_value: attribute.map(|a| a.value.clone()),
+    }
+}
+
+/// Format an element path such as `FlexStatement[3]/Trades/Trade[17]`
+///
+/// Elements above `<FlexStatement>` are dropped. The root and the sections
+/// directly below it are shown without an index, except for the statement
+/// itself.
+fn element_path(elements: &[(String, usize)], statement_index: Option<usize>) -> String {
+    let start = elements
+        .iter()
+        .position(|(name, _)| name == "FlexStatement");
+    // A single section of a statement is shown below that statement
+    if let (None, Some(index), false) = (start, statement_index, elements.is_empty()) {
+        let mut full = vec![("FlexStatement".to_string(), index)];
+        full.extend_from_slice(elements);
+        return element_path(&full, statement_index);
+    }
+    let elements = &elements[start.unwrap_or(0)..];
+
+    elements
+        .iter()
+        .enumerate()
+        .map(|(depth, (name, index))| match depth {
+            0 if start.is_some() => format!("{}[{}]", name, statement_index.unwrap_or(*index)),
+            0 => name.clone(),
+            1 => name.clone(),
+            _ => format!("{}[{}]", name, index),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Which child of an element a path step selects
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Nth {
+    /// The n-th child with the given name (serde field backed by a `Vec`)
+    Named(String, usize),
+    /// The n-th element child of any name (a `$value` sequence)
+    Child(usize),
+}
+
+/// Path from the deserialized root element down to a failing element/attribute
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct FieldPath {
+    steps: Vec<Nth>,
+    attribute: Option<String>,
+}
+
+impl FieldPath {
+    /// Convert a serde path into element steps and an optional attribute name
+    pub(crate) fn from_serde(path: &Path) -> Self {
+        let mut steps = Vec::new();
+        let mut attribute = None;
+        let mut segments = path.iter().peekable();
+
+        while let Some(segment) = segments.next() {
+            let Segment::Map { key } = segment else {
+                continue;
+            };
+
+            if let Some(name) = key.strip_prefix('@') {
+                attribute = Some(name.to_string());
+                continue;
+            }
+            if key == "$text" {
+                continue;
+            }
+
+            let index = match segments.peek() {
+                Some(Segment::Seq { index }) => {
+                    let index = *index;
+                    segments.next();
+                    index
+                }
+                _ => 0,
+            };
+
+            if key == "$value" {
+                // Enum variant (element name) follows the index; the index alone locates it
+                if let Some(Segment::Enum { .. }) = segments.peek() {
+                    segments.next();
+                }
+                steps.push(Nth::Child(index));
+            } else {
+                steps.push(Nth::Named(key.clone(), index));
+            }
+        }
+
+        FieldPath { steps, attribute }
+    }
+
+    /// Name of the failing attribute, if the failure is attribute-level
+    pub(crate) fn attribute(&self) -> Option<&str> {
+        self.attribute.as_deref()
+    }
+}
+
+/// An attribute found in the XML
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AttributeSpan {
+    /// Attribute name
+    pub name: String,
+    /// Unescaped attribute value
+    pub value: String,
+    /// Byte range of the whole `name="value"` pair
+    pub range: Range<usize>,
+}
+
+/// Position of an element (and optionally one of its attributes) in the XML
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Located {
+    /// Element names from the root down, each with its index among
+    /// same-named siblings
+    pub elements: Vec<(String, usize)>,
+    /// Byte offset of the element's `<`
+    pub offset: usize,
+    /// The attribute named by the path, if any and if present
+    pub attribute: Option<AttributeSpan>,
+}
+
+/// Find the element and attribute a [`FieldPath`] points at
+///
+/// `xml` must contain the element that was deserialized as the root of the
+/// path (for example a single `<FlexStatement>`). Returns `None` if the path
+/// does not exist in the document.
+pub(crate) fn locate(xml: &str, path: &FieldPath) -> Option<Located> {
+    let mut reader = Reader::from_str(xml);
+
+    let (mut current, mut is_empty) = loop {
+        match reader.read_event().ok()? {
+            Event::Start(e) => break (e, false),
+            Event::Empty(e) => break (e, true),
+            Event::Eof => return None,
+            _ => {}
+        }
+    };
+    let mut elements = vec![(element_name(&current), 0)];
+
+    for step in &path.steps {
+        if is_empty {
+            return None;
+        }
+
+        let mut children = 0usize;
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        loop {
+            let (e, empty) = match reader.read_event().ok()? {
+                Event::Start(e) => (e, false),
+                Event::Empty(e) => (e, true),
+                Event::End(_) | Event::Eof => return None,
+                _ => continue,
+            };
+
+            let name = element_name(&e);
+            let count = seen.entry(name.clone()).or_insert(0);
+            let index = *count;
+            *count += 1;
+
+            let hit = match step {
+                Nth::Named(wanted, n) => *wanted == name && index == *n,
+                Nth::Child(n) => children == *n,
+            };
+            children += 1;
+
+            if hit {
+                elements.push((name, index));
+                current = e;
+                is_empty = empty;
+                break;
+            }
+            if !empty {
+                reader.read_to_end(e.name()).ok()?;
+            }
+        }
+    }
+
+    let offset = offset_in(xml, &current)?.checked_sub(1)?;
+    let attribute = match path.attribute() {
+        Some(name) => find_attribute(xml, &current, name),
+        None => None,
+    };
+
+    Some(Located {
+        elements,
+        offset,
+        attribute,
+    })
+}
+
+/// Find an attribute of an element borrowed from `xml`
+fn find_attribute(xml: &str, element: &BytesStart, name: &str) -> Option<AttributeSpan> {
+    let attr = element
+        .attributes()
+        .with_checks(false)
+        .filter_map(|a| a.ok())
+        .find(|a| a.key.as_ref() == name.as_bytes())?;
+
+    let start = offset_in(xml, attr.key.as_ref())?;
+    let value_start = offset_in(xml, &attr.value)?;
+    // Include the closing quote
+    let end = value_start + attr.value.len() + 1;
+    let value = attr
+        .unescape_value()
+        .map(|v| v.into_owned())
+        .unwrap_or_else(|_| String::from_utf8_lossy(&attr.value).into_owned());
+
+    Some(AttributeSpan {
+        name: name.to_string(),
+        value,
+        range: start..end,
+    })
+}
+
+/// Element context passed to [`for_each_element`] callbacks
+#[derive(Debug)]
+pub(crate) struct ElementContext<'p> {
+    /// Names of the ancestors of the element, from the root down
+    pub ancestors: &'p [String],
+    /// Element name
+    pub name: &'p str,
+    /// Index of the element among same-named siblings
+    pub index: usize,
+}
+
+/// Visit every element of `xml` in document order
+///
+/// Stops silently at the first XML syntax error; callers only use this on
+/// documents that have already been parsed successfully.
+pub(crate) fn for_each_element<'a, F>(xml: &'a str, mut visit: F)
+where
+    F: FnMut(&ElementContext<'_>, &BytesStart<'a>),
+{
+    let mut reader = Reader::from_str(xml);
+    let mut ancestors: Vec<String> = Vec::new();
+    let mut counters: Vec<HashMap<String, usize>> = vec![HashMap::new()];
+
+    loop {
+        let (e, empty) = match reader.read_event() {
+            Ok(Event::Start(e)) => (e, false),
+            Ok(Event::Empty(e)) => (e, true),
+            Ok(Event::End(_)) => {
+                ancestors.pop();
+                counters.pop();
+                continue;
+            }
+            Ok(Event::Eof) | Err(_) => return,
+            Ok(_) => continue,
+        };
+
+        let name = element_name(&e);
+        let count = counters
+            .last_mut()
+            .expect("counter per open element")
+            .entry(name.clone())
+            .or_insert(0);
+        let index = *count;
+        *count += 1;
+
+        visit(
+            &ElementContext {
+                ancestors: &ancestors,
+                name: &name,
+                index,
+            },
+            &e,
+        );
+
+        if !empty {
+            ancestors.push(name);
+            counters.push(HashMap::new());
+        }
+    }
+}
+
+/// Element name as an owned string
+fn element_name(e: &BytesStart) -> String {
+    String::from_utf8_lossy(e.name().as_ref()).into_owned()
+}
+
+/// Byte offset of a slice borrowed from `xml`
+fn offset_in(xml: &str, slice: &[u8]) -> Option<usize> {
+    let base = xml.as_ptr() as usize;
+    let ptr = slice.as_ptr() as usize;
+    (ptr >= base && ptr <= base + xml.len()).then(|| ptr - base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ActivityFlexStatement;
+
+    const XML: &str = r#"<FlexStatement accountId="U1" fromDate="2025-01-01" toDate="2025-01-01" whenGenerated="2025-01-02;000000">
+<Trades>
+<Order accountId="U1" conid="1" symbol="A" assetCategory="STK" currency="USD" />
+<Trade accountId="U1" conid="1" symbol="A" assetCategory="STK" currency="USD" tradePrice="1" />
+<Trade accountId="U1" conid="2" symbol="B" assetCategory="STK" currency="USD" tradePrice="bad" />
+</Trades>
+<ConversionRates>
+<ConversionRate reportDate="2025-01-01" fromCurrency="EUR" toCurrency="USD" rate="1.1" />
+<ConversionRate reportDate="2025-01-01" fromCurrency="GBP" toCurrency="USD" rate="oops" />
+</ConversionRates>
+</FlexStatement>"#;
+
+    fn failing_path(xml: &str) -> FieldPath {
+        let mut de = quick_xml::de::Deserializer::from_str(xml);
+        let err = serde_path_to_error::deserialize::<_, ActivityFlexStatement>(&mut de)
+            .expect_err("fixture should fail");
+        FieldPath::from_serde(err.path())
+    }
+
+    #[test]
+    fn test_locate_value_sequence_attribute() {
+        let path = failing_path(XML);
+        assert_eq!(path.attribute(), Some("tradePrice"));
+
+        let located = locate(XML, &path).unwrap();
+        assert_eq!(
+            located.elements,
+            vec![
+                ("FlexStatement".to_string(), 0),
+                ("Trades".to_string(), 0),
+                ("Trade".to_string(), 1),
+            ]
+        );
+
+        let attr = located.attribute.unwrap();
+        assert_eq!(attr.value, "bad");
+        assert_eq!(&XML[attr.range], r#"tradePrice="bad""#);
+        assert!(XML[located.offset..].starts_with(r#"<Trade accountId="U1" conid="2""#));
+    }
+
+    #[test]
+    fn test_locate_named_sequence_attribute() {
+        let xml = XML.replace(r#"tradePrice="bad""#, r#"tradePrice="2""#);
+        let path = failing_path(&xml);
+        let located = locate(&xml, &path).unwrap();
+
+        assert_eq!(
+            located.elements.last(),
+            Some(&("ConversionRate".to_string(), 1))
+        );
+        assert_eq!(located.attribute.unwrap().value, "oops");
+    }
+
+    #[test]
+    fn test_missing_field_noted_apart_from_message() {
+        let xml = r#"<FlexStatement accountId="U1" fromDate="2025-01-01" toDate="2025-01-01" whenGenerated="2025-01-02;000000">
+<ConversionRates><ConversionRate fromCurrency="EUR" toCurrency="USD" rate="1.1" /></ConversionRates>
+</FlexStatement>"#;
+        let err = deserialize::<ActivityFlexStatement>(xml, &Origin::default(), "FLEX XML")
+            .expect_err("reportDate is required");
+
+        match err {
+            ParseError::MissingField { field, context, .. } => {
+                assert_eq!(field, "reportDate");
+                assert_eq!(
+                    context,
+                    "FlexStatement[0]/ConversionRates/ConversionRate[0]"
+                );
+            }
+            other => panic!("expected MissingField, got {:?}", other),
+        }
+
+        // A custom error worded like serde's is not a missing field
+        let error = classify(
+            "missing field `reportDate`".to_string(),
+            None,
+            None,
+            "FLEX XML",
+        );
+        assert!(matches!(error, ParseError::XmlError { .. }));
+    }
+
+    #[test]
+    fn test_for_each_element_indices() {
+        let mut seen = Vec::new();
+        for_each_element(XML, |ctx, _| {
+            if ctx.ancestors.len() == 2 {
+                seen.push(format!("{}/{}[{}]", ctx.ancestors[1], ctx.name, ctx.index));
+            }
+        });
+
+        assert_eq!(
+            seen,
+            [
+                "Trades/Order[0]",
+                "Trades/Trade[0]",
+                "Trades/Trade[1]",
+                "ConversionRates/ConversionRate[0]",
+                "ConversionRates/ConversionRate[1]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_element_path_format() {
+        let elements = [
+            ("FlexQueryResponse".to_string(), 0),
+            ("FlexStatements".to_string(), 0),
+            ("FlexStatement".to_string(), 2),
+            ("Trades".to_string(), 0),
+            ("Trade".to_string(), 17),
+        ];
+        assert_eq!(
+            element_path(&elements, None),
+            "FlexStatement[2]/Trades/Trade[17]"
+        );
+        assert_eq!(
+            element_path(&elements[2..], Some(5)),
+            "FlexStatement[5]/Trades/Trade[17]"
+        );
+    }
+
+    #[test]
+    fn test_syntax_error_location_with_origin() {
+        let xml = "<FlexStatement>\n<Trades>\n<Trade a=\"1\"></Oops>";
+        let origin = Origin {
+            offset: 100,
+            line: 9,
+            column: 4,
+            statement_index: Some(1),
+        };
+        let location = syntax_error_location(xml, &origin);
+
+        assert_eq!(location.element_path, "FlexStatement[1]/Trades/Trade[0]");
+        assert_eq!(location.byte_offset, 100 + xml.find("</Oops>").unwrap());
+        assert_eq!((location.line, location.column), (12, 14));
+    }
+
+    #[test]
+    fn test_syntax_error_location_bad_escape() {
+        let xml = "<FlexStatement>\n<CashTransactions>\n<CashTransaction type=\"Deposits & Withdrawals\" />";
+        let location = syntax_error_location(xml, &Origin::default());
+
+        assert_eq!(
+            location.element_path,
+            "FlexStatement[0]/CashTransactions/CashTransaction[0]"
+        );
+        assert_eq!(location.attribute.as_deref(), Some("type"));
+        assert_eq!(
+            location.raw_value.as_deref(),
+            Some("Deposits & Withdrawals")
+        );
+        assert_eq!((location.line, location.column), (3, 18));
+    }
+}
@@ -1,8 +1,33 @@
 //! FLEX XML parsers
 
 pub mod activity;
+pub mod date_format;
+pub mod document;
+pub(crate) mod inventory;
+pub mod lazy;
+pub(crate) mod location;
+pub(crate) mod noting;
+pub mod options;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+pub(crate) mod raw;
+pub mod schema;
+pub(crate) mod scope;
+pub mod stream;
 pub mod trade_confirmation;
 pub mod xml_utils;
 
-pub use activity::{parse_activity_flex, parse_activity_flex_all};
-pub use trade_confirmation::parse_trade_confirmation;
+pub use activity::{
+    parse_activity_flex, parse_activity_flex_all, parse_activity_flex_all_borrowed,
+    parse_activity_flex_all_with_options, parse_activity_flex_borrowed,
+    parse_activity_flex_isolated, parse_activity_flex_with_options,
+};
+pub use date_format::{DateFormat, DateFormatProfile, TimeFormat};
+pub use document::{parse_flex, FlexDocument, FlexEnvelope};
+pub use lazy::LazyActivityStatement;
+pub use options::{ParseMode, ParseOptions, ParseWarning, ParsedStatement, SectionSet};
+#[cfg(feature = "rayon")]
+pub use parallel::parse_activity_flex_all_par;
+pub use schema::{schema_report, ElementReport, SchemaReport, UnknownElement};
+pub use stream::{ActivityFlexStream, StatementResult};
+pub use trade_confirmation::{parse_trade_confirmation, parse_trade_confirmation_all};
@@ -0,0 +1,573 @@
+//! Parse options and lenient-mode warnings
+//!
+//! By default a single malformed value fails the whole statement. In
+//! [`ParseMode::Lenient`] the offending attribute is dropped instead: the field
+//! becomes `None` and a [`ParseWarning`] records where it was and why it was
+//! rejected. Non-fatal findings such as unknown transaction codes are reported
+//! as warnings in both modes, so the library never writes to stderr.
+
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+
+use crate::error::{ParseError, Result};
+use crate::parsers::date_format::DateFormatProfile;
+use crate::parsers::inventory::Rejected;
+use crate::parsers::location::{self, Origin};
+use crate::parsers::noting::Noting;
+use crate::parsers::raw;
+use crate::parsers::scope::Scope;
+use crate::types::common::TransactionCode;
+use crate::types::ActivityFlexStatement;
+
+/// How to handle values that fail to deserialize
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    /// Any invalid value fails the statement (default)
+    #[default]
+    Strict,
+    /// Invalid optional values become `None` and are reported as warnings
+    Lenient,
+}
+
+/// Options controlling how FLEX XML is parsed
+///
+/// # Example
+///
+/// ```rust
+/// use ib_flex::parsers::{ParseMode, ParseOptions};
+///
+/// let options = ParseOptions::lenient();
+/// assert_eq!(options.mode, ParseMode::Lenient);
+/// assert_eq!(ParseOptions::default().mode, ParseMode::Strict);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Strict or lenient handling of invalid values
+    pub mode: ParseMode,
+
+    /// Date/time formats of the query; detected from the statement when `None`
+    pub date_format: Option<DateFormatProfile>,
+
+    /// Sections to deserialize; every section when `None`
+    pub sections: Option<SectionSet>,
+
+    /// Keep unmodeled sections and row attributes; see
+    /// [`ParseOptions::with_raw_capture`]
+    pub capture_raw: bool,
+}
+
+impl ParseOptions {
+    /// Options that fail on any invalid value
+    pub fn strict() -> Self {
+        ParseOptions {
+            mode: ParseMode::Strict,
+            date_format: None,
+            sections: None,
+            capture_raw: false,
+        }
+    }
+
+    /// Options that turn invalid optional values into `None` plus a warning
+    pub fn lenient() -> Self {
+        ParseOptions {
+            mode: ParseMode::Lenient,
+            date_format: None,
+            sections: None,
+            capture_raw: false,
+        }
+    }
+
+    /// Parse dates and times with the given profile instead of detecting it
+    pub fn with_date_format(mut self, profile: DateFormatProfile) -> Self {
+        self.date_format = Some(profile);
+        self
+    }
+
+    /// Deserialize only the given sections, skipping the others unread
+    pub fn with_sections(mut self, sections: SectionSet) -> Self {
+        self.sections = Some(sections);
+        self
+    }
+
+    /// Keep what the typed model does not cover
+    ///
+    /// Sections the crate does not model are stored in
+    /// [`ActivityFlexStatement::unknown_sections`] and unknown attributes of
+    /// typed rows in their `extra_attributes`. This reads each statement a
+    /// second time, so it is off by default.
+    pub fn with_raw_capture(mut self) -> Self {
+        self.capture_raw = true;
+        self
+    }
+}
+
+/// Sections of a `<FlexStatement>` to deserialize
+///
+/// Sections are named by their XML element (e.g. `Trades`, `OpenPositions`,
+/// `StmtFunds`); names of sections [`ActivityFlexStatement`] does not model
+/// are rejected. Sections outside the set are skipped while the statement is
+/// read from the document, before deserialization, and their fields stay
+/// empty. Unmodeled sections are always skipped, so `unknown_sections` stays
+/// empty. Error locations still point into the original document.
+///
+/// # Example
+///
+/// ```rust
+/// use ib_flex::parsers::{ParseOptions, SectionSet};
+///
+/// # fn main() -> ib_flex::Result<()> {
+/// let sections = SectionSet::new().with("Trades")?.with("OpenPositions")?;
+/// assert!(sections.contains("Trades"));
+/// assert!(!sections.contains("StmtFunds"));
+/// assert!(SectionSet::new().with("Trade").is_err());
+///
+/// let options = ParseOptions::default().with_sections(sections);
+/// # let _ = options;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SectionSet {
+    sections: BTreeSet<String>,
+}
+
+impl SectionSet {
+    /// An empty set, which skips every section
+    pub fn new() -> Self {
+        SectionSet::default()
+    }
+
+    /// Add the section with element name `section`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError::UnknownSection`] if `section` is not a section
+    /// element of [`ActivityFlexStatement`].
+    pub fn with(mut self, section: &str) -> Result<Self> {
+        let known = raw::field_names::<ActivityFlexStatement>()
+            .iter()
+            .any(|field| !field.starts_with('@') && *field == section);
+        if !known {
+            return Err(ParseError::UnknownSection(section.to_string()));
+        }
+        self.sections.insert(section.to_string());
+        Ok(self)
+    }
+
+    /// A set of the sections with the given element names
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError::UnknownSection`] for the first name that is not
+    /// a section element of [`ActivityFlexStatement`].
+    pub fn from_names<I>(sections: I) -> Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        sections
+            .into_iter()
+            .try_fold(SectionSet::new(), |set, section| set.with(section.as_ref()))
+    }
+
+    /// Whether the section with element name `section` is deserialized
+    pub fn contains(&self, section: &str) -> bool {
+        self.sections.contains(section)
+    }
+}
+
+/// A non-fatal problem found while parsing a statement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// Section element directly below `<FlexStatement>` (e.g. `Trades`),
+    /// or `FlexStatement` for attributes of the statement itself
+    pub section: String,
+
+    /// Name of the element carrying the attribute (e.g. `Trade`)
+    pub element: String,
+
+    /// Zero-based index of the element among same-named siblings
+    pub element_index: usize,
+
+    /// Attribute name (e.g. `tradePrice`)
+    pub attribute: String,
+
+    /// Raw attribute value as it appeared in the XML
+    pub raw_value: String,
+
+    /// Why the value was rejected or flagged
+    pub reason: String,
+}
+
+impl ParseWarning {
+    /// Build a warning from the element path of a located attribute
+    fn new(elements: &[(String, usize)], attribute: &str, raw_value: &str, reason: String) -> Self {
+        let (element, element_index) = elements.last().cloned().unwrap_or_default();
+        let section = elements
+            .get(1)
+            .map(|(name, _)| name.clone())
+            .unwrap_or_else(|| element.clone());
+
+        ParseWarning {
+            section,
+            element,
+            element_index,
+            attribute: attribute.to_string(),
+            raw_value: raw_value.to_string(),
+            reason,
+        }
+    }
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}[{}]@{}=\"{}\": {}",
+            self.section,
+            self.element,
+            self.element_index,
+            self.attribute,
+            self.raw_value,
+            self.reason
+        )
+    }
+}
+
+/// A parsed statement together with the warnings raised while parsing it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedStatement {
+    /// The parsed statement
+    pub statement: ActivityFlexStatement,
+
+    /// Warnings in document order
+    pub warnings: Vec<ParseWarning>,
+}
+
+/// Deserialize a single `<FlexStatement>` element according to `options`
+///
+/// The fast path is a plain serde deserialization; only a failing statement
+/// is deserialized a second time with path tracking to locate the error. In
+/// lenient mode the optional value deserializers turn invalid values into
+/// `None` during the same pass, and the rejected values are located
+/// afterwards for the warnings; required fields still fail.
+pub(crate) fn parse_statement(
+    xml: &str,
+    options: &ParseOptions,
+    origin: &Origin,
+) -> Result<ParsedStatement> {
+    let scope = Scope {
+        lenient: options.mode == ParseMode::Lenient,
+        ..Scope::current()
+    };
+    let (result, noted) = scope.run(|| quick_xml::de::from_str::<ActivityFlexStatement>(xml));
+    let (mut statement, noted) = match result {
+        Ok(statement) => (statement, noted),
+        Err(err) => {
+            let mut de = quick_xml::de::Deserializer::from_str(xml);
+            let (result, noted) = scope.run(|| {
+                serde_path_to_error::deserialize::<_, ActivityFlexStatement>(Noting(&mut de))
+            });
+            match result {
+                Ok(statement) => (statement, noted),
+                Err(retry) => {
+                    return Err(location::to_parse_error(
+                        xml,
+                        &err,
+                        Some(retry.path()),
+                        noted.failure,
+                        origin,
+                        "FLEX XML",
+                    ))
+                }
+            }
+        }
+    };
+    statement.unknown_values = noted.unknown_values;
+
+    if options.capture_raw {
+        raw::capture(xml, std::slice::from_mut(&mut statement));
+    }
+    let mut warnings = rejected_warnings(xml, noted.rejected);
+    transaction_code_warnings(xml, &statement, &mut warnings);
+    Ok(ParsedStatement {
+        statement,
+        warnings,
+    })
+}
+
+/// Warnings for the values rejected while deserializing `xml`
+///
+/// Rejections come in document order, each from an attribute carrying its
+/// value. Each is matched to the first attribute with its value that the typed
+/// model rejects as well, or else the first with its value, as long as the
+/// later rejections still find their attributes after it.
+fn rejected_warnings(xml: &str, rejected: Vec<Rejected>) -> Vec<ParseWarning> {
+    if rejected.is_empty() {
+        return Vec::new();
+    }
+
+    /// An attribute carrying one of the rejected values
+    struct Candidate {
+        ancestors: Vec<String>,
+        elements: Vec<(String, usize)>,
+        attribute: String,
+        value: String,
+    }
+
+    let values: HashSet<&str> = rejected.iter().map(|r| r.value.as_str()).collect();
+    let mut candidates = Vec::new();
+    location::for_each_element(xml, |ctx, e| {
+        for attr in e.attributes().flatten() {
+            let Ok(value) = attr.unescape_value() else {
+                continue;
+            };
+            if !values.contains(value.as_ref()) {
+                continue;
+            }
+            let mut elements: Vec<(String, usize)> =
+                ctx.ancestors.iter().map(|name| (name.clone(), 0)).collect();
+            elements.push((ctx.name.to_string(), ctx.index));
+            candidates.push(Candidate {
+                ancestors: ctx.ancestors.to_vec(),
+                elements,
+                attribute: String::from_utf8_lossy(attr.key.as_ref()).into_owned(),
+                value: value.into_owned(),
+            });
+        }
+    });
+
+    // Last candidate each rejection can take with the later ones still matched
+    let mut latest = vec![0; rejected.len()];
+    let mut bound = candidates.len();
+    for (i, rejected) in rejected.iter().enumerate().rev() {
+        let found = candidates[..bound]
+            .iter()
+            .rposition(|c| c.value == rejected.value);
+        debug_assert!(found.is_some(), "rejected value not in the statement");
+        bound = found.unwrap_or(0);
+        latest[i] = bound;
+    }
+
+    let mut template = raw::empty_statement();
+    let mut next = 0;
+    let mut warnings = Vec::with_capacity(rejected.len());
+    for (rejected, latest) in rejected.into_iter().zip(latest) {
+        let window = candidates.get(next..=latest).unwrap_or_default();
+        let mut matching = window
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.value == rejected.value);
+        let first = matching.clone().next();
+        let Some((offset, candidate)) = matching
+            .find(|(_, c)| {
+                let (element, _) = c.elements.last().expect("element itself");
+                raw::attribute_rejected(
+                    &mut template,
+                    &c.ancestors,
+                    element,
+                    &c.attribute,
+                    &c.value,
+                )
+            })
+            .or(first)
+        else {
+            continue;
+        };
+        next += offset + 1;
+        warnings.push(ParseWarning::new(
+            &candidate.elements,
+            &candidate.attribute,
+            &rejected.value,
+            rejected.reason,
+        ));
+    }
+    warnings
+}
+
+/// Report every unrecognized code in the `notes` attribute of `<Trades>` rows
+fn transaction_code_warnings(
+    xml: &str,
+    statement: &ActivityFlexStatement,
+    warnings: &mut Vec<ParseWarning>,
+) {
+    let has_unknown = statement
+        .trades
+        .items
+        .iter()
+        .chain(&statement.trades.wash_sales)
+        .chain(&statement.trades.orders)
+        .chain(&statement.trades.symbol_summaries)
+        .chain(&statement.trades.asset_summaries)
+        .chain(statement.trades.lots.iter().map(|l| &l.lot))
+        .filter_map(|t| t.notes.as_ref())
+        .any(|codes| codes.iter().any(TransactionCode::is_unknown));
+    if !has_unknown {
+        return;
+    }
+
+    location::for_each_element(xml, |ctx, e| {
+        if ctx.ancestors.get(1).map(String::as_str) != Some("Trades") {
+            return;
+        }
+        let Ok(Some(notes)) = e.try_get_attribute("notes") else {
+            return;
+        };
+        let Ok(notes) = notes.unescape_value() else {
+            return;
+        };
+
+        for code in notes.split(';').filter(|c| !c.is_empty()) {
+            if matches!(
+                serde_plain::from_str::<TransactionCode>(code),
+                Ok(TransactionCode::Unknown(_))
+            ) {
+                let mut elements: Vec<(String, usize)> =
+                    ctx.ancestors.iter().map(|name| (name.clone(), 0)).collect();
+                elements.push((ctx.name.to_string(), ctx.index));
+                warnings.push(ParseWarning::new(
+                    &elements,
+                    "notes",
+                    &notes,
+                    format!("Unknown TransactionCode '{}'", code),
+                ));
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statement_xml(trade_attrs: &str) -> String {
+        format!(
+            r#"<FlexStatement accountId="U1" fromDate="2025-01-01" toDate="2025-01-01" whenGenerated="2025-01-02;000000">
+<Trades>
+<Trade accountId="U1" conid="1" symbol="A" assetCategory="STK" currency="USD" tradePrice="1" />
+<Trade accountId="U1" conid="2" symbol="B" assetCategory="STK" currency="USD" {} />
+</Trades>
+</FlexStatement>"#,
+            trade_attrs
+        )
+    }
+
+    #[test]
+    fn test_strict_fails_on_bad_decimal() {
+        let xml = statement_xml(r#"tradePrice="abc""#);
+        let err = parse_statement(&xml, &ParseOptions::strict(), &Origin::default()).unwrap_err();
+        assert!(
+            matches!(err, ParseError::InvalidDecimal { .. }),
+            "{:?}",
+            err
+        );
+
+        let location = err.location().unwrap();
+        assert_eq!(location.element_path, "FlexStatement[0]/Trades/Trade[1]");
+        assert_eq!(location.attribute.as_deref(), Some("tradePrice"));
+        assert_eq!(location.raw_value.as_deref(), Some("abc"));
+        assert_eq!(location.line, 4);
+        assert_eq!(
+            location.byte_offset,
+            xml.find(r#"tradePrice="abc""#).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_lenient_drops_bad_fields_with_warnings() {
+        let xml = statement_xml(r#"tradePrice="abc" isAPIOrder="maybe" quantity="5""#);
+        let parsed = parse_statement(&xml, &ParseOptions::lenient(), &Origin::default()).unwrap();
+
+        let trade = &parsed.statement.trades.items[1];
+        assert_eq!(trade.trade_price, None);
+        assert_eq!(trade.is_api_order, None);
+        assert_eq!(trade.quantity.unwrap().to_string(), "5");
+
+        assert_eq!(parsed.warnings.len(), 2);
+        let warning = &parsed.warnings[0];
+        assert_eq!(warning.section, "Trades");
+        assert_eq!(warning.element, "Trade");
+        assert_eq!(warning.element_index, 1);
+        assert_eq!(warning.attribute, "tradePrice");
+        assert_eq!(warning.raw_value, "abc");
+        assert_eq!(parsed.warnings[1].attribute, "isAPIOrder");
+        assert_eq!(parsed.warnings[1].raw_value, "maybe");
+    }
+
+    #[test]
+    fn test_lenient_warning_skips_text_fields_with_same_value() {
+        // `description` accepts "N/A"; only `proceeds` rejects it
+        let xml = statement_xml(r#"description="N/A" proceeds="N/A" isAPIOrder="N/A""#);
+        let parsed = parse_statement(&xml, &ParseOptions::lenient(), &Origin::default()).unwrap();
+
+        let trade = &parsed.statement.trades.items[1];
+        assert_eq!(trade.description.as_deref(), Some("N/A"));
+        assert_eq!(trade.proceeds, None);
+        let attributes: Vec<_> = parsed
+            .warnings
+            .iter()
+            .map(|w| w.attribute.as_str())
+            .collect();
+        assert_eq!(attributes, ["proceeds", "isAPIOrder"]);
+        assert!(parsed.warnings.iter().all(|w| w.element_index == 1));
+    }
+
+    #[test]
+    fn test_rejected_warnings_always_located() {
+        // The typed model accepts both attributes, yet each rejection still
+        // gets an attribute of its own
+        let xml = statement_xml(r#"description="N/A" exchange="N/A""#);
+        let rejected = |value: &str| Rejected {
+            value: value.to_string(),
+            reason: "test".to_string(),
+        };
+        let warnings = rejected_warnings(&xml, vec![rejected("N/A"), rejected("N/A")]);
+
+        let attributes: Vec<_> = warnings.iter().map(|w| w.attribute.as_str()).collect();
+        assert_eq!(attributes, ["description", "exchange"]);
+        assert!(warnings
+            .iter()
+            .all(|w| w.section == "Trades" && w.element_index == 1));
+    }
+
+    #[test]
+    fn test_lenient_still_fails_on_required_field() {
+        let xml = statement_xml(r#"tradePrice="1""#)
+            .replace(r#"fromDate="2025-01-01""#, r#"fromDate="someday""#);
+        let err = parse_statement(&xml, &ParseOptions::lenient(), &Origin::default()).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidDate { .. }), "{:?}", err);
+        assert_eq!(
+            err.location().unwrap().raw_value.as_deref(),
+            Some("someday")
+        );
+    }
+
+    #[test]
+    fn test_unknown_transaction_code_warning() {
+        let xml = statement_xml(r#"notes="C;ZZ""#);
+        let parsed = parse_statement(&xml, &ParseOptions::strict(), &Origin::default()).unwrap();
+
+        assert_eq!(parsed.warnings.len(), 1);
+        let warning = &parsed.warnings[0];
+        assert_eq!(warning.attribute, "notes");
+        assert_eq!(warning.raw_value, "C;ZZ");
+        assert_eq!(warning.element_index, 1);
+        assert!(warning.reason.contains("'ZZ'"));
+    }
+
+    #[test]
+    fn test_section_set_rejects_unknown_names() {
+        let sections = SectionSet::from_names(["Trades", "StmtFunds"]).unwrap();
+        assert!(sections.contains("StmtFunds"));
+
+        for name in ["Trade", "trades", "SoftDollars", "@accountId"] {
+            let err = SectionSet::new().with(name).unwrap_err();
+            assert!(
+                matches!(&err, ParseError::UnknownSection(section) if section == name),
+                "{:?}",
+                err
+            );
+        }
+        assert!(SectionSet::from_names(["Trades", "OpenPosition"]).is_err());
+    }
+}
@@ -0,0 +1,190 @@
+//! Parallel Activity FLEX parsing
+//!
+//! Backfills contain hundreds of independent `<FlexStatement>` elements. One
+//! pass with quick-xml's pull reader finds the byte range of every statement;
+//! the statements are then deserialized on rayon's thread pool and collected
+//! in document order.
+
+use std::ops::Range;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use rayon::prelude::*;
+
+use crate::error::Result;
+use crate::parsers::date_format;
+use crate::parsers::location::{self, Origin};
+use crate::parsers::scope::Scope;
+use crate::types::ActivityFlexStatement;
+use crate::version;
+
+/// Parse all Activity FLEX statements, deserializing them in parallel
+///
+/// Returns the same statements, in the same order, as
+/// [`crate::parse_activity_flex_all`]. If several statements fail, the error
+/// of the first one in document order is returned, as sequential parsing
+/// would. Documents that cannot be split into statements (malformed XML or an
+/// unexpected envelope) are parsed sequentially so that they fail the same way.
+///
+/// # Arguments
+///
+/// * `xml` - XML string from IB Activity FLEX query
+///
+/// # Returns
+///
+/// * `Ok(Vec<ActivityFlexStatement>)` - All parsed statements
+/// * `Err(ParseError)` - Parse error with context
+///
+/// # Errors
+///
+/// Returns `ParseError` for the same reasons as
+/// [`crate::parse_activity_flex_all`].
+pub fn parse_activity_flex_all_par(xml: &str) -> Result<Vec<ActivityFlexStatement>> {
+    version::check_version(xml)?;
+    let Some(ranges) = statement_ranges(xml) else {
+        return super::parse_activity_flex_all(xml);
+    };
+
+    date_format::parse_with(xml, None, || {
+        // Worker threads start outside any scope
+        let scope = Scope::current();
+        let results: Vec<Result<ActivityFlexStatement>> = ranges
+            .par_iter()
+            .enumerate()
+            .map(|(index, range)| {
+                let (statement, noted) = scope.run(|| parse_statement(xml, index, range));
+                statement.map(|mut statement| {
+                    statement.unknown_values = noted.unknown_values;
+                    statement
+                })
+            })
+            .collect();
+        results.into_iter().collect()
+    })
+}
+
+/// Deserialize the statement at `range` of `xml`
+fn parse_statement(xml: &str, index: usize, range: &Range<usize>) -> Result<ActivityFlexStatement> {
+    let statement_xml = &xml[range.clone()];
+    match quick_xml::de::from_str(statement_xml) {
+        Ok(statement) => Ok(statement),
+        Err(_) => {
+            // Finding the line of the statement scans the document up to it,
+            // so only failing statements pay for it
+            let origin = Origin::at(xml, range.start, Some(index));
+            location::deserialize(statement_xml, &origin, "FLEX XML")
+        }
+    }
+}
+
+/// Byte ranges of the `<FlexStatement>` elements of `xml`
+///
+/// Returns `None` unless the document is well-formed and its statements are
+/// the only elements of a single `<FlexStatements>` below the root.
+fn statement_ranges(xml: &str) -> Option<Vec<Range<usize>>> {
+    let mut reader = Reader::from_str(xml);
+    let mut ranges = Vec::new();
+    let mut depth = 0usize;
+    let mut wrappers = 0usize;
+
+    loop {
+        let event = reader.read_event().ok()?;
+        let end = reader.buffer_position() as usize;
+        let (e, empty) = match event {
+            Event::Start(e) => (e, false),
+            Event::Empty(e) => (e, true),
+            Event::End(_) => {
+                depth -= 1;
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+        let start = end - e.len() - if empty { 3 } else { 2 };
+
+        match (depth, e.name().as_ref()) {
+            (1, b"FlexStatements") => wrappers += 1,
+            (1, _) => {}
+            (2, b"FlexStatement") => {
+                if !empty {
+                    reader.read_to_end(e.name()).ok()?;
+                }
+                ranges.push(start..reader.buffer_position() as usize);
+                continue;
+            }
+            (2, _) => return None,
+            _ => {}
+        }
+        if !empty {
+            depth += 1;
+        }
+    }
+
+    (depth == 0 && wrappers == 1).then_some(ranges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParseError;
+
+    #[test]
+    fn test_par_matches_parse_activity_flex_all() {
+        for xml in [
+            include_str!("../../tests/fixtures/activity_backfill.xml"),
+            include_str!("../../tests/fixtures/activity_unknown_values.xml"),
+            include_str!("../../tests/fixtures/activity_minimal.xml"),
+        ] {
+            assert_eq!(
+                parse_activity_flex_all_par(xml).unwrap(),
+                crate::parse_activity_flex_all(xml).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_par_shares_detected_date_format() {
+        // Only the second statement gives dd/MM/yyyy away
+        let xml = r#"<FlexQueryResponse><FlexStatements count="2">
+<FlexStatement accountId="U1" fromDate="01/02/2025" toDate="02/02/2025" whenGenerated="03/02/2025;080000" />
+<FlexStatement accountId="U1" fromDate="13/02/2025" toDate="14/02/2025" whenGenerated="15/02/2025;080000" />
+</FlexStatements></FlexQueryResponse>"#;
+
+        let statements = parse_activity_flex_all_par(xml).unwrap();
+        assert_eq!(statements[0].to_date.to_string(), "2025-02-02");
+        assert_eq!(statements, crate::parse_activity_flex_all(xml).unwrap());
+    }
+
+    #[test]
+    fn test_par_reports_first_failing_statement() {
+        let xml = include_str!("../../tests/fixtures/activity_backfill.xml").replacen(
+            "fromDate=\"",
+            "fromDate=\"x",
+            2,
+        );
+
+        let err = parse_activity_flex_all_par(&xml).unwrap_err();
+        assert_eq!(
+            format!("{:?}", err),
+            format!("{:?}", crate::parse_activity_flex_all(&xml).unwrap_err())
+        );
+        let ParseError::InvalidDate { location, .. } = err else {
+            panic!("expected InvalidDate, got {:?}", err);
+        };
+        assert_eq!(location.unwrap().element_path, "FlexStatement[0]");
+    }
+
+    #[test]
+    fn test_par_falls_back_on_unsplittable_documents() {
+        assert!(statement_ranges("<FlexQueryResponse><FlexStatements>").is_none());
+        assert!(
+            statement_ranges("<FlexQueryResponse><FlexStatement /></FlexQueryResponse>").is_none()
+        );
+
+        let xml = r#"<FlexQueryResponse><FlexStatements><FlexStatement accountId="U1""#;
+        assert_eq!(
+            format!("{:?}", parse_activity_flex_all_par(xml).unwrap_err()),
+            format!("{:?}", crate::parse_activity_flex_all(xml).unwrap_err())
+        );
+    }
+}
@@ -0,0 +1,687 @@
+//! Capture of sections and attributes the typed model does not cover
+//!
+//! Serde skips XML that has no matching field. When requested with
+//! [`ParseOptions::with_raw_capture`](crate::parsers::ParseOptions::with_raw_capture),
+//! a second pass over the same document after deserialization stores every
+//! unknown section in [`ActivityFlexStatement::unknown_sections`] and every
+//! unknown attribute of a typed row in that row's `extra_attributes`.
+
+use std::any::TypeId;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::de::{self, DeserializeOwned, Deserializer, Visitor};
+
+use crate::parsers::location::Failure;
+use crate::parsers::noting::Noting;
+use crate::parsers::scope::Scope;
+use crate::types::activity::TradeLot;
+use crate::types::extended::*;
+use crate::types::{
+    ActivityFlexStatement, CashTransaction, ConversionRate, CorporateAction, Position, RawElement,
+    SecurityInfo, Trade,
+};
+
+/// Fill `unknown_sections` and `extra_attributes` of `statements` from `xml`
+///
+/// The n-th `<FlexStatement>` of `xml` is matched to `statements[n]`;
+/// statements beyond the end of the slice are skipped. Stops silently at the
+/// first XML syntax error, as callers only pass documents that already parsed.
+pub(crate) fn capture(xml: &str, statements: &mut [ActivityFlexStatement]) {
+    capture_from(xml, statements, None);
+}
+
+/// Fill `unknown_sections` and `extra_attributes` of `statement` from `xml`
+/// holding one of its sections
+pub(crate) fn capture_section(xml: &str, statement: &mut ActivityFlexStatement) {
+    capture_from(xml, std::slice::from_mut(statement), Some(0));
+}
+
+/// Capture from `xml`, which is already inside statement `current` if set
+fn capture_from(xml: &str, statements: &mut [ActivityFlexStatement], current: Option<usize>) {
+    let mut reader = Reader::from_str(xml);
+    let mut known = KnownFields::default();
+    let sections: HashSet<&str> = field_names::<ActivityFlexStatement>()
+        .iter()
+        .copied()
+        .collect();
+
+    let mut next_statement = current.map_or(0, |index| index + 1);
+    // Index of the statement being read, and depth below it
+    let mut current = current;
+    let mut depth = 0usize;
+    let mut section = String::new();
+    let mut row_counts: HashMap<&'static str, usize> = HashMap::new();
+    // Open elements of an unknown section, outermost first
+    let mut raw: Vec<RawElement> = Vec::new();
+
+    loop {
+        let (e, empty) = match reader.read_event() {
+            Ok(Event::Start(e)) => (e, false),
+            Ok(Event::Empty(e)) => (e, true),
+            Ok(Event::End(_)) => {
+                let Some(index) = current else { continue };
+                if depth == 0 {
+                    current = None;
+                    continue;
+                }
+                depth -= 1;
+                if let Some(element) = raw.pop() {
+                    close_raw(&mut raw, element, &mut statements[index]);
+                }
+                continue;
+            }
+            Ok(Event::Eof) | Err(_) => return,
+            Ok(_) => continue,
+        };
+
+        let Some(index) = current else {
+            if e.name().as_ref() == b"FlexStatement" {
+                if next_statement < statements.len() && !empty {
+                    current = Some(next_statement);
+                    depth = 0;
+                }
+                next_statement += 1;
+            }
+            continue;
+        };
+        let statement = &mut statements[index];
+        let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+
+        if !raw.is_empty() || (depth == 0 && !sections.contains(name.as_str())) {
+            let element = raw_element(name, &e);
+            if empty {
+                close_raw(&mut raw, element, statement);
+            } else {
+                raw.push(element);
+            }
+        } else if depth == 0 {
+            row_counts.clear();
+            if let Some(rows) = section_row(statement, &name) {
+                rows.set_extra_attributes(0, &e, &mut known);
+            }
+            section = name;
+        } else if depth == 1 {
+            if let Some((slot, rows)) = rows(statement, &section, &name) {
+                let count = row_counts.entry(slot).or_insert(0);
+                rows.set_extra_attributes(*count, &e, &mut known);
+                *count += 1;
+            }
+        }
+
+        if !empty {
+            depth += 1;
+        }
+    }
+}
+
+/// Attach a finished raw element to its parent, or store it as a section
+fn close_raw(open: &mut [RawElement], element: RawElement, statement: &mut ActivityFlexStatement) {
+    match open.last_mut() {
+        Some(parent) => parent.children.push(element),
+        None => statement.unknown_sections.push(element),
+    }
+}
+
+/// Raw element with the attributes of `e` and no children yet
+fn raw_element(name: String, e: &BytesStart) -> RawElement {
+    let attributes = e
+        .attributes()
+        .flatten()
+        .map(|attr| {
+            let key = String::from_utf8_lossy(attr.key.as_ref()).into_owned();
+            let value = attr
+                .unescape_value()
+                .map(|v| v.into_owned())
+                .unwrap_or_else(|_| String::from_utf8_lossy(&attr.value).into_owned());
+            (key, value)
+        })
+        .collect();
+
+    RawElement {
+        name,
+        attributes,
+        children: Vec::new(),
+    }
+}
+
+/// The typed row of a section that is itself a row (e.g. `AccountInformation`)
+pub(crate) fn section_row<'s>(
+    statement: &'s mut ActivityFlexStatement,
+    section: &str,
+) -> Option<&'s mut dyn Rows> {
+    match section {
+        "AccountInformation" => Some(&mut statement.account_information),
+        "ChangeInNAV" => Some(&mut statement.change_in_nav),
+        _ => None,
+    }
+}
+
+/// Typed rows holding `row` elements of `section`
+///
+/// The returned key identifies the target list, so rows stored under
+/// several element names are counted together.
+pub(crate) fn rows<'s>(
+    s: &'s mut ActivityFlexStatement,
+    section: &str,
+    row: &str,
+) -> Option<(&'static str, &'s mut dyn Rows)> {
+    let rows: (&'static str, &'s mut dyn Rows) = match (section, row) {
+        ("Trades", "Trade") => ("Trade", &mut s.trades.items),
+        ("Trades", "WashSale") => ("WashSale", &mut s.trades.wash_sales),
+        ("Trades", "Order") => ("Order", &mut s.trades.orders),
+        ("Trades", "SymbolSummary") => ("SymbolSummary", &mut s.trades.symbol_summaries),
+        ("Trades", "AssetSummary") => ("AssetSummary", &mut s.trades.asset_summaries),
+        ("Trades", "Lot") => ("Lot", &mut s.trades.lots),
+        ("OpenPositions", "OpenPosition") => ("OpenPosition", &mut s.positions.items),
+        ("CashTransactions", "CashTransaction") => {
+            ("CashTransaction", &mut s.cash_transactions.items)
+        }
+        ("CorporateActions", "CorporateAction") => {
+            ("CorporateAction", &mut s.corporate_actions.items)
+        }
+        ("SecuritiesInfo", "SecurityInfo") => ("SecurityInfo", &mut s.securities_info.items),
+        ("ConversionRates", "ConversionRate") => ("ConversionRate", &mut s.conversion_rates.items),
+        ("EquitySummaryInBase", "EquitySummaryByReportDateInBase") => {
+            ("EquitySummary", &mut s.equity_summary.items)
+        }
+        ("CashReport", "CashReportCurrency") => ("CashReport", &mut s.cash_report.items),
+        ("DepositsOnHold", "DepositOnHold") => ("DepositOnHold", &mut s.deposits_on_hold.items),
+        ("FdicInsuredDepositsByBank", "FdicInsuredDepositByBank") => {
+            ("FdicInsuredDeposit", &mut s.fdic_insured_deposits.items)
+        }
+        ("IBGNoteTransactions", "IBGNoteTransaction") => {
+            ("IBGNoteTransaction", &mut s.ibg_note_transactions.items)
+        }
+        ("TradeConfirms", "TradeConfirm") => ("TradeConfirm", &mut s.trade_confirms.items),
+        ("OptionEAE", "OptionEAE") => ("OptionEAE", &mut s.option_eae.items),
+        ("FxTransactions", "FxTransaction") => ("FxTransaction", &mut s.fx_transactions.items),
+        ("ChangeInDividendAccruals", "ChangeInDividendAccrual") => (
+            "ChangeInDividendAccrual",
+            &mut s.change_in_dividend_accruals.items,
+        ),
+        ("OpenDividendAccruals", "OpenDividendAccrual") => {
+            ("OpenDividendAccrual", &mut s.open_dividend_accruals.items)
+        }
+        ("InterestAccruals", "InterestAccrualsCurrency") => {
+            ("InterestAccrual", &mut s.interest_accruals.items)
+        }
+        ("Transfers", "Transfer") => ("Transfer", &mut s.transfers.items),
+        ("MTMPerformanceSummaryInBase", "MTMPerformanceSummaryUnderlying") => (
+            "MTMPerformanceSummary",
+            &mut s.mtm_performance_summary.items,
+        ),
+        ("FIFOPerformanceSummaryInBase", "FIFOPerformanceSummaryUnderlying") => (
+            "FIFOPerformanceSummary",
+            &mut s.fifo_performance_summary.items,
+        ),
+        ("MTDYTDPerformanceSummary", "MTDYTDPerformanceSummaryUnderlying") => (
+            "MTDYTDPerformanceSummary",
+            &mut s.mtd_ytd_performance_summary.items,
+        ),
+        ("StmtFunds", "StatementOfFundsLine") => {
+            ("StatementOfFunds", &mut s.statement_of_funds.items)
+        }
+        ("ChangeInPositionValues", "ChangeInPositionValue") => (
+            "ChangeInPositionValue",
+            &mut s.change_in_position_values.items,
+        ),
+        ("UnbundledCommissionDetails", "UnbundledCommissionDetail") => (
+            "UnbundledCommissionDetail",
+            &mut s.unbundled_commission_details.items,
+        ),
+        ("ClientFees", "ClientFee") => ("ClientFee", &mut s.client_fees.items),
+        ("ClientFeesDetails", "ClientFeesDetail") => {
+            ("ClientFeesDetail", &mut s.client_fees_detail.items)
+        }
+        ("SLBActivities", "SLBActivity") => ("SLBActivity", &mut s.slb_activities.items),
+        ("SLBFees", "SLBFee") => ("SLBFee", &mut s.slb_fees.items),
+        ("SLBOpenContracts", "SLBOpenContract") => {
+            ("SLBOpenContract", &mut s.slb_open_contracts.items)
+        }
+        ("SLBCollaterals", "SLBCollateral") => ("SLBCollateral", &mut s.slb_collaterals.items),
+        ("HardToBorrowDetails", "HardToBorrowDetail") => {
+            ("HardToBorrowDetail", &mut s.hard_to_borrow_details.items)
+        }
+        ("FxLots", "FxLot") => ("FxLot", &mut s.fx_lots.items),
+        ("UnsettledTransfers", "UnsettledTransfer") => {
+            ("UnsettledTransfer", &mut s.unsettled_transfers.items)
+        }
+        ("TradeTransfers", "TradeTransfer") => ("TradeTransfer", &mut s.trade_transfers.items),
+        ("PriorPeriodPositions", "PriorPeriodPosition") => {
+            ("PriorPeriodPosition", &mut s.prior_period_positions.items)
+        }
+        ("TierInterestDetails", "TierInterestDetail") => {
+            ("TierInterestDetail", &mut s.tier_interest_details.items)
+        }
+        ("DebitCardActivities", "DebitCardActivity") => {
+            ("DebitCardActivity", &mut s.debit_card_activities.items)
+        }
+        ("SalesTaxes", "SalesTax") => ("SalesTax", &mut s.sales_tax.items),
+        ("NetStockPositions", "NetStockPosition") => {
+            ("NetStockPosition", &mut s.net_stock_positions.items)
+        }
+        ("NetStockPositionSummary", "NetStockPositionSummary" | "NetStockPosition") => (
+            "NetStockPositionSummary",
+            &mut s.net_stock_position_summary.items,
+        ),
+        ("ComplexPositions", "ComplexPosition") => {
+            ("ComplexPosition", &mut s.complex_positions.items)
+        }
+        ("FxPositions", "FxPosition") => ("FxPosition", &mut s.fx_positions.items),
+        ("MutualFundDividendDetails", "MutualFundDividendDetail") => (
+            "MutualFundDividendDetail",
+            &mut s.mutual_fund_dividends.items,
+        ),
+        ("IncentiveCouponAccrualDetails", "IncentiveCouponAccrualDetail") => (
+            "IncentiveCouponAccrualDetail",
+            &mut s.incentive_coupon_accruals.items,
+        ),
+        ("HKIPOOpenSubscriptions", "HKIPOOpenSubscription") => (
+            "HKIPOOpenSubscription",
+            &mut s.hk_ipo_open_subscriptions.items,
+        ),
+        ("HKIPOSubscriptionActivity", "HKIPOSubscriptionActivity") => (
+            "HKIPOSubscriptionActivity",
+            &mut s.hk_ipo_subscription_activity.items,
+        ),
+        ("StockGrantActivities", "StockGrantActivity") => {
+            ("StockGrantActivity", &mut s.stock_grant_activities.items)
+        }
+        ("PendingExcercises", "PendingExercise" | "PendingExcercise") => {
+            ("PendingExercise", &mut s.pending_exercises.items)
+        }
+        ("UnbookedTrades", "UnbookedTrade") => ("UnbookedTrade", &mut s.unbooked_trades.items),
+        ("TransactionTaxes", "TransactionTax") => {
+            ("TransactionTax", &mut s.transaction_taxes.items)
+        }
+        ("CFDCharges", "CFDCharge") => ("CFDCharge", &mut s.cfd_charges.items),
+        ("RoutingCommissions", "RoutingCommission") => {
+            ("RoutingCommission", &mut s.routing_commissions.items)
+        }
+        ("CommissionCredits", "CommissionCredit") => {
+            ("CommissionCredit", &mut s.commission_credits.items)
+        }
+        _ => return None,
+    };
+    Some(rows)
+}
+
+/// A typed row with an `extra_attributes` map
+trait Row: 'static {
+    /// Names serde accepts for the row's fields
+    fn fields() -> &'static [&'static str];
+
+    /// The row's `extra_attributes`
+    fn extra_attributes_mut(&mut self) -> &mut BTreeMap<String, String>;
+
+    /// Whether `attribute="value"` alone fails to deserialize into the row
+    fn rejects(element: &str, attribute: &str, value: &str) -> bool;
+}
+
+macro_rules! impl_row {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Row for $ty {
+                fn fields() -> &'static [&'static str] {
+                    field_names::<$ty>()
+                }
+
+                fn extra_attributes_mut(&mut self) -> &mut BTreeMap<String, String> {
+                    &mut self.extra_attributes
+                }
+
+                fn rejects(element: &str, attribute: &str, value: &str) -> bool {
+                    rejects::<$ty>(element, attribute, value)
+                }
+            }
+        )*
+    };
+}
+
+impl_row!(
+    Trade,
+    Position,
+    CashTransaction,
+    CorporateAction,
+    SecurityInfo,
+    ConversionRate,
+    AccountInformation,
+    ChangeInNAV,
+    EquitySummaryByReportDateInBase,
+    CashReportCurrency,
+    DepositOnHold,
+    FdicInsuredDepositByBank,
+    IBGNoteTransaction,
+    TradeConfirm,
+    OptionEAE,
+    FxTransaction,
+    ChangeInDividendAccrual,
+    OpenDividendAccrual,
+    InterestAccrualsCurrency,
+    Transfer,
+    MTMPerformanceSummaryUnderlying,
+    FIFOPerformanceSummaryUnderlying,
+    MTDYTDPerformanceSummary,
+    StatementOfFundsLine,
+    ChangeInPositionValue,
+    UnbundledCommissionDetail,
+    ClientFee,
+    ClientFeesDetail,
+    SLBActivity,
+    SLBFee,
+    SLBOpenContract,
+    SLBCollateral,
+    HardToBorrowDetail,
+    FxPosition,
+    FxLot,
+    UnsettledTransfer,
+    TradeTransfer,
+    PriorPeriodPosition,
+    TierInterestDetail,
+    DebitCardActivity,
+    SalesTax,
+    NetStockPosition,
+    ComplexPosition,
+    MutualFundDividendDetail,
+    IncentiveCouponAccrualDetail,
+    StockGrantActivity,
+    HKIPOOpenSubscription,
+    HKIPOSubscriptionActivity,
+    PendingExercise,
+    UnbookedTrade,
+    TransactionTax,
+    CFDCharge,
+    RoutingCommission,
+    CommissionCredit,
+);
+
+impl Row for TradeLot {
+    fn fields() -> &'static [&'static str] {
+        Trade::fields()
+    }
+
+    fn extra_attributes_mut(&mut self) -> &mut BTreeMap<String, String> {
+        &mut self.lot.extra_attributes
+    }
+
+    fn rejects(element: &str, attribute: &str, value: &str) -> bool {
+        Trade::rejects(element, attribute, value)
+    }
+}
+
+/// Storage for typed rows, addressed by position
+pub(crate) trait Rows {
+    /// Record the unknown attributes of `e` on the row at `index`
+    fn set_extra_attributes(&mut self, index: usize, e: &BytesStart, known: &mut KnownFields);
+
+    /// Attribute names modeled by the row type
+    fn known_fields<'k>(&self, known: &'k mut KnownFields) -> &'k HashSet<&'static str>;
+
+    /// Whether `attribute="value"` on a row `element` fails to deserialize
+    fn rejects(&self, element: &str, attribute: &str, value: &str) -> bool;
+}
+
+impl<T: Row> Rows for Vec<T> {
+    fn set_extra_attributes(&mut self, index: usize, e: &BytesStart, known: &mut KnownFields) {
+        if let Some(row) = self.get_mut(index) {
+            fill_extra_attributes(row, e, known);
+        }
+    }
+
+    fn known_fields<'k>(&self, known: &'k mut KnownFields) -> &'k HashSet<&'static str> {
+        known.of::<T>()
+    }
+
+    fn rejects(&self, element: &str, attribute: &str, value: &str) -> bool {
+        T::rejects(element, attribute, value)
+    }
+}
+
+impl<T: Row> Rows for Option<T> {
+    fn set_extra_attributes(&mut self, _index: usize, e: &BytesStart, known: &mut KnownFields) {
+        if let Some(row) = self {
+            fill_extra_attributes(row, e, known);
+        }
+    }
+
+    fn known_fields<'k>(&self, known: &'k mut KnownFields) -> &'k HashSet<&'static str> {
+        known.of::<T>()
+    }
+
+    fn rejects(&self, element: &str, attribute: &str, value: &str) -> bool {
+        T::rejects(element, attribute, value)
+    }
+}
+
+fn fill_extra_attributes<T: Row>(row: &mut T, e: &BytesStart, known: &mut KnownFields) {
+    let fields = known.of::<T>();
+    for attr in e.attributes().flatten() {
+        let Ok(key) = std::str::from_utf8(attr.key.as_ref()) else {
+            continue;
+        };
+        if fields.contains(key) {
+            continue;
+        }
+        let value = attr
+            .unescape_value()
+            .map(|v| v.into_owned())
+            .unwrap_or_else(|_| String::from_utf8_lossy(&attr.value).into_owned());
+        row.extra_attributes_mut().insert(key.to_string(), value);
+    }
+}
+
+/// A statement with every section empty, to look up typed rows by name
+pub(crate) fn empty_statement() -> ActivityFlexStatement {
+    quick_xml::de::from_str(
+        r#"<FlexStatement accountId="" fromDate="20000101" toDate="20000101" whenGenerated="" />"#,
+    )
+    .expect("empty statement deserializes")
+}
+
+/// Whether `attribute="value"` on `element` fails to deserialize, given
+/// the typed model of its position below the statement
+///
+/// `ancestors` are the names of the enclosing elements, starting with
+/// `FlexStatement`. `template` is any statement; only its layout is used.
+pub(crate) fn attribute_rejected(
+    template: &mut ActivityFlexStatement,
+    ancestors: &[String],
+    element: &str,
+    attribute: &str,
+    value: &str,
+) -> bool {
+    match ancestors {
+        [] => rejects::<ActivityFlexStatement>(element, attribute, value),
+        [_] => {
+            section_row(template, element).is_some_and(|row| row.rejects(element, attribute, value))
+        }
+        [_, section] => rows(template, section, element)
+            .is_some_and(|(_, rows)| rows.rejects(element, attribute, value)),
+        _ => false,
+    }
+}
+
+/// Whether `<element attribute="value"/>` fails to deserialize into `T` for
+/// another reason than its missing required fields
+fn rejects<T: DeserializeOwned>(element: &str, attribute: &str, value: &str) -> bool {
+    let xml = format!(
+        "<{} {}=\"{}\"/>",
+        element,
+        attribute,
+        quick_xml::escape::escape(value)
+    );
+    let mut de = quick_xml::de::Deserializer::from_str(&xml);
+    let strict = Scope {
+        lenient: false,
+        ..Scope::current()
+    };
+    match strict.run(|| T::deserialize(Noting(&mut de))) {
+        (Ok(_), _) => false,
+        (Err(_), noted) => !matches!(noted.failure, Some(Failure::MissingField(_))),
+    }
+}
+
+/// Attribute names modeled by each row type, built on first use
+#[derive(Default)]
+pub(crate) struct KnownFields(HashMap<TypeId, HashSet<&'static str>>);
+
+impl KnownFields {
+    fn of<T: Row>(&mut self) -> &HashSet<&'static str> {
+        self.0.entry(TypeId::of::<T>()).or_insert_with(|| {
+            T::fields()
+                .iter()
+                .filter_map(|field| field.strip_prefix('@'))
+                .collect()
+        })
+    }
+}
+
+/// Names serde accepts for the fields of struct `T`, aliases included
+///
+/// Runs `T`'s derived `Deserialize` against a deserializer that records the
+/// field list passed to `deserialize_struct` and then bails out.
+pub(crate) fn field_names<T: DeserializeOwned>() -> &'static [&'static str] {
+    struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+    impl<'de> Deserializer<'de> for FieldNames<'_> {
+        type Error = de::value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+            Err(de::Error::custom("not a struct"))
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            *self.0 = fields;
+            Err(de::Error::custom("field names collected"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldNames(&mut fields));
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_names_include_aliases() {
+        let fields = field_names::<TradeConfirm>();
+        assert!(fields.contains(&"@tradePrice"));
+        assert!(fields.contains(&"@price"));
+        assert!(!fields.contains(&"extra_attributes"));
+    }
+
+    #[test]
+    fn test_every_statement_section_captures_rows() {
+        // Each typed section must be registered in `rows` (or `section_row`),
+        // otherwise its rows never receive extra attributes
+        let mut statement: ActivityFlexStatement = quick_xml::de::from_str(
+            r#"<FlexStatement accountId="U1" fromDate="20250101" toDate="20250101" whenGenerated="20250102;000000" />"#,
+        )
+        .unwrap();
+        let registered: HashSet<&str> = [
+            "Trades",
+            "OpenPositions",
+            "CashTransactions",
+            "CorporateActions",
+            "SecuritiesInfo",
+            "ConversionRates",
+            "EquitySummaryInBase",
+            "CashReport",
+            "DepositsOnHold",
+            "FdicInsuredDepositsByBank",
+            "IBGNoteTransactions",
+            "TradeConfirms",
+            "OptionEAE",
+            "FxTransactions",
+            "ChangeInDividendAccruals",
+            "OpenDividendAccruals",
+            "InterestAccruals",
+            "Transfers",
+            "MTMPerformanceSummaryInBase",
+            "FIFOPerformanceSummaryInBase",
+            "MTDYTDPerformanceSummary",
+            "StmtFunds",
+            "ChangeInPositionValues",
+            "UnbundledCommissionDetails",
+            "ClientFees",
+            "ClientFeesDetails",
+            "SLBActivities",
+            "SLBFees",
+            "SLBOpenContracts",
+            "SLBCollaterals",
+            "HardToBorrowDetails",
+            "FxLots",
+            "UnsettledTransfers",
+            "TradeTransfers",
+            "PriorPeriodPositions",
+            "TierInterestDetails",
+            "DebitCardActivities",
+            "SalesTaxes",
+            "NetStockPositions",
+            "NetStockPositionSummary",
+            "ComplexPositions",
+            "FxPositions",
+            "MutualFundDividendDetails",
+            "IncentiveCouponAccrualDetails",
+            "HKIPOOpenSubscriptions",
+            "HKIPOSubscriptionActivity",
+            "StockGrantActivities",
+            "PendingExcercises",
+            "UnbookedTrades",
+            "TransactionTaxes",
+            "CFDCharges",
+            "RoutingCommissions",
+            "CommissionCredits",
+        ]
+        .into_iter()
+        .collect();
+
+        for section in field_names::<ActivityFlexStatement>() {
+            if section.starts_with('@') {
+                continue;
+            }
+            let captured =
+                section_row(&mut statement, section).is_some() || registered.contains(section);
+            assert!(captured, "section {} has no row mapping", section);
+        }
+    }
+
+    #[test]
+    fn test_capture_unknown_section_and_attributes() {
+        let xml = r#"<FlexStatement accountId="U1" fromDate="20250101" toDate="20250101" whenGenerated="20250102;000000">
+<SoftDollars><SoftDollar accountId="U1" amount="1.5"><Detail note="a &amp; b" /></SoftDollar></SoftDollars>
+<CashTransactions><CashTransaction accountId="U1" currency="USD" amount="10" newColumn="x" /></CashTransactions>
+</FlexStatement>"#;
+        let mut statements = vec![quick_xml::de::from_str::<ActivityFlexStatement>(xml).unwrap()];
+        capture(xml, &mut statements);
+
+        let raw = &statements[0].unknown_sections;
+        assert_eq!(raw.len(), 1);
+        assert_eq!(raw[0].name, "SoftDollars");
+        assert_eq!(raw[0].children[0].attribute("amount"), Some("1.5"));
+        assert_eq!(
+            raw[0].children[0].children[0].attribute("note"),
+            Some("a & b")
+        );
+
+        let cash = &statements[0].cash_transactions.items[0];
+        assert_eq!(cash.extra_attributes.len(), 1);
+        assert_eq!(cash.extra_attributes["newColumn"], "x");
+    }
+}
@@ -0,0 +1,353 @@
+//! Schema drift report
+//!
+//! Walks an Activity FLEX document and compares every element and attribute
+//! with the typed model, without deserializing any values. Intended for CI
+//! jobs that should fail as soon as IB adds columns or sections this crate
+//! does not model yet.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::error::{ParseError, Result};
+use crate::parsers::location::{self, Origin};
+use crate::parsers::raw::{self, KnownFields};
+use crate::types::ActivityFlexStatement;
+
+/// Attribute coverage of one typed element
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ElementReport {
+    /// Section element directly below `<FlexStatement>` (e.g. `Trades`), or
+    /// `FlexStatement` for the statement itself
+    pub section: String,
+
+    /// Element name (e.g. `Trade`); equals `section` for sections that are
+    /// a single row, such as `AccountInformation`
+    pub element: String,
+
+    /// Number of elements seen
+    pub occurrences: usize,
+
+    /// Attributes that map to typed fields, with the number of elements
+    /// carrying each
+    pub mapped: BTreeMap<String, usize>,
+
+    /// Attributes with no typed field, with the number of elements carrying
+    /// each; they end up in the row's `extra_attributes`
+    pub unmapped: BTreeMap<String, usize>,
+}
+
+/// An element with no typed counterpart
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnknownElement {
+    /// Section element containing it; equals `element` for unknown sections
+    pub section: String,
+
+    /// Element name
+    pub element: String,
+
+    /// Number of elements seen
+    pub occurrences: usize,
+}
+
+/// Coverage of a FLEX document by the typed model
+///
+/// Built by [`schema_report`]. Elements are listed in order of first
+/// appearance.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaReport {
+    /// Number of `<FlexStatement>` elements walked
+    pub statements: usize,
+
+    /// Typed elements with their mapped and unmapped attributes
+    pub elements: Vec<ElementReport>,
+
+    /// Sections with no typed counterpart; kept in
+    /// [`ActivityFlexStatement::unknown_sections`] when parsing with raw
+    /// capture
+    pub unknown_sections: Vec<UnknownElement>,
+
+    /// Rows of typed sections whose element name has no typed counterpart
+    pub unknown_elements: Vec<UnknownElement>,
+}
+
+impl SchemaReport {
+    /// Whether every element and attribute maps to the typed model
+    pub fn is_clean(&self) -> bool {
+        self.unknown_sections.is_empty()
+            && self.unknown_elements.is_empty()
+            && self.elements.iter().all(|e| e.unmapped.is_empty())
+    }
+
+    /// Report for `element` in `section`, if it was seen
+    pub fn element(&self, section: &str, element: &str) -> Option<&ElementReport> {
+        self.elements
+            .iter()
+            .find(|e| e.section == section && e.element == element)
+    }
+
+    /// Every unmapped attribute as `(element report, attribute, count)`
+    pub fn unmapped_attributes(&self) -> impl Iterator<Item = (&ElementReport, &str, usize)> {
+        self.elements.iter().flat_map(|report| {
+            report
+                .unmapped
+                .iter()
+                .map(move |(name, count)| (report, name.as_str(), *count))
+        })
+    }
+}
+
+impl fmt::Display for SchemaReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} statement(s)", self.statements)?;
+        for report in &self.elements {
+            writeln!(
+                f,
+                "{}/{} ({}): {} mapped, {} unmapped",
+                report.section,
+                report.element,
+                report.occurrences,
+                report.mapped.len(),
+                report.unmapped.len()
+            )?;
+            for (name, count) in &report.unmapped {
+                writeln!(f, "  unmapped @{} ({})", name, count)?;
+            }
+        }
+        for unknown in &self.unknown_sections {
+            writeln!(
+                f,
+                "unknown section {} ({})",
+                unknown.element, unknown.occurrences
+            )?;
+        }
+        for unknown in &self.unknown_elements {
+            writeln!(
+                f,
+                "unknown element {}/{} ({})",
+                unknown.section, unknown.element, unknown.occurrences
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare an Activity FLEX document with the typed model
+///
+/// Every `<FlexStatement>` is walked; attribute values are not parsed, so the
+/// report is produced even for documents that fail to deserialize.
+///
+/// # Arguments
+///
+/// * `xml` - XML string from an IB Activity FLEX query
+///
+/// # Returns
+///
+/// * `Ok(SchemaReport)` - Per-element attribute coverage and unknown sections
+/// * `Err(ParseError)` - The XML is malformed
+///
+/// # Errors
+///
+/// Returns [`ParseError::XmlError`] if the XML is not well-formed.
+pub fn schema_report(xml: &str) -> Result<SchemaReport> {
+    let mut reader = Reader::from_str(xml);
+    let mut builder = Builder::new();
+    // Depth below the current <FlexStatement>, if inside one
+    let mut depth: Option<usize> = None;
+    let mut section = String::new();
+    let mut section_known = false;
+
+    loop {
+        let (e, empty) = match reader.read_event() {
+            Ok(Event::Start(e)) => (e, false),
+            Ok(Event::Empty(e)) => (e, true),
+            Ok(Event::End(_)) => {
+                depth = match depth {
+                    Some(0) | None => None,
+                    Some(d) => Some(d - 1),
+                };
+                continue;
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => continue,
+            Err(e) => {
+                return Err(ParseError::XmlError {
+                    message: format!("Failed to read FLEX XML: {}", e),
+                    location: Some(Box::new(location::syntax_error_location(
+                        xml,
+                        &Origin::default(),
+                    ))),
+                })
+            }
+        };
+        let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+
+        match depth {
+            None if name == "FlexStatement" => {
+                builder.statement(&e);
+                if !empty {
+                    depth = Some(0);
+                }
+                continue;
+            }
+            None => continue,
+            Some(0) => {
+                section_known = builder.section(&name, &e);
+                section = name;
+            }
+            Some(1) if section_known => builder.row(&section, &name, &e),
+            Some(_) => {}
+        }
+
+        if let (Some(d), false) = (depth, empty) {
+            depth = Some(d + 1);
+        }
+    }
+
+    Ok(builder.tally.report)
+}
+
+/// Accumulates a [`SchemaReport`] while walking the document
+struct Builder {
+    tally: Tally,
+    /// Empty statement used to look up the typed rows of a section
+    template: ActivityFlexStatement,
+    sections: HashSet<&'static str>,
+    statement_fields: HashSet<&'static str>,
+    known: KnownFields,
+}
+
+impl Builder {
+    fn new() -> Self {
+        let fields = raw::field_names::<ActivityFlexStatement>();
+        Builder {
+            tally: Tally::default(),
+            template: raw::empty_statement(),
+            sections: fields
+                .iter()
+                .copied()
+                .filter(|f| !f.starts_with('@'))
+                .collect(),
+            statement_fields: fields.iter().filter_map(|f| f.strip_prefix('@')).collect(),
+            known: KnownFields::default(),
+        }
+    }
+
+    fn statement(&mut self, e: &BytesStart) {
+        self.tally.report.statements += 1;
+        self.tally
+            .record("FlexStatement", "FlexStatement", e, &self.statement_fields);
+    }
+
+    /// Record a section element; returns whether the section is typed
+    fn section(&mut self, name: &str, e: &BytesStart) -> bool {
+        if !self.sections.contains(name) {
+            count_unknown(&mut self.tally.report.unknown_sections, name, name);
+            return false;
+        }
+        if let Some(rows) = raw::section_row(&mut self.template, name) {
+            let fields = rows.known_fields(&mut self.known);
+            self.tally.record(name, name, e, fields);
+        }
+        true
+    }
+
+    fn row(&mut self, section: &str, name: &str, e: &BytesStart) {
+        match raw::rows(&mut self.template, section, name) {
+            Some((_, rows)) => {
+                let fields = rows.known_fields(&mut self.known);
+                self.tally.record(section, name, e, fields);
+            }
+            None => count_unknown(&mut self.tally.report.unknown_elements, section, name),
+        }
+    }
+}
+
+/// The report under construction, with an index of its element entries
+#[derive(Default)]
+struct Tally {
+    report: SchemaReport,
+    elements: HashMap<(String, String), usize>,
+}
+
+impl Tally {
+    fn record(&mut self, section: &str, name: &str, e: &BytesStart, fields: &HashSet<&str>) {
+        let elements = &mut self.report.elements;
+        let index = *self
+            .elements
+            .entry((section.to_string(), name.to_string()))
+            .or_insert_with(|| {
+                elements.push(ElementReport {
+                    section: section.to_string(),
+                    element: name.to_string(),
+                    ..Default::default()
+                });
+                elements.len() - 1
+            });
+        let report = &mut elements[index];
+        report.occurrences += 1;
+
+        for attr in e.attributes().flatten() {
+            let key = String::from_utf8_lossy(attr.key.as_ref());
+            let counts = if fields.contains(key.as_ref()) {
+                &mut report.mapped
+            } else {
+                &mut report.unmapped
+            };
+            match counts.get_mut(key.as_ref()) {
+                Some(count) => *count += 1,
+                None => {
+                    counts.insert(key.into_owned(), 1);
+                }
+            }
+        }
+    }
+}
+
+fn count_unknown(unknown: &mut Vec<UnknownElement>, section: &str, element: &str) {
+    match unknown
+        .iter_mut()
+        .find(|u| u.section == section && u.element == element)
+    {
+        Some(u) => u.occurrences += 1,
+        None => unknown.push(UnknownElement {
+            section: section.to_string(),
+            element: element.to_string(),
+            occurrences: 1,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_report_clean_fixture() {
+        let xml = include_str!("../../tests/fixtures/activity_costs.xml");
+        let report = schema_report(xml).unwrap();
+
+        assert_eq!(report.statements, 1);
+        assert!(report.is_clean(), "{}", report);
+        assert!(report
+            .element("TransactionTaxes", "TransactionTax")
+            .is_some());
+    }
+
+    #[test]
+    fn test_schema_report_malformed() {
+        let err = schema_report("<FlexStatement><Trades></FlexStatement>").unwrap_err();
+        assert!(matches!(err, ParseError::XmlError { .. }));
+        assert!(
+            err.to_string().contains("Failed to read FLEX XML"),
+            "{}",
+            err
+        );
+        assert_eq!(
+            err.location().unwrap().element_path,
+            "FlexStatement[0]/Trades"
+        );
+    }
+}
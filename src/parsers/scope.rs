@@ -0,0 +1,159 @@
+//! Per-thread state of a running deserialization
+//!
+//! The derived `Deserialize` impls give the FLEX value deserializers no way
+//! to reach the caller, so what they need (the date format profile, whether
+//! invalid values are rejected) and what they note (unknown enum values,
+//! rejected values, the failure) live in one thread-local [`Scope`].
+//! [`Scope::run`] installs a scope for the duration of a closure and hands
+//! back the [`Noted`] values; nested runs restore the enclosing scope when
+//! they end, even on panic.
+//!
+//! Threads start outside any scope. Work moved to another thread (rayon's
+//! workers) runs in a copy of [`Scope::current`].
+
+use std::cell::RefCell;
+
+use crate::parsers::date_format::DateFormatProfile;
+use crate::parsers::inventory::Rejected;
+use crate::parsers::location::Failure;
+use crate::types::UnknownValue;
+
+/// Settings of a deserialization
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Scope {
+    /// Profile used by the date deserializers (`None` for the default formats)
+    pub profile: Option<DateFormatProfile>,
+    /// Whether the optional value deserializers turn invalid values into `None`
+    pub lenient: bool,
+}
+
+/// What the deserializers noted while a [`Scope`] ran
+#[derive(Debug, Default)]
+pub(crate) struct Noted {
+    /// Unrecognized enum values in order of first appearance
+    pub unknown_values: Vec<UnknownValue>,
+    /// Values rejected in lenient mode, in document order
+    pub rejected: Vec<Rejected>,
+    /// Failure noted last
+    pub failure: Option<Failure>,
+}
+
+/// A running scope
+struct State {
+    scope: Scope,
+    noted: Noted,
+}
+
+thread_local! {
+    /// Innermost running scope on this thread
+    static STATE: RefCell<Option<State>> = const { RefCell::new(None) };
+}
+
+impl Scope {
+    /// Settings of the innermost running scope, or the defaults outside any
+    pub(crate) fn current() -> Scope {
+        STATE.with(|state| state.borrow().as_ref().map(|s| s.scope).unwrap_or_default())
+    }
+
+    /// Run `f` in this scope, returning what was noted meanwhile
+    pub(crate) fn run<T>(self, f: impl FnOnce() -> T) -> (T, Noted) {
+        /// Restores the enclosing scope even if `f` panics
+        struct Restore(Option<State>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let outer = self.0.take();
+                STATE.with(|state| *state.borrow_mut() = outer);
+            }
+        }
+
+        let _restore = Restore(STATE.with(|state| {
+            state.borrow_mut().replace(State {
+                scope: self,
+                noted: Noted::default(),
+            })
+        }));
+        let result = f();
+        let noted = STATE.with(|state| state.borrow_mut().take().map(|s| s.noted));
+        (result, noted.unwrap_or_default())
+    }
+}
+
+/// Update the notes of the innermost running scope with `f`; returns `None`
+/// outside any scope
+pub(crate) fn noting<R>(f: impl FnOnce(Scope, &mut Noted) -> R) -> Option<R> {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let state = state.as_mut()?;
+        Some(f(state.scope, &mut state.noted))
+    })
+}
+
+/// Run `f`, returning the unknown values recorded meanwhile in order of first
+/// appearance
+///
+/// Inside a scope only the unknown values are set apart; rejected values and
+/// failures are still noted in the enclosing scope. Outside any scope `f`
+/// runs in a default one.
+pub(crate) fn collecting<T>(f: impl FnOnce() -> T) -> (T, Vec<UnknownValue>) {
+    let Some(outer) = noting(|_, noted| std::mem::take(&mut noted.unknown_values)) else {
+        let (result, noted) = Scope::default().run(f);
+        return (result, noted.unknown_values);
+    };
+    let result = f();
+    let values = noting(|_, noted| std::mem::replace(&mut noted.unknown_values, outer));
+    (result, values.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::date_format::DateFormat;
+    use crate::parsers::inventory;
+
+    #[test]
+    fn test_runs_are_scoped() {
+        let profile = DateFormatProfile::new(DateFormat::DayMonthYear);
+        assert_eq!(Scope::current(), Scope::default());
+
+        let scope = Scope {
+            profile: Some(profile),
+            lenient: true,
+        };
+        let ((), noted) = scope.run(|| {
+            assert_eq!(Scope::current(), scope);
+            assert!(inventory::reject("abc", "not a number"));
+
+            let ((), inner) = Scope::default().run(|| {
+                assert_eq!(Scope::current(), Scope::default());
+                assert!(!inventory::reject("def", "not a number"));
+            });
+            assert!(inner.rejected.is_empty());
+            assert_eq!(Scope::current(), scope);
+        });
+
+        assert_eq!(Scope::current(), Scope::default());
+        assert_eq!(noted.rejected.len(), 1);
+        assert_eq!(noted.rejected[0].value, "abc");
+    }
+
+    #[test]
+    fn test_collecting_keeps_other_notes() {
+        let scope = Scope {
+            lenient: true,
+            ..Scope::default()
+        };
+        let ((), noted) = scope.run(|| {
+            inventory::record("AssetCategory", "XYZ");
+            let ((), values) = collecting(|| {
+                inventory::record("BuySell", "HOLD");
+                inventory::reject("abc", "not a number");
+            });
+            assert_eq!(values.len(), 1);
+            assert_eq!(values[0].enum_name, "BuySell");
+        });
+
+        assert_eq!(noted.unknown_values.len(), 1);
+        assert_eq!(noted.unknown_values[0].enum_name, "AssetCategory");
+        assert_eq!(noted.rejected.len(), 1);
+    }
+}
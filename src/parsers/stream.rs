@@ -0,0 +1,659 @@
+//! Streaming Activity FLEX parser
+//!
+//! Large backfills contain hundreds of `<FlexStatement>` elements. Instead of
+//! deserializing the whole `FlexQueryResponse` at once, [`ActivityFlexStream`]
+//! scans the document with quick-xml's pull reader, copies one statement at a
+//! time into a reusable buffer, and deserializes only that statement. Memory use
+//! stays close to the size of the largest single statement.
+
+use std::io::{self, BufRead, Read};
+
+use chrono::NaiveDate;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, Writer};
+
+use crate::error::{ErrorLocation, ParseError, Result};
+use crate::parsers::date_format;
+use crate::parsers::location::{self, Origin};
+use crate::parsers::options::{self, ParseOptions, ParseWarning, ParsedStatement, SectionSet};
+use crate::parsers::xml_utils::parse_flex_date;
+use crate::types::ActivityFlexStatement;
+use crate::version;
+
+/// Element name of a single statement inside a FLEX response
+const FLEX_STATEMENT: &[u8] = b"FlexStatement";
+
+/// Root element of a FLEX response
+const FLEX_QUERY_RESPONSE: &[u8] = b"FlexQueryResponse";
+
+/// Iterator over the Activity FLEX statements in a reader
+///
+/// Yields one `Result<ActivityFlexStatement>` per `<FlexStatement>` element,
+/// in document order. A statement that fails to deserialize yields an `Err`
+/// and the iterator moves on to the next statement. Malformed XML that
+/// prevents finding the next statement boundary ends the iteration after
+/// yielding the error.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ib_flex::parsers::ActivityFlexStream;
+/// use std::fs::File;
+/// use std::io::BufReader;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let file = BufReader::new(File::open("backfill.xml")?);
+///
+/// for statement in ActivityFlexStream::new(file) {
+///     let statement = statement?;
+///     println!("{}: {} trades", statement.to_date, statement.trades.items.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ActivityFlexStream<R> {
+    reader: Reader<Tracked<R>>,
+    /// Scratch buffer for reader events
+    buf: Vec<u8>,
+    /// Raw XML of the current statement
+    statement: Vec<u8>,
+    /// Position of the current statement in the document
+    origin: Origin,
+    /// Identifying attributes of the current statement
+    header: StatementHeader,
+    /// Number of statements returned so far
+    index: usize,
+    /// Strict or lenient parsing of each statement
+    options: ParseOptions,
+    /// Set once the end of the document (or a fatal XML error) is reached
+    finished: bool,
+}
+
+/// Outcome of parsing one `<FlexStatement>` of a multi-statement document
+///
+/// The identifying attributes are read straight from the statement's start
+/// tag, so they are available even when the statement itself fails to
+/// deserialize. This lets ingestion jobs load the good days of a backfill and
+/// quarantine the bad ones.
+#[derive(Debug)]
+pub struct StatementResult {
+    /// Zero-based position of the statement in the document
+    pub index: usize,
+
+    /// `accountId` attribute of the statement, if present
+    pub account_id: Option<String>,
+
+    /// `fromDate` attribute of the statement, if present and valid
+    pub from_date: Option<NaiveDate>,
+
+    /// `toDate` attribute of the statement, if present and valid
+    pub to_date: Option<NaiveDate>,
+
+    /// Parsed statement or the error that prevented parsing it
+    pub result: Result<ActivityFlexStatement>,
+
+    /// Warnings raised while parsing the statement (empty on error)
+    pub warnings: Vec<ParseWarning>,
+}
+
+/// Identifying attributes of a `<FlexStatement>` start tag
+#[derive(Debug, Clone, Default)]
+struct StatementHeader {
+    account_id: Option<String>,
+    from_date: Option<NaiveDate>,
+    to_date: Option<NaiveDate>,
+}
+
+impl StatementHeader {
+    fn from_start(start: &BytesStart) -> Self {
+        let attr = |name: &str| {
+            start
+                .try_get_attribute(name)
+                .ok()
+                .flatten()
+                .and_then(|a| a.unescape_value().ok())
+                .map(|v| v.into_owned())
+                .filter(|v| !v.is_empty())
+        };
+        let date = |name: &str| attr(name).and_then(|v| parse_flex_date(&v).ok());
+
+        StatementHeader {
+            account_id: attr("accountId"),
+            from_date: date("fromDate"),
+            to_date: date("toDate"),
+        }
+    }
+}
+
+impl<R: BufRead> ActivityFlexStream<R> {
+    /// Create a stream over an Activity FLEX XML reader
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Buffered reader over the XML of an IB Activity FLEX query
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, ParseOptions::default())
+    }
+
+    /// Create a stream that parses each statement with the given options
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Buffered reader over the XML of an IB Activity FLEX query
+    /// * `options` - Strict or lenient handling of invalid values
+    ///
+    /// Warnings are available through [`ActivityFlexStream::next_result`].
+    pub fn with_options(reader: R, options: ParseOptions) -> Self {
+        ActivityFlexStream {
+            reader: Reader::from_reader(Tracked::new(reader)),
+            buf: Vec::new(),
+            statement: Vec::new(),
+            origin: Origin::default(),
+            header: StatementHeader::default(),
+            index: 0,
+            options,
+            finished: false,
+        }
+    }
+
+    /// Parse the next statement, tagged with its position and identifying attributes
+    ///
+    /// Unlike the `Iterator` implementation, this keeps the index, account and
+    /// date range of each statement alongside its result. Returns `None` once
+    /// the document has no more statements.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ib_flex::parsers::ActivityFlexStream;
+    /// use std::fs::File;
+    /// use std::io::BufReader;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut stream = ActivityFlexStream::new(BufReader::new(File::open("backfill.xml")?));
+    ///
+    /// while let Some(outcome) = stream.next_result() {
+    ///     match outcome.result {
+    ///         Ok(statement) => println!("loaded {}", statement.to_date),
+    ///         Err(e) => eprintln!("quarantined #{} ({:?}): {}", outcome.index, outcome.to_date, e),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn next_result(&mut self) -> Option<StatementResult> {
+        if self.finished {
+            return None;
+        }
+
+        let parsed = match self.read_statement() {
+            Ok(true) => self.deserialize_statement(),
+            Ok(false) => {
+                self.finished = true;
+                return None;
+            }
+            Err(e) => {
+                self.finished = true;
+                Err(e)
+            }
+        };
+
+        let header = std::mem::take(&mut self.header);
+        let index = self.index;
+        self.index += 1;
+        let (result, warnings) = match parsed {
+            Ok(parsed) => (Ok(parsed.statement), parsed.warnings),
+            Err(e) => (Err(e), Vec::new()),
+        };
+
+        Some(StatementResult {
+            index,
+            account_id: header.account_id,
+            from_date: header.from_date,
+            to_date: header.to_date,
+            result,
+            warnings,
+        })
+    }
+
+    /// Copy the raw XML of the next `<FlexStatement>` into `self.statement`
+    ///
+    /// Returns `Ok(false)` once the document has no more statements.
+    fn read_statement(&mut self) -> Result<bool> {
+        let Self {
+            reader,
+            buf,
+            statement,
+            origin,
+            header,
+            index,
+            options,
+            ..
+        } = self;
+
+        *header = StatementHeader::default();
+        loop {
+            buf.clear();
+            let position = reader.get_ref().origin();
+            let event = match reader.read_event_into(buf) {
+                Ok(event) => event,
+                Err(e) => {
+                    let location = reader_error_location(reader, &position);
+                    return Err(xml_error(e, Some(location)));
+                }
+            };
+
+            let (empty, tag_len) = match &event {
+                Event::Start(e) if e.name().as_ref() == FLEX_STATEMENT => (false, e.len() + 2),
+                Event::Empty(e) if e.name().as_ref() == FLEX_STATEMENT => (true, e.len() + 3),
+                Event::Start(e) | Event::Empty(e) if e.name().as_ref() == FLEX_QUERY_RESPONSE => {
+                    version::check_element(e)?;
+                    continue;
+                }
+                Event::Eof => return Ok(false),
+                _ => continue,
+            };
+            if let Event::Start(e) | Event::Empty(e) = &event {
+                *header = date_format::with_profile(options.date_format, || {
+                    StatementHeader::from_start(e)
+                });
+                version::check_element(e)?;
+            }
+
+            // Events are copied verbatim, so the statement starts at its `<`. The
+            // reader may already have consumed that `<` while reading the
+            // preceding text, which puts `position` one character past it.
+            let start = reader.get_ref().offset - tag_len;
+            let skipped = position.offset.saturating_sub(start);
+            *origin = Origin {
+                offset: start,
+                line: position.line,
+                column: position.column.saturating_sub(skipped),
+                statement_index: Some(*index),
+            };
+
+            statement.clear();
+            let mut writer = Writer::new(&mut *statement);
+            let copied = match writer.write_event(event) {
+                Err(e) => Err(e.into()),
+                Ok(()) if empty => Ok(true),
+                Ok(()) => copy_element(reader, buf, &mut writer, options.sections.as_ref()),
+            };
+
+            // The copied prefix is well-formed, so any error sits right after it
+            let location = || {
+                let partial = String::from_utf8_lossy(statement);
+                Some(location::syntax_error_location(&partial, origin))
+            };
+            return match copied {
+                Ok(true) => Ok(true),
+                Ok(false) => Err(ParseError::XmlError {
+                    message: "Unexpected end of FLEX XML inside FlexStatement".to_string(),
+                    location: location().map(Box::new),
+                }),
+                Err(e) => Err(xml_error(e, location())),
+            };
+        }
+    }
+
+    /// Deserialize the statement currently held in `self.statement`
+    fn deserialize_statement(&self) -> Result<ParsedStatement> {
+        let xml = std::str::from_utf8(&self.statement).map_err(|e| ParseError::XmlError {
+            message: format!("Failed to parse FLEX XML: {}", e),
+            location: None,
+        })?;
+        date_format::parse_with(xml, self.options.date_format, || {
+            options::parse_statement(xml, &self.options, &self.origin)
+        })
+    }
+}
+
+impl<R: BufRead> Iterator for ActivityFlexStream<R> {
+    type Item = Result<ActivityFlexStatement>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_result().map(|outcome| outcome.result)
+    }
+}
+
+impl<R: BufRead> std::iter::FusedIterator for ActivityFlexStream<R> {}
+
+/// Copy events into `writer` until the element whose start tag was just read is closed
+///
+/// Child elements not in `sections` are skipped without being written. Each is
+/// replaced by whitespace of the same length and line count, so offsets, lines
+/// and columns in the copy still match the document.
+///
+/// Returns `Ok(false)` if the document ends before the element is closed.
+fn copy_element<R: BufRead, W: io::Write>(
+    reader: &mut Reader<Tracked<R>>,
+    buf: &mut Vec<u8>,
+    writer: &mut Writer<W>,
+    sections: Option<&SectionSet>,
+) -> std::result::Result<bool, quick_xml::Error> {
+    let mut depth = 1usize;
+    // Offset and line where the child being skipped starts
+    let mut skipping: Option<(usize, usize)> = None;
+
+    while depth > 0 {
+        buf.clear();
+        let line = reader.get_ref().line;
+        let event = reader.read_event_into(buf)?;
+
+        if let (1, None, Some(sections)) = (depth, skipping, sections) {
+            let tag_len = match &event {
+                Event::Start(e) => Some(e.len() + 2),
+                Event::Empty(e) => Some(e.len() + 3),
+                _ => None,
+            };
+            let wanted = |e: &BytesStart| {
+                std::str::from_utf8(e.name().as_ref()).is_ok_and(|name| sections.contains(name))
+            };
+            if let (Some(tag_len), Event::Start(e) | Event::Empty(e)) = (tag_len, &event) {
+                if !wanted(e) {
+                    skipping = Some((reader.get_ref().offset - tag_len, line));
+                }
+            }
+        }
+
+        let eof = matches!(event, Event::Eof);
+        match &event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth -= 1,
+            _ => {}
+        }
+        match skipping {
+            None if !eof => writer.write_event(event)?,
+            Some((start, line)) if depth == 1 || eof => {
+                blank(writer.get_mut(), reader.get_ref(), start, line)?;
+                skipping = None;
+            }
+            _ => {}
+        }
+        if eof {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Write whitespace standing in for the skipped input from `start` (on
+/// `line`) up to the current position of `tracked`
+fn blank<R, W: io::Write>(
+    out: &mut W,
+    tracked: &Tracked<R>,
+    start: usize,
+    line: usize,
+) -> io::Result<()> {
+    let len = tracked.offset - start;
+    let lines = tracked.line - line;
+    // Spaces after the last newline put the next byte at the same column
+    let tail = if lines == 0 {
+        0
+    } else {
+        tracked.column.min(len - lines)
+    };
+
+    for (byte, count) in [(b' ', len - lines - tail), (b'\n', lines), (b' ', tail)] {
+        io::copy(&mut io::repeat(byte).take(count as u64), out)?;
+    }
+    Ok(())
+}
+
+/// Location of a reader error outside any statement
+///
+/// `position` is where the failing read started; the error offset reported by
+/// the reader is assumed to be on the same line.
+fn reader_error_location<R>(reader: &Reader<Tracked<R>>, position: &Origin) -> ErrorLocation {
+    let byte_offset = (reader.error_position() as usize).max(position.offset);
+    ErrorLocation {
+        byte_offset,
+        line: position.line + 1,
+        column: position.column + (byte_offset - position.offset) + 1,
+        element_path: String::new(),
+        attribute: None,
+        raw_value: None,
+    }
+}
+
+/// Convert a quick-xml reader error into a `ParseError`
+fn xml_error(e: quick_xml::Error, location: Option<ErrorLocation>) -> ParseError {
+    ParseError::XmlError {
+        message: format!("Failed to read FLEX XML: {}", e),
+        location: location.map(Box::new),
+    }
+}
+
+/// `BufRead` adapter that tracks the line and column of the consumed input
+struct Tracked<R> {
+    inner: R,
+    /// Bytes consumed so far
+    offset: usize,
+    /// Zero-based line of the next byte
+    line: usize,
+    /// Zero-based column (in characters) of the next byte
+    column: usize,
+}
+
+impl<R> Tracked<R> {
+    fn new(inner: R) -> Self {
+        Tracked {
+            inner,
+            offset: 0,
+            line: 0,
+            column: 0,
+        }
+    }
+
+    /// Position of the next byte to be consumed
+    fn origin(&self) -> Origin {
+        Origin {
+            offset: self.offset,
+            line: self.line,
+            column: self.column,
+            statement_index: None,
+        }
+    }
+}
+
+impl<R: BufRead> Read for Tracked<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(out.len());
+        out[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for Tracked<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The bytes being consumed are still buffered, so this does no I/O
+        if let Ok(buffered) = self.inner.fill_buf() {
+            for &byte in &buffered[..amt.min(buffered.len())] {
+                if byte == b'\n' {
+                    self.line += 1;
+                    self.column = 0;
+                } else if byte & 0xC0 != 0x80 {
+                    // Count characters, not UTF-8 continuation bytes
+                    self.column += 1;
+                }
+            }
+        }
+        self.offset += amt;
+        self.inner.consume(amt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_backfill_statements_in_order() {
+        let xml = include_str!("../../tests/fixtures/activity_backfill.xml");
+        let statements = ActivityFlexStream::new(xml.as_bytes())
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(statements.len(), 3);
+        assert_eq!(statements[0].from_date.to_string(), "2025-01-01");
+        assert_eq!(statements[2].from_date.to_string(), "2025-01-03");
+    }
+
+    #[test]
+    fn test_stream_matches_parse_activity_flex_all() {
+        let xml = include_str!("../../tests/fixtures/activity_backfill.xml");
+        let streamed = ActivityFlexStream::new(xml.as_bytes())
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        let parsed = crate::parsers::parse_activity_flex_all(xml).unwrap();
+
+        assert_eq!(streamed, parsed);
+    }
+
+    #[test]
+    fn test_stream_skips_bad_statement() {
+        let xml = r#"<FlexQueryResponse queryName="test" type="AF">
+<FlexStatements count="3">
+<FlexStatement accountId="U1" fromDate="2025-01-01" toDate="2025-01-01" whenGenerated="2025-01-02;000000" />
+<FlexStatement accountId="U1" fromDate="not-a-date" toDate="2025-01-02" whenGenerated="2025-01-03;000000"><Trades /></FlexStatement>
+<FlexStatement accountId="U1" fromDate="2025-01-03" toDate="2025-01-03" whenGenerated="2025-01-04;000000"><Trades /></FlexStatement>
+</FlexStatements>
+</FlexQueryResponse>"#;
+
+        let results: Vec<_> = ActivityFlexStream::new(xml.as_bytes()).collect();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert_eq!(
+            results[2].as_ref().unwrap().from_date.to_string(),
+            "2025-01-03"
+        );
+    }
+
+    #[test]
+    fn test_next_result_tags_statements() {
+        let xml = r#"<FlexQueryResponse><FlexStatements count="2">
+<FlexStatement accountId="U1" fromDate="20250101" toDate="20250101" whenGenerated="20250102;000000" />
+<FlexStatement accountId="U2" fromDate="2025-01-02" toDate="2025-01-02"><Trades /></FlexStatement>
+</FlexStatements></FlexQueryResponse>"#;
+
+        let mut stream = ActivityFlexStream::new(xml.as_bytes());
+
+        let first = stream.next_result().unwrap();
+        assert_eq!(first.index, 0);
+        assert_eq!(first.account_id.as_deref(), Some("U1"));
+        assert_eq!(first.to_date.unwrap().to_string(), "2025-01-01");
+        assert!(first.result.is_ok());
+
+        // Missing whenGenerated fails the statement but keeps its tags
+        let second = stream.next_result().unwrap();
+        assert_eq!(second.index, 1);
+        assert_eq!(second.account_id.as_deref(), Some("U2"));
+        assert_eq!(second.from_date.unwrap().to_string(), "2025-01-02");
+        assert!(second.result.is_err());
+
+        assert!(stream.next_result().is_none());
+    }
+
+    #[test]
+    fn test_stream_rejects_unsupported_version() {
+        let xml = r#"<?xml version="1.0"?>
+<FlexQueryResponse queryName="test" type="AF" version="2"><FlexStatements count="1">
+<FlexStatement accountId="U1" fromDate="2025-01-01" toDate="2025-01-01" whenGenerated="2025-01-02;000000" />
+</FlexStatements></FlexQueryResponse>"#;
+
+        let mut stream = ActivityFlexStream::new(xml.as_bytes());
+        let err = stream.next().unwrap().unwrap_err();
+        assert!(matches!(err, ParseError::UnsupportedSchemaVersion(ref v) if v == "2"));
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn test_stream_truncated_document() {
+        let xml = r#"<FlexQueryResponse><FlexStatements count="1">
+<FlexStatement accountId="U1" fromDate="2025-01-01" toDate="2025-01-01" whenGenerated="2025-01-02;000000">
+<Trades>"#;
+
+        let mut stream = ActivityFlexStream::new(xml.as_bytes());
+        let err = stream.next().unwrap().unwrap_err();
+        assert!(stream.next().is_none());
+
+        let location = err.location().unwrap();
+        assert_eq!(location.element_path, "FlexStatement[0]/Trades");
+        assert_eq!(location.byte_offset, xml.len());
+        assert_eq!((location.line, location.column), (3, 9));
+    }
+
+    #[test]
+    fn test_stream_skips_unrequested_sections() {
+        let xml = include_str!("../../tests/fixtures/activity_backfill.xml");
+        let options = ParseOptions::default()
+            .with_sections(SectionSet::from_names(["Trades", "OpenPositions"]).unwrap());
+        let selected = ActivityFlexStream::with_options(xml.as_bytes(), options)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        let full = crate::parsers::parse_activity_flex_all(xml).unwrap();
+
+        assert_eq!(selected.len(), full.len());
+        for (selected, full) in selected.iter().zip(&full) {
+            assert_eq!(selected.trades, full.trades);
+            assert_eq!(selected.positions, full.positions);
+            assert!(selected.account_information.is_none());
+            assert!(selected.cash_transactions.items.is_empty());
+            assert!(selected.corporate_actions.items.is_empty());
+        }
+        assert!(!full[0].cash_transactions.items.is_empty());
+    }
+
+    #[test]
+    fn test_stream_skipped_sections_keep_error_locations() {
+        let xml = r#"<FlexQueryResponse><FlexStatements count="1">
+<FlexStatement accountId="U1" fromDate="20250101" toDate="20250101" whenGenerated="20250102;000000">
+<CashTransactions>
+<CashTransaction accountId="U1" currency="EUR" amount="1" description="Café" />
+</CashTransactions><Skipped a="1"
+  b="2" />  <Trades><Trade accountId="U1" currency="USD" assetCategory="STK" symbol="X" tradeDate="20250101" quantity="abc" />
+</Trades>
+</FlexStatement>
+</FlexStatements></FlexQueryResponse>"#;
+
+        let full = ActivityFlexStream::new(xml.as_bytes())
+            .next()
+            .unwrap()
+            .unwrap_err();
+        let options =
+            ParseOptions::default().with_sections(SectionSet::from_names(["Trades"]).unwrap());
+        let selected = ActivityFlexStream::with_options(xml.as_bytes(), options)
+            .next()
+            .unwrap()
+            .unwrap_err();
+
+        let (full, selected) = (full.location().unwrap(), selected.location().unwrap());
+        assert_eq!(selected.attribute.as_deref(), Some("quantity"));
+        assert_eq!(
+            (selected.byte_offset, selected.line, selected.column),
+            (full.byte_offset, full.line, full.column)
+        );
+    }
+
+    #[test]
+    fn test_stream_truncated_inside_skipped_section() {
+        let xml = r#"<FlexQueryResponse><FlexStatements count="1">
+<FlexStatement accountId="U1" fromDate="2025-01-01" toDate="2025-01-01" whenGenerated="2025-01-02;000000">
+<StmtFunds>"#;
+
+        let options =
+            ParseOptions::default().with_sections(SectionSet::from_names(["Trades"]).unwrap());
+        let mut stream = ActivityFlexStream::with_options(xml.as_bytes(), options);
+        let err = stream.next().unwrap().unwrap_err();
+        assert!(stream.next().is_none());
+        assert_eq!(err.location().unwrap().byte_offset, xml.len());
+    }
+}
@@ -1,8 +1,11 @@
 //! Trade Confirmation FLEX parser
 
 use crate::error::{ParseError, Result};
+use crate::parsers::date_format;
+use crate::parsers::location::{self, Origin};
+use crate::types::trade_confirmation::TradeConfirmationResponse;
 use crate::types::TradeConfirmationStatement;
-use quick_xml::de::from_str;
+use crate::version;
 
 /// Parse a Trade Confirmation FLEX XML statement
 ///
@@ -10,6 +13,9 @@ use quick_xml::de::from_str;
 /// They are similar to Activity FLEX but focused only on trade executions
 /// and are updated immediately after each trade.
 ///
+/// Accepts both a `<FlexQueryResponse type="TCF">` document, returning its
+/// first statement, and the legacy `<TradeConfirmationStatement>` layout.
+///
 /// # Arguments
 ///
 /// * `xml` - XML string from IB Trade Confirmation FLEX query
@@ -34,15 +40,51 @@ use quick_xml::de::from_str;
 /// let statement = parse_trade_confirmation(&xml)?;
 ///
 /// println!("Account: {}", statement.account_id);
-/// println!("Trades: {}", statement.trades.items.len());
+/// println!("Confirmations: {}", statement.trade_confirms.items.len());
 /// # Ok(())
 /// # }
 /// ```
 pub fn parse_trade_confirmation(xml: &str) -> Result<TradeConfirmationStatement> {
+    parse_trade_confirmation_all(xml)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| ParseError::MissingField {
+            field: "FlexStatement".to_string(),
+            context: "FlexQueryResponse".to_string(),
+            location: None,
+        })
+}
+
+/// Parse all Trade Confirmation FLEX statements from XML
+///
+/// A `type="TCF"` response holds one `<FlexStatement>` per account. The
+/// legacy `<TradeConfirmationStatement>` layout yields a single statement.
+///
+/// # Arguments
+///
+/// * `xml` - XML string from IB Trade Confirmation FLEX query
+///
+/// # Returns
+///
+/// * `Ok(Vec<TradeConfirmationStatement>)` - All parsed statements
+/// * `Err(ParseError)` - Parse error with context
+pub fn parse_trade_confirmation_all(xml: &str) -> Result<Vec<TradeConfirmationStatement>> {
+    version::check_version(xml)?;
+
+    let is_response = version::root_element(xml)?
+        .is_some_and(|root| root.name().as_ref() == b"FlexQueryResponse");
+
     // Parse the XML using serde and quick-xml
-    from_str(xml).map_err(|e| ParseError::XmlError {
-        message: format!("Failed to parse Trade Confirmation FLEX XML: {}", e),
-        location: None,
+    date_format::parse_with(xml, None, || {
+        if is_response {
+            let response: TradeConfirmationResponse =
+                location::deserialize(xml, &Origin::default(), "Trade Confirmation FLEX XML")?;
+            Ok(response.statements.statements)
+        } else {
+            let statement: TradeConfirmationStatement =
+                location::deserialize(xml, &Origin::default(), "Trade Confirmation FLEX XML")?;
+            Ok(vec![statement])
+        }
     })
 }
 
@@ -101,6 +143,38 @@ mod tests {
         assert_eq!(statement.trades.items.len(), 0);
     }
 
+    #[test]
+    fn test_parse_tcf_response() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<FlexQueryResponse queryName="Confirms" type="TCF">
+    <FlexStatements count="2">
+        <FlexStatement accountId="U1234567" fromDate="2025-01-15" toDate="2025-01-15" period="Today" whenGenerated="2025-01-15;160501">
+            <TradeConfirms>
+                <TradeConfirm accountId="U1234567" execID="0000e0d5.6784a3b2.01.01" tradeDate="2025-01-15" symbol="AAPL" assetCategory="STK" quantity="100" price="150.50" amount="15050" commission="-1.00" commissionCurrency="USD" regulatoryFee="-0.02" awayBrokerCommission="-0.35" />
+            </TradeConfirms>
+        </FlexStatement>
+        <FlexStatement accountId="U7654321" fromDate="2025-01-15" toDate="2025-01-15" period="Today" whenGenerated="2025-01-15;160501">
+            <TradeConfirms />
+        </FlexStatement>
+    </FlexStatements>
+</FlexQueryResponse>"#;
+
+        let statements = parse_trade_confirmation_all(xml).unwrap();
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[1].account_id, "U7654321");
+        assert!(statements[1].trade_confirms.items.is_empty());
+
+        let confirm = &statements[0].trade_confirms.items[0];
+        assert_eq!(confirm.trade_price.to_string(), "150.50");
+        assert_eq!(confirm.commission.unwrap().to_string(), "-1.00");
+        assert_eq!(confirm.regulatory_fee.unwrap().to_string(), "-0.02");
+        assert_eq!(confirm.away_broker_commission.unwrap().to_string(), "-0.35");
+
+        let first = parse_trade_confirmation(xml).unwrap();
+        assert_eq!(first.account_id, "U1234567");
+        assert_eq!(first.period.as_deref(), Some("Today"));
+    }
+
     #[test]
     fn test_parse_malformed_trade_confirmation() {
         let xml = r#"<Invalid>XML</Invalid>"#;
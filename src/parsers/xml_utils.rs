@@ -1,18 +1,41 @@
 //! XML parsing utilities and custom deserializers
 
+use std::borrow::Cow;
+
+use crate::parsers::location::{self, ValueKind};
+use crate::parsers::{date_format, inventory};
 use crate::types::common::TransactionCode;
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Deserializer, Serializer};
 
+/// Attribute text, borrowed from the input unless it contains escapes
+#[derive(Deserialize)]
+struct Text<'a>(#[serde(borrow)] Cow<'a, str>);
+
+/// Deserialize an optional string that borrows from the input where possible
+///
+/// Used by the borrowed row types such as [`crate::types::TradeRef`]; the
+/// string is only allocated when it contains escaped characters.
+pub fn deserialize_optional_cow<'de, D>(deserializer: D) -> Result<Option<Cow<'de, str>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<Text>::deserialize(deserializer)?.map(|text| text.0))
+}
+
 /// Deserialize a list of TransactionCode from a semicolon-separated string
+///
+/// Unrecognized codes become [`TransactionCode::Unknown`]; they are reported as
+/// warnings by [`crate::parse_activity_flex_with_options`].
 pub fn deserialize_transaction_codes<'de, D>(
     deserializer: D,
 ) -> Result<Option<Vec<TransactionCode>>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let s = Option::<String>::deserialize(deserializer)?;
+    let s = deserialize_optional_cow(deserializer)?;
     match s.as_deref() {
         None | Some("") => Ok(None),
         Some(s) => {
@@ -20,15 +43,7 @@ where
                 .split(';')
                 .filter(|code| !code.is_empty())
                 .map(|code| {
-                    let tc = serde_plain::from_str::<TransactionCode>(code)
-                        .map_err(serde::de::Error::custom)?;
-                    if tc == TransactionCode::Unknown {
-                        eprintln!(
-                            "WARNING: Unknown TransactionCode '{}' in notes '{}'",
-                            code, s
-                        );
-                    }
-                    Ok(tc)
+                    serde_plain::from_str::<TransactionCode>(code).map_err(serde::de::Error::custom)
                 })
                 .collect::<Result<Vec<_>, _>>()?;
             Ok(Some(codes))
@@ -57,14 +72,86 @@ where
     serializer.serialize_str(&s)
 }
 
-/// Parse a date string in either YYYY-MM-DD or YYYYMMDD format
-fn parse_flex_date(s: &str) -> Result<NaiveDate, chrono::ParseError> {
-    // Try ISO format first (YYYY-MM-DD)
-    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
-        return Ok(date);
+/// Parse a date string in YYYY-MM-DD or YYYYMMDD format, or in the format
+/// of the [`DateFormatProfile`] active for the current parse
+pub(crate) fn parse_flex_date(s: &str) -> Result<NaiveDate, chrono::ParseError> {
+    date_format::active().unwrap_or_default().parse_date(s)
+}
+
+/// Time zone of the timestamps in FLEX statements
+///
+/// IB reports execution and generation times in US Eastern time.
+pub const EXCHANGE_TIMEZONE: Tz = chrono_tz::America::New_York;
+
+/// Parse an IB timestamp such as `2025-01-15;093015`
+///
+/// The date part may be `yyyy-MM-dd` or `yyyyMMdd`. Date and time may be
+/// separated by `;`, a space or `T`, and the time may be written as `HHmmss`
+/// or `HH:mm:ss`. A date without a time (as IB emits for some corporate
+/// actions) is midnight.
+///
+/// Queries using other date formats are parsed with
+/// [`DateFormatProfile::parse_datetime`](crate::parsers::DateFormatProfile::parse_datetime).
+///
+/// # Example
+///
+/// ```rust
+/// use ib_flex::parsers::xml_utils::parse_flex_datetime;
+///
+/// let a = parse_flex_datetime("2025-01-15;093015").unwrap();
+/// let b = parse_flex_datetime("20250115 09:30:15").unwrap();
+/// let c = parse_flex_datetime("2025-01-15T09:30:15").unwrap();
+/// assert_eq!(a, b);
+/// assert_eq!(a, c);
+/// ```
+pub fn parse_flex_datetime(s: &str) -> Result<NaiveDateTime, chrono::ParseError> {
+    date_format::active().unwrap_or_default().parse_datetime(s)
+}
+
+/// Interpret a FLEX timestamp as [`EXCHANGE_TIMEZONE`] local time and convert it to UTC
+///
+/// Returns `None` for local times skipped when clocks spring forward. Times
+/// repeated when clocks fall back resolve to the earlier instant.
+///
+/// # Example
+///
+/// ```rust
+/// use ib_flex::parsers::xml_utils::{exchange_time_to_utc, parse_flex_datetime};
+///
+/// let local = parse_flex_datetime("2025-01-15;093015").unwrap();
+/// let utc = exchange_time_to_utc(local).unwrap();
+/// assert_eq!(utc.to_rfc3339(), "2025-01-15T14:30:15+00:00");
+/// ```
+pub fn exchange_time_to_utc(datetime: NaiveDateTime) -> Option<DateTime<Utc>> {
+    EXCHANGE_TIMEZONE
+        .from_local_datetime(&datetime)
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Error for a date attribute that matches none of the FLEX formats
+fn invalid_date<E: serde::de::Error>(value: &str, cause: chrono::ParseError) -> E {
+    location::value_error(ValueKind::Date, invalid_date_message(value, cause))
+}
+
+fn invalid_date_message(value: &str, cause: chrono::ParseError) -> String {
+    format!("invalid date '{}': {}", value, cause)
+}
+
+/// Fail an optional field with `reason`, or yield `None` if invalid values
+/// are being rejected leniently
+fn invalid<T, E: serde::de::Error>(
+    value: &str,
+    kind: Option<ValueKind>,
+    reason: String,
+) -> Result<Option<T>, E> {
+    if inventory::reject(value, &reason) {
+        return Ok(None);
     }
-    // Try compact format (YYYYMMDD)
-    NaiveDate::parse_from_str(s, "%Y%m%d")
+    Err(match kind {
+        Some(kind) => location::value_error(kind, reason),
+        None => E::custom(reason),
+    })
 }
 
 /// Deserialize a NaiveDate from either YYYY-MM-DD or YYYYMMDD format
@@ -72,8 +159,22 @@ pub fn deserialize_flex_date<'de, D>(deserializer: D) -> Result<NaiveDate, D::Er
 where
     D: Deserializer<'de>,
 {
-    let s = String::deserialize(deserializer)?;
-    parse_flex_date(&s).map_err(serde::de::Error::custom)
+    let s = Text::deserialize(deserializer)?.0;
+    parse_flex_date(&s).map_err(|e| invalid_date(&s, e))
+}
+
+/// Deserialize a required Decimal
+///
+/// Accepts plain and scientific notation, like `rust_decimal`'s own
+/// deserializer.
+pub fn deserialize_decimal<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = Text::deserialize(deserializer)?.0;
+    s.parse::<Decimal>()
+        .or_else(|e| Decimal::from_scientific(&s).map_err(|_| e))
+        .map_err(|e| location::value_error(ValueKind::Decimal, e.to_string()))
 }
 
 /// Deserialize an optional Decimal, treating empty strings as None
@@ -81,13 +182,13 @@ pub fn deserialize_optional_decimal<'de, D>(deserializer: D) -> Result<Option<De
 where
     D: Deserializer<'de>,
 {
-    let s = Option::<String>::deserialize(deserializer)?;
+    let s = deserialize_optional_cow(deserializer)?;
     match s.as_deref() {
         None | Some("") => Ok(None),
-        Some(s) => s
-            .parse::<Decimal>()
-            .map(Some)
-            .map_err(serde::de::Error::custom),
+        Some(s) => match s.parse::<Decimal>() {
+            Ok(value) => Ok(Some(value)),
+            Err(e) => invalid(s, Some(ValueKind::Decimal), e.to_string()),
+        },
     }
 }
 
@@ -97,12 +198,13 @@ pub fn deserialize_optional_date<'de, D>(deserializer: D) -> Result<Option<Naive
 where
     D: Deserializer<'de>,
 {
-    let s = Option::<String>::deserialize(deserializer)?;
+    let s = deserialize_optional_cow(deserializer)?;
     match s.as_deref() {
         None | Some("") => Ok(None),
-        Some(s) => parse_flex_date(s)
-            .map(Some)
-            .map_err(serde::de::Error::custom),
+        Some(s) => match parse_flex_date(s) {
+            Ok(date) => Ok(Some(date)),
+            Err(e) => invalid(s, Some(ValueKind::Date), invalid_date_message(s, e)),
+        },
     }
 }
 
@@ -126,15 +228,16 @@ pub fn deserialize_optional_bool<'de, D>(deserializer: D) -> Result<Option<bool>
 where
     D: Deserializer<'de>,
 {
-    let s = Option::<String>::deserialize(deserializer)?;
+    let s = deserialize_optional_cow(deserializer)?;
     match s.as_deref() {
         None | Some("") => Ok(None),
         Some("Y") | Some("y") => Ok(Some(true)),
         Some("N") | Some("n") => Ok(Some(false)),
-        Some(other) => Err(serde::de::Error::custom(format!(
-            "Invalid boolean value '{}', expected 'Y' or 'N'",
-            other
-        ))),
+        Some(other) => invalid(
+            other,
+            None,
+            format!("Invalid boolean value '{}', expected 'Y' or 'N'", other),
+        ),
     }
 }
 
@@ -244,4 +347,54 @@ mod tests {
         let result: Result<TestStruct, _> = quick_xml::de::from_str(xml);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_flex_datetime_separators() {
+        let expected = NaiveDate::from_ymd_opt(2025, 1, 15)
+            .unwrap()
+            .and_hms_opt(9, 30, 15)
+            .unwrap();
+
+        for s in [
+            "2025-01-15;093015",
+            "2025-01-15;09:30:15",
+            "2025-01-15 093015",
+            "2025-01-15T09:30:15",
+            "20250115;093015",
+            "20250115 09:30:15",
+        ] {
+            assert_eq!(parse_flex_datetime(s).unwrap(), expected, "{}", s);
+        }
+
+        let midnight = parse_flex_datetime("2025-01-15").unwrap();
+        assert_eq!(midnight.to_string(), "2025-01-15 00:00:00");
+    }
+
+    #[test]
+    fn test_flex_datetime_invalid() {
+        assert!(parse_flex_datetime("").is_err());
+        assert!(parse_flex_datetime("2025-01-15;0930").is_err());
+        assert!(parse_flex_datetime("2025-01-15;253000").is_err());
+        assert!(parse_flex_datetime("yesterday;093015").is_err());
+    }
+
+    #[test]
+    fn test_exchange_time_to_utc_dst() {
+        let summer = parse_flex_datetime("2025-07-01;093000").unwrap();
+        assert_eq!(
+            exchange_time_to_utc(summer).unwrap().to_rfc3339(),
+            "2025-07-01T13:30:00+00:00"
+        );
+
+        // Skipped by the spring-forward transition
+        let skipped = parse_flex_datetime("2025-03-09;023000").unwrap();
+        assert_eq!(exchange_time_to_utc(skipped), None);
+
+        // Repeated by the fall-back transition: earlier (EDT) instant
+        let repeated = parse_flex_datetime("2025-11-02;013000").unwrap();
+        assert_eq!(
+            exchange_time_to_utc(repeated).unwrap().to_rfc3339(),
+            "2025-11-02T05:30:00+00:00"
+        );
+    }
 }
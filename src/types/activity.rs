@@ -1,6 +1,8 @@
 //! Activity FLEX statement types
 
-use chrono::NaiveDate;
+use std::collections::BTreeMap;
+
+use chrono::{NaiveDate, NaiveDateTime};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
@@ -8,8 +10,10 @@ use super::common::{
     AssetCategory, BuySell, DerivativeInfo, LevelOfDetail, OpenClose, OrderType, PutCall,
     SecurityIdType, SubCategory, TradeType,
 };
+use crate::parsers::date_format::{self, DateFormatProfile};
 use crate::parsers::xml_utils::{
-    deserialize_optional_bool, deserialize_optional_date, deserialize_optional_decimal,
+    deserialize_decimal, deserialize_optional_bool, deserialize_optional_date,
+    deserialize_optional_decimal,
 };
 
 /// Top-level FLEX query response
@@ -69,7 +73,11 @@ pub struct FlexStatementsWrapper {
     pub count: Option<String>,
 
     /// Flex statement(s)
-    #[serde(rename = "FlexStatement")]
+    #[serde(
+        rename = "FlexStatement",
+        default,
+        deserialize_with = "crate::parsers::inventory::deserialize_statements"
+    )]
     pub statements: Vec<ActivityFlexStatement>,
 }
 
@@ -143,6 +151,10 @@ pub struct ActivityFlexStatement {
     #[serde(rename = "@whenGenerated")]
     pub when_generated: String, // Parse separately due to IB format
 
+    /// Reporting period (e.g. `LastBusinessDay`)
+    #[serde(rename = "@period", default)]
+    pub period: Option<String>,
+
     /// All trades in the period
     #[serde(rename = "Trades", default)]
     pub trades: TradesWrapper,
@@ -184,6 +196,18 @@ pub struct ActivityFlexStatement {
     #[serde(rename = "CashReport", default)]
     pub cash_report: CashReportWrapper,
 
+    /// Deposits on hold
+    #[serde(rename = "DepositsOnHold", default)]
+    pub deposits_on_hold: DepositsOnHoldWrapper,
+
+    /// Sweep deposits by FDIC-insured bank
+    #[serde(rename = "FdicInsuredDepositsByBank", default)]
+    pub fdic_insured_deposits: FdicInsuredDepositsWrapper,
+
+    /// IBG note transactions
+    #[serde(rename = "IBGNoteTransactions", default)]
+    pub ibg_note_transactions: IBGNoteTransactionsWrapper,
+
     /// Trade confirmations
     #[serde(rename = "TradeConfirms", default)]
     pub trade_confirms: TradeConfirmsWrapper,
@@ -253,6 +277,14 @@ pub struct ActivityFlexStatement {
     #[serde(rename = "SLBFees", default)]
     pub slb_fees: SLBFeesWrapper,
 
+    /// Securities lending open contracts
+    #[serde(rename = "SLBOpenContracts", default)]
+    pub slb_open_contracts: SLBOpenContractsWrapper,
+
+    /// Securities lending collateral
+    #[serde(rename = "SLBCollaterals", default)]
+    pub slb_collaterals: SLBCollateralsWrapper,
+
     /// Hard to borrow details
     #[serde(rename = "HardToBorrowDetails", default)]
     pub hard_to_borrow_details: HardToBorrowDetailsWrapper,
@@ -285,72 +317,618 @@ pub struct ActivityFlexStatement {
     #[serde(rename = "SalesTaxes", default)]
     pub sales_tax: SalesTaxWrapper,
 
+    /// Net stock positions (held, borrowed and lent shares)
+    #[serde(rename = "NetStockPositions", default)]
+    pub net_stock_positions: NetStockPositionsWrapper,
+
+    /// Net stock position summary
+    #[serde(rename = "NetStockPositionSummary", default)]
+    pub net_stock_position_summary: NetStockPositionSummaryWrapper,
+
+    /// Complex (combination) positions
+    #[serde(rename = "ComplexPositions", default)]
+    pub complex_positions: ComplexPositionsWrapper,
+
+    /// FX (cash currency) positions
+    #[serde(rename = "FxPositions", default)]
+    pub fx_positions: FxPositionsWrapper,
+
+    /// Mutual fund dividend details
+    #[serde(rename = "MutualFundDividendDetails", default)]
+    pub mutual_fund_dividends: MutualFundDividendDetailsWrapper,
+
+    /// Incentive coupon accrual details
+    #[serde(rename = "IncentiveCouponAccrualDetails", default)]
+    pub incentive_coupon_accruals: IncentiveCouponAccrualDetailsWrapper,
+
+    /// Open Hong Kong IPO subscriptions
+    #[serde(rename = "HKIPOOpenSubscriptions", default)]
+    pub hk_ipo_open_subscriptions: HKIPOOpenSubscriptionsWrapper,
+
+    /// Hong Kong IPO subscription activity
+    #[serde(rename = "HKIPOSubscriptionActivity", default)]
+    pub hk_ipo_subscription_activity: HKIPOSubscriptionActivityWrapper,
+
+    /// Stock grant (RSU) vesting activity
+    #[serde(rename = "StockGrantActivities", default)]
+    pub stock_grant_activities: StockGrantActivitiesWrapper,
+
+    /// Pending option exercises and assignments (IB spells it `PendingExcercises`)
+    #[serde(rename = "PendingExcercises", default)]
+    pub pending_exercises: PendingExercisesWrapper,
+
+    /// Trades executed but not yet booked
+    #[serde(rename = "UnbookedTrades", default)]
+    pub unbooked_trades: UnbookedTradesWrapper,
+
+    /// Transaction taxes (e.g. financial transaction taxes)
+    #[serde(rename = "TransactionTaxes", default)]
+    pub transaction_taxes: TransactionTaxesWrapper,
+
+    /// CFD financing charges
+    #[serde(rename = "CFDCharges", default)]
+    pub cfd_charges: CFDChargesWrapper,
+
+    /// Routing commissions
+    #[serde(rename = "RoutingCommissions", default)]
+    pub routing_commissions: RoutingCommissionsWrapper,
+
+    /// Commission credits
+    #[serde(rename = "CommissionCredits", default)]
+    pub commission_credits: CommissionCreditsWrapper,
+
     // Note: SymbolSummary and AssetSummary elements appear INSIDE <Trades>,
     // not as separate sections. They're handled by TradesWrapper.
     // Orders also appear inside <Trades> as Order elements.
     // See TradesWrapper for how these are handled.
+    /// Sections this crate does not model (e.g. `SoftDollars`), as raw
+    /// elements in document order; only filled when parsing with
+    /// [`ParseOptions::with_raw_capture`](crate::parsers::ParseOptions::with_raw_capture)
+    #[serde(skip)]
+    pub unknown_sections: Vec<super::raw::RawElement>,
+
+    /// Enum values this crate does not recognize, in order of first
+    /// appearance; the fields themselves hold them as `Unknown(raw)`
+    #[serde(skip)]
+    pub unknown_values: Vec<super::common::UnknownValue>,
+
+    /// Date format profile the statement was parsed with (`None` for the
+    /// default formats); used by [`ActivityFlexStatement::when_generated_datetime`]
+    /// and passed to the row timestamp accessors such as [`Trade::trade_datetime`]
+    #[serde(skip, default = "date_format::active")]
+    pub date_format: Option<DateFormatProfile>,
+}
+
+impl ActivityFlexStatement {
+    /// When the report was generated, parsed from [`ActivityFlexStatement::when_generated`]
+    ///
+    /// Returns `None` if the attribute is not a valid IB timestamp. The value
+    /// is exchange local time; see [`crate::parsers::xml_utils::exchange_time_to_utc`].
+    pub fn when_generated_datetime(&self) -> Option<NaiveDateTime> {
+        self.date_format
+            .unwrap_or_default()
+            .parse_datetime(&self.when_generated)
+            .ok()
+    }
 
-    // --- Catch-all fields for sections not yet fully implemented ---
-    // These prevent parse errors when XML contains these sections
-    #[serde(rename = "DepositsOnHold", default, skip_serializing)]
-    deposits_on_hold: IgnoredSection,
-    #[serde(rename = "FxPositions", default, skip_serializing)]
-    fx_positions: IgnoredSection,
-    #[serde(rename = "NetStockPositions", default, skip_serializing)]
-    net_stock_positions: IgnoredSection,
-    #[serde(rename = "ComplexPositions", default, skip_serializing)]
-    complex_positions: IgnoredSection,
-    #[serde(rename = "CFDCharges", default, skip_serializing)]
-    cfd_charges: IgnoredSection,
-    #[serde(rename = "CommissionCredits", default, skip_serializing)]
-    commission_credits: IgnoredSection,
-    #[serde(rename = "FdicInsuredDepositsByBank", default, skip_serializing)]
-    fdic_insured_deposits: IgnoredSection,
-    #[serde(rename = "HKIPOOpenSubscriptions", default, skip_serializing)]
-    hk_ipo_open_subscriptions: IgnoredSection,
-    #[serde(rename = "HKIPOSubscriptionActivity", default, skip_serializing)]
-    hk_ipo_subscription_activity: IgnoredSection,
-    #[serde(rename = "IBGNoteTransactions", default, skip_serializing)]
-    ibg_note_transactions: IgnoredSection,
-    #[serde(rename = "IncentiveCouponAccrualDetails", default, skip_serializing)]
-    incentive_coupon_accruals: IgnoredSection,
-    #[serde(rename = "MutualFundDividendDetails", default, skip_serializing)]
-    mutual_fund_dividends: IgnoredSection,
-    #[serde(rename = "NetStockPositionSummary", default, skip_serializing)]
-    net_stock_position_summary: IgnoredSection,
-    #[serde(rename = "PendingExcercises", default, skip_serializing)]
-    pending_exercises: IgnoredSection,
-    #[serde(rename = "RoutingCommissions", default, skip_serializing)]
-    routing_commissions: IgnoredSection,
-    #[serde(rename = "SLBCollaterals", default, skip_serializing)]
-    slb_collaterals: IgnoredSection,
-    #[serde(rename = "SLBOpenContracts", default, skip_serializing)]
-    slb_open_contracts: IgnoredSection,
-    #[serde(rename = "SoftDollars", default, skip_serializing)]
-    soft_dollars: IgnoredSection,
-    #[serde(rename = "StockGrantActivities", default, skip_serializing)]
-    stock_grant_activities: IgnoredSection,
-    #[serde(rename = "TransactionTaxes", default, skip_serializing)]
-    transaction_taxes: IgnoredSection,
-    #[serde(rename = "UnbookedTrades", default, skip_serializing)]
-    unbooked_trades: IgnoredSection,
-    // Note: Catch-all flatten disabled as it causes issues with multi-statement files
-    // All unknown sections should be explicitly listed above with IgnoredSection
-}
-
-/// Helper type for sections we want to ignore during parsing
+    /// Join each securities lending open contract to its open positions
+    ///
+    /// Contracts and positions are matched by `conid`; each contract gets
+    /// every matching position row in document order. With lot-level open
+    /// positions that is one row per lot (plus the summary row if the query
+    /// includes both; see [`Position::level_of_detail`]). The list is empty
+    /// when the statement has no open position in that contract.
+    pub fn slb_open_contract_positions(
+        &self,
+    ) -> Vec<(&super::extended::SLBOpenContract, Vec<&Position>)> {
+        let mut positions: std::collections::HashMap<&str, Vec<&Position>> =
+            std::collections::HashMap::new();
+        for position in &self.positions.items {
+            positions
+                .entry(position.conid.as_str())
+                .or_default()
+                .push(position);
+        }
+
+        self.slb_open_contracts
+            .items
+            .iter()
+            .map(|contract| {
+                let matching = contract
+                    .conid
+                    .as_deref()
+                    .and_then(|conid| positions.get(conid).cloned())
+                    .unwrap_or_default();
+                (contract, matching)
+            })
+            .collect()
+    }
+
+    /// Pair each mutual fund dividend detail with its cash transactions
+    ///
+    /// A cash transaction matches when its `conid` equals the detail's and its
+    /// date, settle date or timestamp falls on the detail's pay date. Details
+    /// without a `conid` or pay date get no matches.
+    pub fn mutual_fund_dividends_with_cash(
+        &self,
+    ) -> Vec<(
+        &super::extended::MutualFundDividendDetail,
+        Vec<&CashTransaction>,
+    )> {
+        self.mutual_fund_dividends
+            .items
+            .iter()
+            .map(|detail| {
+                let matches = match (detail.conid.as_deref(), detail.pay_date) {
+                    (Some(conid), Some(pay_date)) => self
+                        .cash_transactions
+                        .items
+                        .iter()
+                        .filter(|cash| {
+                            cash.conid.as_deref() == Some(conid)
+                                && (cash.date == Some(pay_date)
+                                    || cash.settle_date == Some(pay_date)
+                                    || cash.datetime(self.date_format).map(|dt| dt.date())
+                                        == Some(pay_date))
+                        })
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                (detail, matches)
+            })
+            .collect()
+    }
+
+    /// Per-grant vesting ledger from [`stock_grant_activities`](Self::stock_grant_activities)
+    ///
+    /// Rows are grouped by `conid` and award date, in document order, and
+    /// summed by [`activity_type`](super::extended::StockGrantActivity::activity_type):
+    /// grant rows give the shares awarded, forfeiture rows the shares lost, and
+    /// vest and release rows become tranches. A release row joins the vest row
+    /// with the same vesting date instead of adding a second tranche. Rows
+    /// without a type are vesting schedule entries and become tranches too.
+    ///
+    /// A tranche is vested when its vesting date is on or before `as_of`; use
+    /// [`to_date`](Self::to_date) for the statement's view. Each tranche is
+    /// linked to the open position with the same `conid` and
+    /// [`Position::vesting_date`]. Rows without a `conid` are skipped.
+    pub fn vesting_ledger(&self, as_of: NaiveDate) -> Vec<VestingGrant<'_>> {
+        use super::common::StockGrantActivityType as Type;
+
+        let mut grants: Vec<VestingGrant<'_>> = Vec::new();
+
+        for activity in &self.stock_grant_activities.items {
+            let Some(conid) = activity.conid.as_deref() else {
+                continue;
+            };
+            let grant = match grants
+                .iter_mut()
+                .position(|g| g.conid == conid && g.award_date == activity.award_date)
+            {
+                Some(i) => &mut grants[i],
+                None => {
+                    grants.push(VestingGrant {
+                        conid: conid.to_string(),
+                        symbol: activity.symbol.clone(),
+                        award_date: activity.award_date,
+                        granted_quantity: Decimal::ZERO,
+                        vested_quantity: Decimal::ZERO,
+                        unvested_quantity: Decimal::ZERO,
+                        forfeited_quantity: Decimal::ZERO,
+                        vested_cost_basis: Decimal::ZERO,
+                        grants: Vec::new(),
+                        forfeitures: Vec::new(),
+                        tranches: Vec::new(),
+                    });
+                    grants.last_mut().expect("grant was just pushed")
+                }
+            };
+            let quantity = activity.quantity.unwrap_or_default();
+
+            match &activity.activity_type {
+                Some(Type::Grant) => {
+                    grant.granted_quantity += quantity;
+                    grant.grants.push(activity);
+                    continue;
+                }
+                Some(Type::Forfeit) => {
+                    grant.forfeited_quantity += quantity;
+                    grant.forfeitures.push(activity);
+                    continue;
+                }
+                Some(Type::Release) => {
+                    let vest = grant.tranches.iter_mut().find(|t| {
+                        t.release.is_none()
+                            && t.activity.activity_type != Some(Type::Release)
+                            && same_vesting_date(t.activity, activity)
+                    });
+                    if let Some(vest) = vest {
+                        vest.release = Some(activity);
+                        continue;
+                    }
+                }
+                // The release row of the tranche may come first
+                Some(Type::Vest) => {
+                    let released = grant.tranches.iter_mut().find(|t| {
+                        t.activity.activity_type == Some(Type::Release)
+                            && same_vesting_date(t.activity, activity)
+                    });
+                    if let Some(released) = released {
+                        released.release = Some(released.activity);
+                        released.activity = activity;
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+
+            let position = self.positions.items.iter().find(|p| {
+                p.conid == conid
+                    && p.vesting_date.is_some()
+                    && p.vesting_date == activity.vesting_date
+            });
+            grant.tranches.push(VestingTranche {
+                activity,
+                release: None,
+                vested: activity.vesting_date.is_some_and(|date| date <= as_of),
+                position,
+            });
+        }
+
+        for grant in &mut grants {
+            let mut scheduled = Decimal::ZERO;
+            for tranche in &grant.tranches {
+                scheduled += tranche.quantity();
+                if tranche.vested {
+                    grant.vested_quantity += tranche.quantity();
+                    grant.vested_cost_basis += tranche.cost_basis().unwrap_or_default();
+                }
+            }
+            // Without grant rows the tranches are the whole award
+            if grant.grants.is_empty() {
+                grant.granted_quantity = scheduled;
+            }
+            grant.unvested_quantity =
+                grant.granted_quantity - grant.vested_quantity - grant.forfeited_quantity;
+        }
+
+        grants
+    }
+
+    /// Pair Hong Kong IPO subscriptions with their activity rows
+    ///
+    /// Open subscriptions and activity rows are grouped by `conid`, open
+    /// subscriptions first and then in document order, so IPOs that are no
+    /// longer open still get an entry. Activity rows are sorted by their
+    /// `type` into applied, allotted, refunded and other. Rows without a
+    /// `conid` are skipped.
+    pub fn hk_ipo_subscriptions(&self) -> Vec<HKIPOSubscription<'_>> {
+        use super::common::HKIPOActivityType as Type;
+
+        let mut subscriptions: Vec<HKIPOSubscription<'_>> = self
+            .hk_ipo_open_subscriptions
+            .items
+            .iter()
+            .filter_map(|open| {
+                Some(HKIPOSubscription {
+                    conid: open.conid.clone()?,
+                    subscription: Some(open),
+                    ..Default::default()
+                })
+            })
+            .collect();
+
+        for activity in &self.hk_ipo_subscription_activity.items {
+            let Some(conid) = activity.conid.as_deref() else {
+                continue;
+            };
+            let entry = match subscriptions.iter().position(|s| s.conid == conid) {
+                Some(i) => &mut subscriptions[i],
+                None => {
+                    subscriptions.push(HKIPOSubscription {
+                        conid: conid.to_string(),
+                        ..Default::default()
+                    });
+                    subscriptions
+                        .last_mut()
+                        .expect("subscription was just pushed")
+                }
+            };
+
+            match activity.activity_type {
+                Some(Type::Applied) => entry.applied.push(activity),
+                Some(Type::Allotted) => entry.allotted.push(activity),
+                Some(Type::Refunded) => entry.refunded.push(activity),
+                _ => entry.other.push(activity),
+            }
+        }
+
+        subscriptions
+    }
+
+    /// Positions expected at the start of the next trading day
+    ///
+    /// Starts from [`positions`](Self::positions) and applies pending option
+    /// exercises/assignments and unbooked trades, matched by `conid`. An
+    /// exercise removes the option contracts and moves `quantity x multiplier`
+    /// shares of the underlying (bought for calls, sold for puts). Contracts
+    /// that only appear in the pending sections are appended in document order;
+    /// rows without a `conid` are skipped.
+    ///
+    /// Lot-level position rows are summed per `conid`, unless the contract also
+    /// has a summary row, which already holds their total.
+    pub fn effective_next_day_positions(&self) -> Vec<EffectivePosition> {
+        let is_lot = |p: &Position| p.level_of_detail == Some(LevelOfDetail::Lot);
+        let summarized: std::collections::HashSet<&str> = self
+            .positions
+            .items
+            .iter()
+            .filter(|p| !is_lot(p))
+            .map(|p| p.conid.as_str())
+            .collect();
+
+        let mut effective: Vec<EffectivePosition> = Vec::new();
+        let mut index: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+        for p in &self.positions.items {
+            if is_lot(p) && summarized.contains(p.conid.as_str()) {
+                continue;
+            }
+            match index.get(&p.conid) {
+                Some(&i) => {
+                    effective[i].reported_quantity += p.quantity;
+                    effective[i].quantity += p.quantity;
+                }
+                None => {
+                    index.insert(p.conid.clone(), effective.len());
+                    effective.push(EffectivePosition {
+                        conid: p.conid.clone(),
+                        symbol: p.symbol.clone(),
+                        asset_category: Some(p.asset_category.clone()),
+                        reported_quantity: p.quantity,
+                        quantity: p.quantity,
+                    });
+                }
+            }
+        }
+
+        let mut adjust = |conid: &str,
+                          symbol: Option<&str>,
+                          asset_category: Option<AssetCategory>,
+                          delta: Decimal| {
+            let i = *index.entry(conid.to_string()).or_insert_with(|| {
+                effective.push(EffectivePosition {
+                    conid: conid.to_string(),
+                    symbol: symbol.unwrap_or_default().to_string(),
+                    asset_category,
+                    reported_quantity: Decimal::ZERO,
+                    quantity: Decimal::ZERO,
+                });
+                effective.len() - 1
+            });
+            effective[i].quantity += delta;
+        };
+
+        for exercise in &self.pending_exercises.items {
+            let (Some(conid), Some(contracts)) = (exercise.conid.as_deref(), exercise.quantity)
+            else {
+                continue;
+            };
+            adjust(
+                conid,
+                exercise.symbol.as_deref(),
+                exercise.asset_category.clone(),
+                -contracts,
+            );
+
+            let direction = match exercise.put_call {
+                Some(PutCall::Call) => Decimal::ONE,
+                Some(PutCall::Put) => Decimal::NEGATIVE_ONE,
+                _ => continue,
+            };
+            let multiplier = exercise
+                .multiplier
+                .or_else(|| {
+                    self.positions
+                        .items
+                        .iter()
+                        .find(|p| p.conid == conid)
+                        .and_then(|p| p.multiplier)
+                })
+                .unwrap_or(Decimal::ONE);
+            if let Some(underlying) = exercise.underlying_conid.as_deref() {
+                adjust(
+                    underlying,
+                    exercise.underlying_symbol.as_deref(),
+                    None,
+                    contracts * multiplier * direction,
+                );
+            }
+        }
+
+        for trade in &self.unbooked_trades.items {
+            if let (Some(conid), Some(quantity)) = (trade.conid.as_deref(), trade.quantity) {
+                adjust(
+                    conid,
+                    trade.symbol.as_deref(),
+                    trade.asset_category.clone(),
+                    quantity,
+                );
+            }
+        }
+
+        effective
+    }
+}
+
+/// Position quantity after pending activity
+///
+/// Returned by [`ActivityFlexStatement::effective_next_day_positions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EffectivePosition {
+    /// IB contract ID
+    pub conid: String,
+
+    /// Ticker symbol
+    pub symbol: String,
+
+    /// Asset category, if known
+    pub asset_category: Option<AssetCategory>,
+
+    /// Quantity reported in `OpenPositions` (zero if not held)
+    pub reported_quantity: Decimal,
+
+    /// Quantity after pending exercises and unbooked trades
+    pub quantity: Decimal,
+}
+
+/// Vesting totals for one stock grant
+///
+/// Returned by [`ActivityFlexStatement::vesting_ledger`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct VestingGrant<'a> {
+    /// IB contract ID of the granted stock
+    pub conid: String,
+
+    /// Ticker symbol
+    pub symbol: Option<String>,
+
+    /// Grant (award) date
+    pub award_date: Option<NaiveDate>,
+
+    /// Shares awarded: the grant rows, or else the sum of the tranches
+    pub granted_quantity: Decimal,
+
+    /// Shares vested as of the ledger date
+    pub vested_quantity: Decimal,
+
+    /// Shares neither vested nor forfeited
+    pub unvested_quantity: Decimal,
+
+    /// Shares forfeited
+    pub forfeited_quantity: Decimal,
+
+    /// Cost basis of the vested shares (FMV at vest)
+    ///
+    /// Tranches without a price or value contribute nothing.
+    pub vested_cost_basis: Decimal,
+
+    /// Grant rows, in document order
+    pub grants: Vec<&'a super::extended::StockGrantActivity>,
+
+    /// Forfeiture rows, in document order
+    pub forfeitures: Vec<&'a super::extended::StockGrantActivity>,
+
+    /// Vesting tranches, in document order
+    pub tranches: Vec<VestingTranche<'a>>,
+}
+
+/// One vesting tranche of a [`VestingGrant`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VestingTranche<'a> {
+    /// The vest row, or the schedule or release row when there is none
+    pub activity: &'a super::extended::StockGrantActivity,
+
+    /// Release row with the same vesting date, if reported separately
+    pub release: Option<&'a super::extended::StockGrantActivity>,
+
+    /// Whether the tranche had vested as of the ledger date
+    pub vested: bool,
+
+    /// Open restricted position with the same conid and vesting date
+    pub position: Option<&'a Position>,
+}
+
+impl VestingTranche<'_> {
+    /// Shares in the tranche (zero if not reported)
+    pub fn quantity(&self) -> Decimal {
+        self.activity.quantity.unwrap_or_default()
+    }
+
+    /// Date the shares were released, from the release row if there is one
+    pub fn release_date(&self) -> Option<NaiveDate> {
+        self.release
+            .and_then(|release| release.release_date.or(release.vesting_date))
+            .or(self.activity.release_date)
+    }
+
+    /// Per-share fair market value at vest, once vested
+    ///
+    /// Taken from the vest row, falling back to the release row.
+    pub fn fmv_at_vest(&self) -> Option<Decimal> {
+        if !self.vested {
+            return None;
+        }
+        self.activity
+            .price
+            .or_else(|| self.release.and_then(|release| release.price))
+    }
+
+    /// Cost basis later sales of these shares must use, once vested
+    ///
+    /// Uses the reported `value` of the vest row, falling back to
+    /// `quantity x` [`fmv_at_vest`](Self::fmv_at_vest).
+    pub fn cost_basis(&self) -> Option<Decimal> {
+        if !self.vested {
+            return None;
+        }
+        self.activity
+            .value
+            .or_else(|| Some(self.activity.quantity? * self.fmv_at_vest()?))
+    }
+}
+
+/// A Hong Kong IPO subscription with its activity
+///
+/// Returned by [`ActivityFlexStatement::hk_ipo_subscriptions`].
 #[derive(Debug, Clone, PartialEq, Default)]
-struct IgnoredSection;
+pub struct HKIPOSubscription<'a> {
+    /// IB contract ID of the IPO
+    pub conid: String,
 
-impl<'de> serde::Deserialize<'de> for IgnoredSection {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        // Ignore whatever content is in this section
-        serde::de::IgnoredAny::deserialize(deserializer)?;
-        Ok(IgnoredSection)
+    /// Open subscription row, if the subscription is still open
+    pub subscription: Option<&'a super::extended::HKIPOOpenSubscription>,
+
+    /// Application rows (cash frozen)
+    pub applied: Vec<&'a super::extended::HKIPOSubscriptionActivity>,
+
+    /// Allotment rows
+    pub allotted: Vec<&'a super::extended::HKIPOSubscriptionActivity>,
+
+    /// Refund rows (unallotted cash released)
+    pub refunded: Vec<&'a super::extended::HKIPOSubscriptionActivity>,
+
+    /// Rows with any other activity type
+    pub other: Vec<&'a super::extended::HKIPOSubscriptionActivity>,
+}
+
+impl HKIPOSubscription<'_> {
+    /// Total shares allotted
+    pub fn allotted_quantity(&self) -> Decimal {
+        self.allotted.iter().filter_map(|a| a.quantity).sum()
     }
+
+    /// Total cash refunded
+    pub fn refunded_amount(&self) -> Decimal {
+        self.refunded.iter().filter_map(|a| a.amount).sum()
+    }
+}
+
+/// Whether two stock grant rows belong to the same vesting tranche
+fn same_vesting_date(
+    a: &super::extended::StockGrantActivity,
+    b: &super::extended::StockGrantActivity,
+) -> bool {
+    a.vesting_date.is_some() && a.vesting_date == b.vesting_date
+}
+
+/// Parse an optional raw IB timestamp, treating invalid values as absent
+fn parse_optional_datetime(
+    value: &Option<String>,
+    profile: Option<DateFormatProfile>,
+) -> Option<NaiveDateTime> {
+    value
+        .as_deref()
+        .and_then(|v| profile.unwrap_or_default().parse_datetime(v).ok())
 }
 
 /// Element types that can appear in the `<Trades>` section.
@@ -376,7 +954,8 @@ enum TradesItem {
 /// - `<Order>` with levelOfDetail="ORDER" - order summaries
 /// - `<SymbolSummary>`, `<AssetSummary>`, `<WashSale>`, `<Lot>` - various summary records
 ///
-/// These elements can be interleaved (grouped by symbol), not by type.
+/// These elements can be interleaved (grouped by symbol), not by type. Each
+/// element type is kept in its own vector, in document order.
 #[derive(Debug, Clone, PartialEq, Default, Serialize)]
 pub struct TradesWrapper {
     /// Trade executions (main trading data)
@@ -384,6 +963,48 @@ pub struct TradesWrapper {
 
     /// Wash sale records
     pub wash_sales: Vec<Trade>,
+
+    /// Order-level aggregates (`<Order>`)
+    pub orders: Vec<Trade>,
+
+    /// Per-symbol summaries (`<SymbolSummary>`)
+    pub symbol_summaries: Vec<Trade>,
+
+    /// Per-asset-class summaries (`<AssetSummary>`)
+    pub asset_summaries: Vec<Trade>,
+
+    /// Closed lots (`<Lot>`) with a link to the trade that closed them
+    pub lots: Vec<TradeLot>,
+}
+
+/// A closed lot from the `<Trades>` section
+///
+/// IB emits `<Lot>` rows right after the closing `<Trade>` and lists the
+/// opening lots that trade consumed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TradeLot {
+    /// The `<Lot>` row
+    pub lot: Trade,
+
+    /// Index into [`TradesWrapper::items`] of the closing trade
+    ///
+    /// Matched by `transactionID` when the lot carries one that appears on a
+    /// trade, otherwise the `<Trade>` preceding the lot in the document.
+    pub closing_trade: Option<usize>,
+}
+
+impl TradesWrapper {
+    /// The trade that closed a lot
+    pub fn closing_trade(&self, lot: &TradeLot) -> Option<&Trade> {
+        lot.closing_trade.and_then(|i| self.items.get(i))
+    }
+
+    /// Lots consumed by the trade at `trade_index` in [`TradesWrapper::items`]
+    pub fn lots_closed_by(&self, trade_index: usize) -> impl Iterator<Item = &TradeLot> {
+        self.lots
+            .iter()
+            .filter(move |lot| lot.closing_trade == Some(trade_index))
+    }
 }
 
 impl<'de> serde::Deserialize<'de> for TradesWrapper {
@@ -399,22 +1020,43 @@ impl<'de> serde::Deserialize<'de> for TradesWrapper {
 
         let raw = Raw::deserialize(deserializer)?;
 
-        let mut trades = Vec::new();
-        let mut wash_sales = Vec::new();
+        let mut wrapper = TradesWrapper::default();
 
         for item in raw.items {
             match item {
-                TradesItem::Trade(t) => trades.push(t),
-                TradesItem::WashSale(t) => wash_sales.push(t),
-                // Ignore Order, SymbolSummary, AssetSummary, Lot for items
-                _ => {}
+                TradesItem::Trade(t) => wrapper.items.push(t),
+                TradesItem::WashSale(t) => wrapper.wash_sales.push(t),
+                TradesItem::Order(t) => wrapper.orders.push(t),
+                TradesItem::SymbolSummary(t) => wrapper.symbol_summaries.push(t),
+                TradesItem::AssetSummary(t) => wrapper.asset_summaries.push(t),
+                TradesItem::Lot(t) => wrapper.lots.push(TradeLot {
+                    lot: t,
+                    // Stream position; refined by transactionID below
+                    closing_trade: wrapper.items.len().checked_sub(1),
+                }),
             }
         }
 
-        Ok(TradesWrapper {
-            items: trades,
-            wash_sales,
-        })
+        if !wrapper.lots.is_empty() {
+            let by_transaction: std::collections::HashMap<&str, usize> = wrapper
+                .items
+                .iter()
+                .enumerate()
+                .filter_map(|(i, t)| t.transaction_id.as_deref().map(|id| (id, i)))
+                .collect();
+            for lot in &mut wrapper.lots {
+                if let Some(&i) = lot
+                    .lot
+                    .transaction_id
+                    .as_deref()
+                    .and_then(|id| by_transaction.get(id))
+                {
+                    lot.closing_trade = Some(i);
+                }
+            }
+        }
+
+        Ok(wrapper)
     }
 }
 
@@ -988,6 +1630,10 @@ pub struct Trade {
         deserialize_with = "deserialize_optional_decimal"
     )]
     pub initial_investment: Option<Decimal>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 impl Trade {
@@ -1029,7 +1675,7 @@ impl Trade {
                 // For options, we need: strike, expiry, put_call, underlying_symbol
                 let strike = self.strike?;
                 let expiry = self.expiry?;
-                let put_call = self.put_call?;
+                let put_call = self.put_call.clone()?;
                 let underlying_symbol = self.underlying_symbol.clone()?;
 
                 Some(DerivativeInfo::Option {
@@ -1055,7 +1701,7 @@ impl Trade {
                 // For future options, we need: strike, expiry, put_call, underlying_symbol
                 let strike = self.strike?;
                 let expiry = self.expiry?;
-                let put_call = self.put_call?;
+                let put_call = self.put_call.clone()?;
                 let underlying_symbol = self.underlying_symbol.clone()?;
 
                 Some(DerivativeInfo::FutureOption {
@@ -1080,6 +1726,61 @@ impl Trade {
             _ => None,
         }
     }
+
+    /// Execution time, parsed from [`Trade::trade_time`]
+    ///
+    /// `profile` is the statement's [`ActivityFlexStatement::date_format`].
+    /// Timestamps are exchange local time and `None` when absent or not a
+    /// valid IB timestamp. Convert with
+    /// [`exchange_time_to_utc`](crate::parsers::xml_utils::exchange_time_to_utc)
+    /// to line fills up with UTC market data.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ib_flex::parse_activity_flex;
+    /// use ib_flex::parsers::xml_utils::exchange_time_to_utc;
+    ///
+    /// let xml = std::fs::read_to_string("activity.xml")?;
+    /// let statement = parse_activity_flex(&xml)?;
+    ///
+    /// for trade in &statement.trades.items {
+    ///     if let Some(utc) = trade
+    ///         .trade_datetime(statement.date_format)
+    ///         .and_then(exchange_time_to_utc) {
+    ///         println!("{} filled at {}", trade.symbol, utc);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn trade_datetime(&self, profile: Option<DateFormatProfile>) -> Option<NaiveDateTime> {
+        parse_optional_datetime(&self.trade_time, profile)
+    }
+
+    /// Order time, parsed from [`Trade::order_time`]
+    pub fn order_datetime(&self, profile: Option<DateFormatProfile>) -> Option<NaiveDateTime> {
+        parse_optional_datetime(&self.order_time, profile)
+    }
+
+    /// When P&L was realized, parsed from [`Trade::when_realized`]
+    pub fn when_realized_datetime(
+        &self,
+        profile: Option<DateFormatProfile>,
+    ) -> Option<NaiveDateTime> {
+        parse_optional_datetime(&self.when_realized, profile)
+    }
+
+    /// Holding period start, parsed from [`Trade::holding_period_date_time`]
+    pub fn holding_period_datetime(
+        &self,
+        profile: Option<DateFormatProfile>,
+    ) -> Option<NaiveDateTime> {
+        parse_optional_datetime(&self.holding_period_date_time, profile)
+    }
+
+    /// When the position was opened, parsed from [`Trade::open_date_time`]
+    pub fn open_datetime(&self, profile: Option<DateFormatProfile>) -> Option<NaiveDateTime> {
+        parse_optional_datetime(&self.open_date_time, profile)
+    }
 }
 
 /// An open position snapshot
@@ -1206,15 +1907,15 @@ pub struct Position {
 
     // --- Position and Value ---
     /// Position quantity (negative for short)
-    #[serde(rename = "@position")]
+    #[serde(rename = "@position", deserialize_with = "deserialize_decimal")]
     pub quantity: Decimal,
 
     /// Mark price (current market price)
-    #[serde(rename = "@markPrice")]
+    #[serde(rename = "@markPrice", deserialize_with = "deserialize_decimal")]
     pub mark_price: Decimal,
 
     /// Position value (quantity * mark_price * multiplier)
-    #[serde(rename = "@positionValue")]
+    #[serde(rename = "@positionValue", deserialize_with = "deserialize_decimal")]
     pub position_value: Decimal,
 
     /// Side (Long/Short)
@@ -1396,6 +2097,10 @@ pub struct Position {
         deserialize_with = "deserialize_optional_date"
     )]
     pub vesting_date: Option<NaiveDate>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 impl Position {
@@ -1461,6 +2166,7 @@ impl Position {
     /// #     model: None,
     /// #     acct_alias: None,
     /// #     vesting_date: None,
+    /// #     extra_attributes: Default::default(),
     /// # };
     /// if let Some(derivative) = position.derivative() {
     ///     match derivative {
@@ -1477,7 +2183,7 @@ impl Position {
                 // For options, we need: strike, expiry, put_call, underlying_symbol
                 let strike = self.strike?;
                 let expiry = self.expiry?;
-                let put_call = self.put_call?;
+                let put_call = self.put_call.clone()?;
                 let underlying_symbol = self.underlying_symbol.clone()?;
 
                 Some(DerivativeInfo::Option {
@@ -1503,7 +2209,7 @@ impl Position {
                 // For future options, we need: strike, expiry, put_call, underlying_symbol
                 let strike = self.strike?;
                 let expiry = self.expiry?;
-                let put_call = self.put_call?;
+                let put_call = self.put_call.clone()?;
                 let underlying_symbol = self.underlying_symbol.clone()?;
 
                 Some(DerivativeInfo::FutureOption {
@@ -1528,6 +2234,21 @@ impl Position {
             _ => None,
         }
     }
+
+    /// Holding period start, parsed from [`Position::holding_period_date_time`]
+    ///
+    /// `profile` is the statement's [`ActivityFlexStatement::date_format`].
+    pub fn holding_period_datetime(
+        &self,
+        profile: Option<DateFormatProfile>,
+    ) -> Option<NaiveDateTime> {
+        parse_optional_datetime(&self.holding_period_date_time, profile)
+    }
+
+    /// When the position was opened, parsed from [`Position::open_date_time`]
+    pub fn open_datetime(&self, profile: Option<DateFormatProfile>) -> Option<NaiveDateTime> {
+        parse_optional_datetime(&self.open_date_time, profile)
+    }
 }
 
 /// A cash transaction (deposit, withdrawal, dividend, interest, fee)
@@ -1601,7 +2322,7 @@ pub struct CashTransaction {
     pub description: Option<String>,
 
     /// Amount (positive for credits, negative for debits)
-    #[serde(rename = "@amount")]
+    #[serde(rename = "@amount", deserialize_with = "deserialize_decimal")]
     pub amount: Decimal,
 
     /// Currency
@@ -1824,6 +2545,20 @@ pub struct CashTransaction {
     /// Account alias
     #[serde(rename = "@acctAlias", default)]
     pub acct_alias: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+impl CashTransaction {
+    /// Transaction time, parsed from [`CashTransaction::date_time`]
+    ///
+    /// `profile` is the statement's [`ActivityFlexStatement::date_format`].
+    /// Returns `None` when absent or not a valid IB timestamp.
+    pub fn datetime(&self, profile: Option<DateFormatProfile>) -> Option<NaiveDateTime> {
+        parse_optional_datetime(&self.date_time, profile)
+    }
 }
 
 /// A corporate action (split, merger, spinoff, etc.)
@@ -2171,6 +2906,10 @@ pub struct CorporateAction {
     /// Account alias
     #[serde(rename = "@acctAlias", default)]
     pub acct_alias: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Security information (reference data)
@@ -2382,6 +3121,10 @@ pub struct SecurityInfo {
     /// Code
     #[serde(rename = "@code", default)]
     pub code: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Foreign exchange conversion rate
@@ -2435,8 +3178,12 @@ pub struct ConversionRate {
     pub to_currency: String,
 
     /// Exchange rate
-    #[serde(rename = "@rate")]
+    #[serde(rename = "@rate", deserialize_with = "deserialize_decimal")]
     pub rate: Decimal,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Wrapper for securities info section
@@ -2473,6 +3220,30 @@ pub struct CashReportWrapper {
     pub items: Vec<super::extended::CashReportCurrency>,
 }
 
+/// Wrapper for deposits on hold section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct DepositsOnHoldWrapper {
+    /// List of deposits on hold
+    #[serde(rename = "DepositOnHold", default)]
+    pub items: Vec<super::extended::DepositOnHold>,
+}
+
+/// Wrapper for FDIC insured deposits by bank section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct FdicInsuredDepositsWrapper {
+    /// List of per-bank deposits
+    #[serde(rename = "FdicInsuredDepositByBank", default)]
+    pub items: Vec<super::extended::FdicInsuredDepositByBank>,
+}
+
+/// Wrapper for IBG note transactions section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct IBGNoteTransactionsWrapper {
+    /// List of IBG note transactions
+    #[serde(rename = "IBGNoteTransaction", default)]
+    pub items: Vec<super::extended::IBGNoteTransaction>,
+}
+
 /// Wrapper for trade confirmations section
 #[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
 pub struct TradeConfirmsWrapper {
@@ -2611,6 +3382,22 @@ pub struct SLBFeesWrapper {
     pub items: Vec<super::extended::SLBFee>,
 }
 
+/// Wrapper for securities lending open contracts section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct SLBOpenContractsWrapper {
+    /// List of open contracts
+    #[serde(rename = "SLBOpenContract", default)]
+    pub items: Vec<super::extended::SLBOpenContract>,
+}
+
+/// Wrapper for securities lending collateral section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct SLBCollateralsWrapper {
+    /// List of collateral rows
+    #[serde(rename = "SLBCollateral", default)]
+    pub items: Vec<super::extended::SLBCollateral>,
+}
+
 /// Wrapper for hard to borrow details section
 #[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
 pub struct HardToBorrowDetailsWrapper {
@@ -2675,6 +3462,130 @@ pub struct SalesTaxWrapper {
     pub items: Vec<super::extended::SalesTax>,
 }
 
+/// Wrapper for net stock positions section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct NetStockPositionsWrapper {
+    /// List of net stock positions
+    #[serde(rename = "NetStockPosition", default)]
+    pub items: Vec<super::extended::NetStockPosition>,
+}
+
+/// Wrapper for net stock position summary section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct NetStockPositionSummaryWrapper {
+    /// List of per-symbol net stock positions
+    #[serde(
+        rename = "NetStockPositionSummary",
+        alias = "NetStockPosition",
+        default
+    )]
+    pub items: Vec<super::extended::NetStockPosition>,
+}
+
+/// Wrapper for complex positions section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct ComplexPositionsWrapper {
+    /// List of complex positions
+    #[serde(rename = "ComplexPosition", default)]
+    pub items: Vec<super::extended::ComplexPosition>,
+}
+
+/// Wrapper for FX positions section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct FxPositionsWrapper {
+    /// List of FX positions
+    #[serde(rename = "FxPosition", default)]
+    pub items: Vec<super::extended::FxPosition>,
+}
+
+/// Wrapper for mutual fund dividend details section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct MutualFundDividendDetailsWrapper {
+    /// List of mutual fund dividend details
+    #[serde(rename = "MutualFundDividendDetail", default)]
+    pub items: Vec<super::extended::MutualFundDividendDetail>,
+}
+
+/// Wrapper for incentive coupon accrual details section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct IncentiveCouponAccrualDetailsWrapper {
+    /// List of incentive coupon accruals
+    #[serde(rename = "IncentiveCouponAccrualDetail", default)]
+    pub items: Vec<super::extended::IncentiveCouponAccrualDetail>,
+}
+
+/// Wrapper for HK IPO open subscriptions section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct HKIPOOpenSubscriptionsWrapper {
+    /// List of open subscriptions
+    #[serde(rename = "HKIPOOpenSubscription", default)]
+    pub items: Vec<super::extended::HKIPOOpenSubscription>,
+}
+
+/// Wrapper for HK IPO subscription activity section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct HKIPOSubscriptionActivityWrapper {
+    /// List of subscription activity rows
+    #[serde(rename = "HKIPOSubscriptionActivity", default)]
+    pub items: Vec<super::extended::HKIPOSubscriptionActivity>,
+}
+
+/// Wrapper for stock grant activities section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct StockGrantActivitiesWrapper {
+    /// List of stock grant activities
+    #[serde(rename = "StockGrantActivity", default)]
+    pub items: Vec<super::extended::StockGrantActivity>,
+}
+
+/// Wrapper for pending exercises section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct PendingExercisesWrapper {
+    /// List of pending exercises and assignments
+    #[serde(rename = "PendingExercise", alias = "PendingExcercise", default)]
+    pub items: Vec<super::extended::PendingExercise>,
+}
+
+/// Wrapper for unbooked trades section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct UnbookedTradesWrapper {
+    /// List of unbooked trades
+    #[serde(rename = "UnbookedTrade", default)]
+    pub items: Vec<super::extended::UnbookedTrade>,
+}
+
+/// Wrapper for transaction taxes section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct TransactionTaxesWrapper {
+    /// List of transaction taxes
+    #[serde(rename = "TransactionTax", default)]
+    pub items: Vec<super::extended::TransactionTax>,
+}
+
+/// Wrapper for CFD charges section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct CFDChargesWrapper {
+    /// List of CFD charges
+    #[serde(rename = "CFDCharge", default)]
+    pub items: Vec<super::extended::CFDCharge>,
+}
+
+/// Wrapper for routing commissions section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct RoutingCommissionsWrapper {
+    /// List of routing commissions
+    #[serde(rename = "RoutingCommission", default)]
+    pub items: Vec<super::extended::RoutingCommission>,
+}
+
+/// Wrapper for commission credits section
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct CommissionCreditsWrapper {
+    /// List of commission credits
+    #[serde(rename = "CommissionCredit", default)]
+    pub items: Vec<super::extended::CommissionCredit>,
+}
+
 /// Wrapper for symbol summary section
 #[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
 pub struct SymbolSummaryWrapper {
@@ -0,0 +1,1907 @@
+//! Borrowed Activity FLEX types
+//!
+//! The owned row types allocate a `String` for every text attribute. The
+//! types here hold `Cow<'a, str>` instead, borrowing from the XML they were
+//! parsed from, for hot paths that only need trades, open positions and cash
+//! transactions. Every row converts into its owned counterpart with
+//! `into_owned`.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::activity::{CashTransaction, Position, Trade};
+use super::common::{
+    AssetCategory, BuySell, LevelOfDetail, OpenClose, OrderType, PutCall, SecurityIdType,
+    SubCategory, TradeType,
+};
+use crate::parsers::xml_utils::{
+    deserialize_decimal, deserialize_optional_bool, deserialize_optional_cow,
+    deserialize_optional_date, deserialize_optional_decimal,
+};
+
+/// FLEX query response with borrowed statements
+#[derive(Debug, Deserialize)]
+pub(crate) struct FlexQueryResponseRef<'a> {
+    /// FlexStatements wrapper
+    #[serde(rename = "FlexStatements", borrow)]
+    pub statements: FlexStatementsRef<'a>,
+}
+
+/// Wrapper for borrowed FlexStatements
+#[derive(Debug, Deserialize)]
+pub(crate) struct FlexStatementsRef<'a> {
+    /// Flex statement(s)
+    #[serde(rename = "FlexStatement", default, borrow)]
+    pub statements: Vec<ActivityFlexStatementRef<'a>>,
+}
+
+/// Activity FLEX statement borrowing from its XML
+///
+/// Parsed by [`crate::parse_activity_flex_borrowed`]. Only trades, open
+/// positions and cash transactions are deserialized; other sections are
+/// skipped.
+///
+/// # Example
+///
+/// ```rust
+/// use ib_flex::parse_activity_flex_borrowed;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let xml = std::fs::read_to_string("tests/fixtures/activity_minimal.xml")?;
+/// let statement = parse_activity_flex_borrowed(&xml)?;
+///
+/// for trade in &statement.trades {
+///     println!("{} {:?}", trade.symbol, trade.quantity);
+/// }
+/// let owned: Vec<ib_flex::Trade> = statement.trades.into_iter().map(|t| t.into_owned()).collect();
+/// # let _ = owned;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename = "FlexStatement")]
+pub struct ActivityFlexStatementRef<'a> {
+    /// IB account number
+    #[serde(rename = "@accountId", borrow)]
+    pub account_id: Cow<'a, str>,
+
+    /// Statement date range - start date
+    #[serde(
+        rename = "@fromDate",
+        deserialize_with = "crate::parsers::xml_utils::deserialize_flex_date"
+    )]
+    pub from_date: NaiveDate,
+
+    /// Statement date range - end date
+    #[serde(
+        rename = "@toDate",
+        deserialize_with = "crate::parsers::xml_utils::deserialize_flex_date"
+    )]
+    pub to_date: NaiveDate,
+
+    /// When the report was generated
+    #[serde(rename = "@whenGenerated", borrow)]
+    pub when_generated: Cow<'a, str>,
+
+    /// Trade executions (`<Trade>` rows of `<Trades>`)
+    #[serde(rename = "Trades", default, borrow, deserialize_with = "trades")]
+    pub trades: Vec<TradeRef<'a>>,
+
+    /// Open positions
+    #[serde(
+        rename = "OpenPositions",
+        default,
+        borrow,
+        deserialize_with = "positions"
+    )]
+    pub positions: Vec<PositionRef<'a>>,
+
+    /// Cash transactions
+    #[serde(
+        rename = "CashTransactions",
+        default,
+        borrow,
+        deserialize_with = "cash_transactions"
+    )]
+    pub cash_transactions: Vec<CashTransactionRef<'a>>,
+}
+
+/// The `<Trade>` rows of a `<Trades>` section
+fn trades<'de, D>(deserializer: D) -> Result<Vec<TradeRef<'de>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    /// Rows interleaved in `<Trades>`; see `TradesWrapper`. Boxing the
+    /// trade would cost an allocation per row.
+    #[derive(Deserialize)]
+    #[allow(clippy::large_enum_variant)]
+    enum Item<'a> {
+        Trade(#[serde(borrow)] TradeRef<'a>),
+        Order(IgnoredAny),
+        SymbolSummary(IgnoredAny),
+        AssetSummary(IgnoredAny),
+        WashSale(IgnoredAny),
+        Lot(IgnoredAny),
+    }
+
+    #[derive(Deserialize)]
+    struct Section<'a> {
+        #[serde(rename = "$value", default, borrow)]
+        items: Vec<Item<'a>>,
+    }
+
+    let section = Section::deserialize(deserializer)?;
+    Ok(section
+        .items
+        .into_iter()
+        .filter_map(|item| match item {
+            Item::Trade(trade) => Some(trade),
+            _ => None,
+        })
+        .collect())
+}
+
+/// The rows of an `<OpenPositions>` section
+fn positions<'de, D>(deserializer: D) -> Result<Vec<PositionRef<'de>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Section<'a> {
+        #[serde(rename = "OpenPosition", default, borrow)]
+        items: Vec<PositionRef<'a>>,
+    }
+
+    Ok(Section::deserialize(deserializer)?.items)
+}
+
+/// The rows of a `<CashTransactions>` section
+fn cash_transactions<'de, D>(deserializer: D) -> Result<Vec<CashTransactionRef<'de>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Section<'a> {
+        #[serde(rename = "CashTransaction", default, borrow)]
+        items: Vec<CashTransactionRef<'a>>,
+    }
+
+    Ok(Section::deserialize(deserializer)?.items)
+}
+
+/// Borrowed [`Trade`]
+///
+/// Text fields borrow from the input XML unless they contain escaped
+/// characters. Unmodeled attributes are not kept.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TradeRef<'a> {
+    // ==================== CORE FIELDS ====================
+    // Essential for tax reporting and portfolio analytics
+
+    // --- Account ---
+    /// IB account number
+    #[serde(rename = "@accountId", borrow)]
+    pub account_id: Cow<'a, str>,
+
+    /// IB transaction ID (unique identifier for idempotency)
+    #[serde(
+        rename = "@transactionID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub transaction_id: Option<Cow<'a, str>>,
+
+    // --- Security Identification ---
+    /// IB contract ID (unique per security)
+    #[serde(rename = "@conid", borrow)]
+    pub conid: Cow<'a, str>,
+
+    /// Ticker symbol
+    #[serde(rename = "@symbol", borrow)]
+    pub symbol: Cow<'a, str>,
+
+    /// Security description
+    #[serde(
+        rename = "@description",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub description: Option<Cow<'a, str>>,
+
+    /// Asset category (stock, option, future, etc.)
+    #[serde(rename = "@assetCategory")]
+    pub asset_category: AssetCategory,
+
+    /// CUSIP
+    #[serde(
+        rename = "@cusip",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub cusip: Option<Cow<'a, str>>,
+
+    /// ISIN
+    #[serde(
+        rename = "@isin",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub isin: Option<Cow<'a, str>>,
+
+    /// FIGI
+    #[serde(
+        rename = "@figi",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub figi: Option<Cow<'a, str>>,
+
+    /// Security ID
+    #[serde(
+        rename = "@securityID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub security_id: Option<Cow<'a, str>>,
+
+    /// Security ID type
+    #[serde(rename = "@securityIDType", default)]
+    pub security_id_type: Option<SecurityIdType>,
+
+    // --- Derivatives (Options/Futures) ---
+    /// Contract multiplier (for futures/options)
+    #[serde(
+        rename = "@multiplier",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub multiplier: Option<Decimal>,
+
+    /// Strike price (for options)
+    #[serde(
+        rename = "@strike",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub strike: Option<Decimal>,
+
+    /// Expiry date (for options/futures)
+    #[serde(
+        rename = "@expiry",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub expiry: Option<NaiveDate>,
+
+    /// Put or Call (for options)
+    #[serde(rename = "@putCall", default)]
+    pub put_call: Option<PutCall>,
+
+    /// Underlying security's contract ID (for derivatives)
+    #[serde(
+        rename = "@underlyingConid",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub underlying_conid: Option<Cow<'a, str>>,
+
+    /// Underlying symbol
+    #[serde(
+        rename = "@underlyingSymbol",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub underlying_symbol: Option<Cow<'a, str>>,
+
+    // --- Trade Execution ---
+    /// Trade date (may be empty for summary records)
+    #[serde(
+        rename = "@tradeDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub trade_date: Option<NaiveDate>,
+
+    /// Settlement date (may be empty for summary records)
+    #[serde(
+        rename = "@settleDateTarget",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub settle_date: Option<NaiveDate>,
+
+    /// Buy or Sell
+    #[serde(rename = "@buySell", default)]
+    pub buy_sell: Option<BuySell>,
+
+    /// Open or Close indicator (for options/futures)
+    #[serde(rename = "@openCloseIndicator", default)]
+    pub open_close: Option<OpenClose>,
+
+    /// Transaction type (ExchTrade, BookTrade, etc.)
+    #[serde(rename = "@transactionType", default)]
+    pub transaction_type: Option<TradeType>,
+
+    // --- Quantities and Prices ---
+    /// Quantity (number of shares/contracts)
+    #[serde(
+        rename = "@quantity",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub quantity: Option<Decimal>,
+
+    /// Trade price per share/contract
+    #[serde(
+        rename = "@tradePrice",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub trade_price: Option<Decimal>,
+
+    /// Trade proceeds (negative for buys, positive for sells)
+    #[serde(
+        rename = "@proceeds",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub proceeds: Option<Decimal>,
+
+    /// Cost basis
+    #[serde(
+        rename = "@cost",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub cost: Option<Decimal>,
+
+    // --- Fees and Taxes ---
+    /// Commission paid
+    #[serde(
+        rename = "@ibCommission",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub commission: Option<Decimal>,
+
+    /// Taxes paid
+    #[serde(
+        rename = "@taxes",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub taxes: Option<Decimal>,
+
+    /// Net cash (proceeds + commission + taxes)
+    #[serde(
+        rename = "@netCash",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub net_cash: Option<Decimal>,
+
+    // --- P&L ---
+    /// FIFO realized P&L (for closing trades)
+    #[serde(
+        rename = "@fifoPnlRealized",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fifo_pnl_realized: Option<Decimal>,
+
+    /// Mark-to-market P&L
+    #[serde(
+        rename = "@mtmPnl",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub mtm_pnl: Option<Decimal>,
+
+    /// FX P&L (for multi-currency)
+    #[serde(
+        rename = "@fxPnl",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_pnl: Option<Decimal>,
+
+    // --- Currency ---
+    /// Trade currency
+    #[serde(rename = "@currency", borrow)]
+    pub currency: Cow<'a, str>,
+
+    /// FX rate to base currency
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    // --- Tax Lot Tracking (Critical for tax reporting) ---
+    /// Original trade date (for lot tracking and holding period)
+    #[serde(
+        rename = "@origTradeDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub orig_trade_date: Option<NaiveDate>,
+
+    /// Original trade price (cost basis of the lot)
+    #[serde(
+        rename = "@origTradePrice",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub orig_trade_price: Option<Decimal>,
+
+    /// Original trade ID (links closing trade to opening trade)
+    #[serde(
+        rename = "@origTradeID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub orig_trade_id: Option<Cow<'a, str>>,
+
+    /// Holding period date/time (for long-term vs short-term determination)
+    #[serde(
+        rename = "@holdingPeriodDateTime",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub holding_period_date_time: Option<Cow<'a, str>>,
+
+    /// When position was opened
+    #[serde(
+        rename = "@openDateTime",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub open_date_time: Option<Cow<'a, str>>,
+
+    /// When position was reopened (for wash sale tracking)
+    #[serde(
+        rename = "@whenReopened",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub when_reopened: Option<Cow<'a, str>>,
+
+    /// Trade notes/codes (may contain multiple TransactionCode, e.g. Closing + WashSale)
+    #[serde(
+        rename = "@notes",
+        default,
+        deserialize_with = "crate::parsers::xml_utils::deserialize_transaction_codes",
+        serialize_with = "crate::parsers::xml_utils::serialize_transaction_codes"
+    )]
+    pub notes: Option<Vec<super::common::TransactionCode>>,
+
+    // ==================== EXTENDED FIELDS ====================
+    // Metadata, execution details, and less commonly used fields
+
+    // --- Order/Execution IDs ---
+    /// IB order ID (may be shared across multiple executions)
+    #[serde(
+        rename = "@ibOrderID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub ib_order_id: Option<Cow<'a, str>>,
+
+    /// Execution ID
+    #[serde(
+        rename = "@execID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub exec_id: Option<Cow<'a, str>>,
+
+    /// Trade ID
+    #[serde(
+        rename = "@tradeID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub trade_id: Option<Cow<'a, str>>,
+
+    /// Original transaction ID
+    #[serde(
+        rename = "@origTransactionID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub orig_transaction_id: Option<Cow<'a, str>>,
+
+    /// Original order ID
+    #[serde(
+        rename = "@origOrderID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub orig_order_id: Option<Cow<'a, str>>,
+
+    // --- Timestamps ---
+    /// Trade time (date + time)
+    #[serde(
+        rename = "@dateTime",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub trade_time: Option<Cow<'a, str>>,
+
+    /// When P&L was realized
+    #[serde(
+        rename = "@whenRealized",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub when_realized: Option<Cow<'a, str>>,
+
+    /// Order time
+    #[serde(
+        rename = "@orderTime",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub order_time: Option<Cow<'a, str>>,
+
+    // --- Order Details ---
+    /// Order type (market, limit, stop, etc.)
+    #[serde(rename = "@orderType", default)]
+    pub order_type: Option<OrderType>,
+
+    /// Brokerage order ID
+    #[serde(
+        rename = "@brokerageOrderID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub brokerage_order_id: Option<Cow<'a, str>>,
+
+    /// Order reference
+    #[serde(
+        rename = "@orderReference",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub order_reference: Option<Cow<'a, str>>,
+
+    /// Exchange order ID
+    #[serde(
+        rename = "@exchOrderId",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub exch_order_id: Option<Cow<'a, str>>,
+
+    /// External execution ID
+    #[serde(
+        rename = "@extExecID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub ext_exec_id: Option<Cow<'a, str>>,
+
+    /// IB execution ID
+    #[serde(
+        rename = "@ibExecID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub ib_exec_id: Option<Cow<'a, str>>,
+
+    // --- Issuer/Security Metadata ---
+    /// Issuer
+    #[serde(
+        rename = "@issuer",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub issuer: Option<Cow<'a, str>>,
+
+    /// Issuer country code
+    #[serde(
+        rename = "@issuerCountryCode",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub issuer_country_code: Option<Cow<'a, str>>,
+
+    /// Sub-category
+    #[serde(rename = "@subCategory", default)]
+    pub sub_category: Option<SubCategory>,
+
+    /// Listing exchange
+    #[serde(
+        rename = "@listingExchange",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub listing_exchange: Option<Cow<'a, str>>,
+
+    // --- Underlying Extended ---
+    /// Underlying listing exchange
+    #[serde(
+        rename = "@underlyingListingExchange",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub underlying_listing_exchange: Option<Cow<'a, str>>,
+
+    /// Underlying security ID
+    #[serde(
+        rename = "@underlyingSecurityID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub underlying_security_id: Option<Cow<'a, str>>,
+
+    // --- Execution Metadata ---
+    /// Trader ID
+    #[serde(
+        rename = "@traderID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub trader_id: Option<Cow<'a, str>>,
+
+    /// Is API order (true if order was placed via API)
+    #[serde(
+        rename = "@isAPIOrder",
+        default,
+        deserialize_with = "deserialize_optional_bool"
+    )]
+    pub is_api_order: Option<bool>,
+
+    /// Volatility order link
+    #[serde(
+        rename = "@volatilityOrderLink",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub volatility_order_link: Option<Cow<'a, str>>,
+
+    /// Clearing firm ID
+    #[serde(
+        rename = "@clearingFirmID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub clearing_firm_id: Option<Cow<'a, str>>,
+
+    /// Level of detail (EXECUTION, ORDER, CLOSED_LOT, etc.)
+    #[serde(rename = "@levelOfDetail", default)]
+    pub level_of_detail: Option<LevelOfDetail>,
+
+    // --- Price/Quantity Changes ---
+    /// Trade amount
+    #[serde(
+        rename = "@amount",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub amount: Option<Decimal>,
+
+    /// Trade money (quantity * price)
+    #[serde(
+        rename = "@tradeMoney",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub trade_money: Option<Decimal>,
+
+    /// Close price
+    #[serde(
+        rename = "@closePrice",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub close_price: Option<Decimal>,
+
+    /// Change in price
+    #[serde(
+        rename = "@changeInPrice",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub change_in_price: Option<Decimal>,
+
+    /// Change in quantity
+    #[serde(
+        rename = "@changeInQuantity",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub change_in_quantity: Option<Decimal>,
+
+    /// Commission currency
+    #[serde(
+        rename = "@ibCommissionCurrency",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub commission_currency: Option<Cow<'a, str>>,
+
+    // --- Related Trade Tracking ---
+    /// Related trade ID
+    #[serde(
+        rename = "@relatedTradeID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub related_trade_id: Option<Cow<'a, str>>,
+
+    /// Related transaction ID
+    #[serde(
+        rename = "@relatedTransactionID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub related_transaction_id: Option<Cow<'a, str>>,
+
+    // --- Bond Fields ---
+    /// Accrued interest
+    #[serde(
+        rename = "@accruedInt",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub accrued_int: Option<Decimal>,
+
+    /// Principal adjust factor
+    #[serde(
+        rename = "@principalAdjustFactor",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub principal_adjust_factor: Option<Decimal>,
+
+    // --- Commodity/Physical Delivery ---
+    /// Serial number (for physical delivery)
+    #[serde(
+        rename = "@serialNumber",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub serial_number: Option<Cow<'a, str>>,
+
+    /// Delivery type
+    #[serde(
+        rename = "@deliveryType",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub delivery_type: Option<Cow<'a, str>>,
+
+    /// Commodity type
+    #[serde(
+        rename = "@commodityType",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub commodity_type: Option<Cow<'a, str>>,
+
+    /// Fineness (for precious metals)
+    #[serde(
+        rename = "@fineness",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fineness: Option<Decimal>,
+
+    /// Weight
+    #[serde(
+        rename = "@weight",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub weight: Option<Cow<'a, str>>,
+
+    // --- Other Metadata ---
+    /// Report date
+    #[serde(
+        rename = "@reportDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub report_date: Option<NaiveDate>,
+
+    /// Exchange where trade executed
+    #[serde(
+        rename = "@exchange",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub exchange: Option<Cow<'a, str>>,
+
+    /// Model (for model portfolios)
+    #[serde(
+        rename = "@model",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub model: Option<Cow<'a, str>>,
+
+    /// Account alias
+    #[serde(
+        rename = "@acctAlias",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub acct_alias: Option<Cow<'a, str>>,
+
+    /// RTN
+    #[serde(
+        rename = "@rtn",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub rtn: Option<Cow<'a, str>>,
+
+    /// Position action ID
+    #[serde(
+        rename = "@positionActionID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub position_action_id: Option<Cow<'a, str>>,
+
+    /// Initial investment
+    #[serde(
+        rename = "@initialInvestment",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub initial_investment: Option<Decimal>,
+}
+
+impl TradeRef<'_> {
+    /// Convert into the owned [`Trade`]
+    pub fn into_owned(self) -> Trade {
+        Trade {
+            account_id: self.account_id.into_owned(),
+            transaction_id: self.transaction_id.map(Cow::into_owned),
+            conid: self.conid.into_owned(),
+            symbol: self.symbol.into_owned(),
+            description: self.description.map(Cow::into_owned),
+            asset_category: self.asset_category,
+            cusip: self.cusip.map(Cow::into_owned),
+            isin: self.isin.map(Cow::into_owned),
+            figi: self.figi.map(Cow::into_owned),
+            security_id: self.security_id.map(Cow::into_owned),
+            security_id_type: self.security_id_type,
+            multiplier: self.multiplier,
+            strike: self.strike,
+            expiry: self.expiry,
+            put_call: self.put_call,
+            underlying_conid: self.underlying_conid.map(Cow::into_owned),
+            underlying_symbol: self.underlying_symbol.map(Cow::into_owned),
+            trade_date: self.trade_date,
+            settle_date: self.settle_date,
+            buy_sell: self.buy_sell,
+            open_close: self.open_close,
+            transaction_type: self.transaction_type,
+            quantity: self.quantity,
+            trade_price: self.trade_price,
+            proceeds: self.proceeds,
+            cost: self.cost,
+            commission: self.commission,
+            taxes: self.taxes,
+            net_cash: self.net_cash,
+            fifo_pnl_realized: self.fifo_pnl_realized,
+            mtm_pnl: self.mtm_pnl,
+            fx_pnl: self.fx_pnl,
+            currency: self.currency.into_owned(),
+            fx_rate_to_base: self.fx_rate_to_base,
+            orig_trade_date: self.orig_trade_date,
+            orig_trade_price: self.orig_trade_price,
+            orig_trade_id: self.orig_trade_id.map(Cow::into_owned),
+            holding_period_date_time: self.holding_period_date_time.map(Cow::into_owned),
+            open_date_time: self.open_date_time.map(Cow::into_owned),
+            when_reopened: self.when_reopened.map(Cow::into_owned),
+            notes: self.notes,
+            ib_order_id: self.ib_order_id.map(Cow::into_owned),
+            exec_id: self.exec_id.map(Cow::into_owned),
+            trade_id: self.trade_id.map(Cow::into_owned),
+            orig_transaction_id: self.orig_transaction_id.map(Cow::into_owned),
+            orig_order_id: self.orig_order_id.map(Cow::into_owned),
+            trade_time: self.trade_time.map(Cow::into_owned),
+            when_realized: self.when_realized.map(Cow::into_owned),
+            order_time: self.order_time.map(Cow::into_owned),
+            order_type: self.order_type,
+            brokerage_order_id: self.brokerage_order_id.map(Cow::into_owned),
+            order_reference: self.order_reference.map(Cow::into_owned),
+            exch_order_id: self.exch_order_id.map(Cow::into_owned),
+            ext_exec_id: self.ext_exec_id.map(Cow::into_owned),
+            ib_exec_id: self.ib_exec_id.map(Cow::into_owned),
+            issuer: self.issuer.map(Cow::into_owned),
+            issuer_country_code: self.issuer_country_code.map(Cow::into_owned),
+            sub_category: self.sub_category,
+            listing_exchange: self.listing_exchange.map(Cow::into_owned),
+            underlying_listing_exchange: self.underlying_listing_exchange.map(Cow::into_owned),
+            underlying_security_id: self.underlying_security_id.map(Cow::into_owned),
+            trader_id: self.trader_id.map(Cow::into_owned),
+            is_api_order: self.is_api_order,
+            volatility_order_link: self.volatility_order_link.map(Cow::into_owned),
+            clearing_firm_id: self.clearing_firm_id.map(Cow::into_owned),
+            level_of_detail: self.level_of_detail,
+            amount: self.amount,
+            trade_money: self.trade_money,
+            close_price: self.close_price,
+            change_in_price: self.change_in_price,
+            change_in_quantity: self.change_in_quantity,
+            commission_currency: self.commission_currency.map(Cow::into_owned),
+            related_trade_id: self.related_trade_id.map(Cow::into_owned),
+            related_transaction_id: self.related_transaction_id.map(Cow::into_owned),
+            accrued_int: self.accrued_int,
+            principal_adjust_factor: self.principal_adjust_factor,
+            serial_number: self.serial_number.map(Cow::into_owned),
+            delivery_type: self.delivery_type.map(Cow::into_owned),
+            commodity_type: self.commodity_type.map(Cow::into_owned),
+            fineness: self.fineness,
+            weight: self.weight.map(Cow::into_owned),
+            report_date: self.report_date,
+            exchange: self.exchange.map(Cow::into_owned),
+            model: self.model.map(Cow::into_owned),
+            acct_alias: self.acct_alias.map(Cow::into_owned),
+            rtn: self.rtn.map(Cow::into_owned),
+            position_action_id: self.position_action_id.map(Cow::into_owned),
+            initial_investment: self.initial_investment,
+            extra_attributes: BTreeMap::new(),
+        }
+    }
+}
+
+/// Borrowed [`Position`]
+///
+/// Text fields borrow from the input XML unless they contain escaped
+/// characters. Unmodeled attributes are not kept.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PositionRef<'a> {
+    // ==================== CORE FIELDS ====================
+    // Essential for tax reporting and portfolio analytics
+
+    // --- Account ---
+    /// IB account number
+    #[serde(rename = "@accountId", borrow)]
+    pub account_id: Cow<'a, str>,
+
+    // --- Security Identification ---
+    /// IB contract ID
+    #[serde(rename = "@conid", borrow)]
+    pub conid: Cow<'a, str>,
+
+    /// Ticker symbol
+    #[serde(rename = "@symbol", borrow)]
+    pub symbol: Cow<'a, str>,
+
+    /// Security description
+    #[serde(
+        rename = "@description",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub description: Option<Cow<'a, str>>,
+
+    /// Asset category
+    #[serde(rename = "@assetCategory")]
+    pub asset_category: AssetCategory,
+
+    /// CUSIP
+    #[serde(
+        rename = "@cusip",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub cusip: Option<Cow<'a, str>>,
+
+    /// ISIN
+    #[serde(
+        rename = "@isin",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub isin: Option<Cow<'a, str>>,
+
+    /// FIGI
+    #[serde(
+        rename = "@figi",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub figi: Option<Cow<'a, str>>,
+
+    /// Security ID
+    #[serde(
+        rename = "@securityID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub security_id: Option<Cow<'a, str>>,
+
+    /// Security ID type
+    #[serde(rename = "@securityIDType", default)]
+    pub security_id_type: Option<SecurityIdType>,
+
+    // --- Derivatives (Options/Futures) ---
+    /// Contract multiplier
+    #[serde(
+        rename = "@multiplier",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub multiplier: Option<Decimal>,
+
+    /// Strike (for options)
+    #[serde(
+        rename = "@strike",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub strike: Option<Decimal>,
+
+    /// Expiry (for options/futures)
+    #[serde(
+        rename = "@expiry",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub expiry: Option<NaiveDate>,
+
+    /// Put or Call
+    #[serde(rename = "@putCall", default)]
+    pub put_call: Option<PutCall>,
+
+    /// Underlying contract ID
+    #[serde(
+        rename = "@underlyingConid",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub underlying_conid: Option<Cow<'a, str>>,
+
+    /// Underlying symbol
+    #[serde(
+        rename = "@underlyingSymbol",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub underlying_symbol: Option<Cow<'a, str>>,
+
+    // --- Position and Value ---
+    /// Position quantity (negative for short)
+    #[serde(rename = "@position", deserialize_with = "deserialize_decimal")]
+    pub quantity: Decimal,
+
+    /// Mark price (current market price)
+    #[serde(rename = "@markPrice", deserialize_with = "deserialize_decimal")]
+    pub mark_price: Decimal,
+
+    /// Position value (quantity * mark_price * multiplier)
+    #[serde(rename = "@positionValue", deserialize_with = "deserialize_decimal")]
+    pub position_value: Decimal,
+
+    /// Side (Long/Short)
+    #[serde(
+        rename = "@side",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub side: Option<Cow<'a, str>>,
+
+    // --- Cost Basis and P&L ---
+    /// Open price
+    #[serde(
+        rename = "@openPrice",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub open_price: Option<Decimal>,
+
+    /// Cost basis price per share/contract
+    #[serde(
+        rename = "@costBasisPrice",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub cost_basis_price: Option<Decimal>,
+
+    /// Total cost basis
+    #[serde(
+        rename = "@costBasisMoney",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub cost_basis_money: Option<Decimal>,
+
+    /// FIFO unrealized P&L
+    #[serde(
+        rename = "@fifoPnlUnrealized",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fifo_pnl_unrealized: Option<Decimal>,
+
+    /// Percent of NAV
+    #[serde(
+        rename = "@percentOfNAV",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub percent_of_nav: Option<Decimal>,
+
+    // --- Currency ---
+    /// Currency
+    #[serde(rename = "@currency", borrow)]
+    pub currency: Cow<'a, str>,
+
+    /// FX rate to base currency
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    // --- Dates ---
+    /// Date of this position snapshot
+    #[serde(
+        rename = "@reportDate",
+        deserialize_with = "crate::parsers::xml_utils::deserialize_flex_date"
+    )]
+    pub report_date: NaiveDate,
+
+    // --- Tax Lot Tracking (Critical for tax reporting) ---
+    /// Holding period date/time (for long-term vs short-term determination)
+    #[serde(
+        rename = "@holdingPeriodDateTime",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub holding_period_date_time: Option<Cow<'a, str>>,
+
+    /// When position was opened
+    #[serde(
+        rename = "@openDateTime",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub open_date_time: Option<Cow<'a, str>>,
+
+    /// Originating transaction ID
+    #[serde(
+        rename = "@originatingTransactionID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub originating_transaction_id: Option<Cow<'a, str>>,
+
+    /// Position code (may contain tax-related codes)
+    #[serde(
+        rename = "@code",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub code: Option<Cow<'a, str>>,
+
+    // ==================== EXTENDED FIELDS ====================
+    // Metadata and less commonly used fields
+
+    // --- Extended IDs ---
+    /// Originating order ID (links to opening trade)
+    #[serde(
+        rename = "@originatingOrderID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub originating_order_id: Option<Cow<'a, str>>,
+
+    // --- Issuer/Security Metadata ---
+    /// Issuer
+    #[serde(
+        rename = "@issuer",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub issuer: Option<Cow<'a, str>>,
+
+    /// Issuer country code
+    #[serde(
+        rename = "@issuerCountryCode",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub issuer_country_code: Option<Cow<'a, str>>,
+
+    /// Sub-category
+    #[serde(rename = "@subCategory", default)]
+    pub sub_category: Option<SubCategory>,
+
+    /// Listing exchange
+    #[serde(
+        rename = "@listingExchange",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub listing_exchange: Option<Cow<'a, str>>,
+
+    // --- Underlying Extended ---
+    /// Underlying listing exchange
+    #[serde(
+        rename = "@underlyingListingExchange",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub underlying_listing_exchange: Option<Cow<'a, str>>,
+
+    /// Underlying security ID
+    #[serde(
+        rename = "@underlyingSecurityID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub underlying_security_id: Option<Cow<'a, str>>,
+
+    // --- Bond Fields ---
+    /// Accrued interest
+    #[serde(
+        rename = "@accruedInt",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub accrued_int: Option<Decimal>,
+
+    /// Principal adjust factor
+    #[serde(
+        rename = "@principalAdjustFactor",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub principal_adjust_factor: Option<Decimal>,
+
+    // --- Commodity/Physical Delivery ---
+    /// Serial number (for physical delivery)
+    #[serde(
+        rename = "@serialNumber",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub serial_number: Option<Cow<'a, str>>,
+
+    /// Delivery type
+    #[serde(
+        rename = "@deliveryType",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub delivery_type: Option<Cow<'a, str>>,
+
+    /// Commodity type
+    #[serde(
+        rename = "@commodityType",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub commodity_type: Option<Cow<'a, str>>,
+
+    /// Fineness (for precious metals)
+    #[serde(
+        rename = "@fineness",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fineness: Option<Decimal>,
+
+    /// Weight
+    #[serde(
+        rename = "@weight",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub weight: Option<Cow<'a, str>>,
+
+    // --- Other Metadata ---
+    /// Level of detail
+    #[serde(rename = "@levelOfDetail", default)]
+    pub level_of_detail: Option<LevelOfDetail>,
+
+    /// Model (for model portfolios)
+    #[serde(
+        rename = "@model",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub model: Option<Cow<'a, str>>,
+
+    /// Account alias
+    #[serde(
+        rename = "@acctAlias",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub acct_alias: Option<Cow<'a, str>>,
+
+    /// Vesting date (for restricted stock)
+    #[serde(
+        rename = "@vestingDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub vesting_date: Option<NaiveDate>,
+}
+
+impl PositionRef<'_> {
+    /// Convert into the owned [`Position`]
+    pub fn into_owned(self) -> Position {
+        Position {
+            account_id: self.account_id.into_owned(),
+            conid: self.conid.into_owned(),
+            symbol: self.symbol.into_owned(),
+            description: self.description.map(Cow::into_owned),
+            asset_category: self.asset_category,
+            cusip: self.cusip.map(Cow::into_owned),
+            isin: self.isin.map(Cow::into_owned),
+            figi: self.figi.map(Cow::into_owned),
+            security_id: self.security_id.map(Cow::into_owned),
+            security_id_type: self.security_id_type,
+            multiplier: self.multiplier,
+            strike: self.strike,
+            expiry: self.expiry,
+            put_call: self.put_call,
+            underlying_conid: self.underlying_conid.map(Cow::into_owned),
+            underlying_symbol: self.underlying_symbol.map(Cow::into_owned),
+            quantity: self.quantity,
+            mark_price: self.mark_price,
+            position_value: self.position_value,
+            side: self.side.map(Cow::into_owned),
+            open_price: self.open_price,
+            cost_basis_price: self.cost_basis_price,
+            cost_basis_money: self.cost_basis_money,
+            fifo_pnl_unrealized: self.fifo_pnl_unrealized,
+            percent_of_nav: self.percent_of_nav,
+            currency: self.currency.into_owned(),
+            fx_rate_to_base: self.fx_rate_to_base,
+            report_date: self.report_date,
+            holding_period_date_time: self.holding_period_date_time.map(Cow::into_owned),
+            open_date_time: self.open_date_time.map(Cow::into_owned),
+            originating_transaction_id: self.originating_transaction_id.map(Cow::into_owned),
+            code: self.code.map(Cow::into_owned),
+            originating_order_id: self.originating_order_id.map(Cow::into_owned),
+            issuer: self.issuer.map(Cow::into_owned),
+            issuer_country_code: self.issuer_country_code.map(Cow::into_owned),
+            sub_category: self.sub_category,
+            listing_exchange: self.listing_exchange.map(Cow::into_owned),
+            underlying_listing_exchange: self.underlying_listing_exchange.map(Cow::into_owned),
+            underlying_security_id: self.underlying_security_id.map(Cow::into_owned),
+            accrued_int: self.accrued_int,
+            principal_adjust_factor: self.principal_adjust_factor,
+            serial_number: self.serial_number.map(Cow::into_owned),
+            delivery_type: self.delivery_type.map(Cow::into_owned),
+            commodity_type: self.commodity_type.map(Cow::into_owned),
+            fineness: self.fineness,
+            weight: self.weight.map(Cow::into_owned),
+            level_of_detail: self.level_of_detail,
+            model: self.model.map(Cow::into_owned),
+            acct_alias: self.acct_alias.map(Cow::into_owned),
+            vesting_date: self.vesting_date,
+            extra_attributes: BTreeMap::new(),
+        }
+    }
+}
+
+/// Borrowed [`CashTransaction`]
+///
+/// Text fields borrow from the input XML unless they contain escaped
+/// characters. Unmodeled attributes are not kept.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CashTransactionRef<'a> {
+    // ==================== CORE FIELDS ====================
+    // Essential for tax reporting and portfolio analytics
+
+    // --- Account ---
+    /// IB account number
+    #[serde(rename = "@accountId", borrow)]
+    pub account_id: Cow<'a, str>,
+
+    /// IB transaction ID
+    #[serde(
+        rename = "@transactionID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub transaction_id: Option<Cow<'a, str>>,
+
+    // --- Transaction Details ---
+    /// Transaction type (Deposits, Dividends, WithholdingTax, BrokerInterest, etc.)
+    #[serde(rename = "@type", default)]
+    pub transaction_type: Option<super::common::CashTransactionType>,
+
+    /// Description of transaction
+    #[serde(
+        rename = "@description",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub description: Option<Cow<'a, str>>,
+
+    /// Amount (positive for credits, negative for debits)
+    #[serde(rename = "@amount", deserialize_with = "deserialize_decimal")]
+    pub amount: Decimal,
+
+    /// Currency
+    #[serde(rename = "@currency", borrow)]
+    pub currency: Cow<'a, str>,
+
+    /// FX rate to base currency
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    // --- Dates ---
+    /// Transaction date
+    #[serde(
+        rename = "@date",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub date: Option<NaiveDate>,
+
+    /// Settlement date
+    #[serde(
+        rename = "@settleDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub settle_date: Option<NaiveDate>,
+
+    /// Ex-dividend date (tax-critical for dividends)
+    #[serde(
+        rename = "@exDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub ex_date: Option<NaiveDate>,
+
+    // --- Security Identification ---
+    /// Related security's contract ID (for dividends)
+    #[serde(
+        rename = "@conid",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub conid: Option<Cow<'a, str>>,
+
+    /// Related security's symbol
+    #[serde(
+        rename = "@symbol",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub symbol: Option<Cow<'a, str>>,
+
+    /// Asset category
+    #[serde(rename = "@assetCategory", default)]
+    pub asset_category: Option<AssetCategory>,
+
+    /// CUSIP
+    #[serde(
+        rename = "@cusip",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub cusip: Option<Cow<'a, str>>,
+
+    /// ISIN
+    #[serde(
+        rename = "@isin",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub isin: Option<Cow<'a, str>>,
+
+    /// FIGI
+    #[serde(
+        rename = "@figi",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub figi: Option<Cow<'a, str>>,
+
+    /// Security ID
+    #[serde(
+        rename = "@securityID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub security_id: Option<Cow<'a, str>>,
+
+    /// Security ID type
+    #[serde(rename = "@securityIDType", default)]
+    pub security_id_type: Option<SecurityIdType>,
+
+    // --- Derivatives ---
+    /// Contract multiplier
+    #[serde(
+        rename = "@multiplier",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub multiplier: Option<Decimal>,
+
+    /// Strike price
+    #[serde(
+        rename = "@strike",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub strike: Option<Decimal>,
+
+    /// Expiry date
+    #[serde(
+        rename = "@expiry",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub expiry: Option<NaiveDate>,
+
+    /// Put or Call
+    #[serde(rename = "@putCall", default)]
+    pub put_call: Option<PutCall>,
+
+    /// Underlying contract ID
+    #[serde(
+        rename = "@underlyingConid",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub underlying_conid: Option<Cow<'a, str>>,
+
+    /// Underlying symbol
+    #[serde(
+        rename = "@underlyingSymbol",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub underlying_symbol: Option<Cow<'a, str>>,
+
+    /// Transaction code (tax-relevant codes)
+    #[serde(
+        rename = "@code",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub code: Option<Cow<'a, str>>,
+
+    // ==================== EXTENDED FIELDS ====================
+    // Metadata and less commonly used fields
+
+    // --- Timestamps ---
+    /// Transaction datetime
+    #[serde(
+        rename = "@dateTime",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub date_time: Option<Cow<'a, str>>,
+
+    /// Report date
+    #[serde(
+        rename = "@reportDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub report_date: Option<NaiveDate>,
+
+    /// Available for trading date
+    #[serde(
+        rename = "@availableForTradingDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub available_for_trading_date: Option<NaiveDate>,
+
+    // --- Extended IDs ---
+    /// Action ID
+    #[serde(
+        rename = "@actionID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub action_id: Option<Cow<'a, str>>,
+
+    /// Trade ID (for dividend/interest related to specific trade)
+    #[serde(
+        rename = "@tradeID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub trade_id: Option<Cow<'a, str>>,
+
+    /// Client reference
+    #[serde(
+        rename = "@clientReference",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub client_reference: Option<Cow<'a, str>>,
+
+    // --- Issuer/Security Metadata ---
+    /// Issuer
+    #[serde(
+        rename = "@issuer",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub issuer: Option<Cow<'a, str>>,
+
+    /// Issuer country code
+    #[serde(
+        rename = "@issuerCountryCode",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub issuer_country_code: Option<Cow<'a, str>>,
+
+    /// Sub-category
+    #[serde(rename = "@subCategory", default)]
+    pub sub_category: Option<SubCategory>,
+
+    /// Listing exchange
+    #[serde(
+        rename = "@listingExchange",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub listing_exchange: Option<Cow<'a, str>>,
+
+    // --- Underlying Extended ---
+    /// Underlying listing exchange
+    #[serde(
+        rename = "@underlyingListingExchange",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub underlying_listing_exchange: Option<Cow<'a, str>>,
+
+    /// Underlying security ID
+    #[serde(
+        rename = "@underlyingSecurityID",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub underlying_security_id: Option<Cow<'a, str>>,
+
+    // --- Bond Fields ---
+    /// Principal adjust factor
+    #[serde(
+        rename = "@principalAdjustFactor",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub principal_adjust_factor: Option<Decimal>,
+
+    // --- Commodity/Physical Delivery ---
+    /// Serial number
+    #[serde(
+        rename = "@serialNumber",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub serial_number: Option<Cow<'a, str>>,
+
+    /// Delivery type
+    #[serde(
+        rename = "@deliveryType",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub delivery_type: Option<Cow<'a, str>>,
+
+    /// Commodity type
+    #[serde(
+        rename = "@commodityType",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub commodity_type: Option<Cow<'a, str>>,
+
+    /// Fineness
+    #[serde(
+        rename = "@fineness",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fineness: Option<Decimal>,
+
+    /// Weight
+    #[serde(
+        rename = "@weight",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub weight: Option<Cow<'a, str>>,
+
+    // --- Other Metadata ---
+    /// Level of detail
+    #[serde(
+        rename = "@levelOfDetail",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub level_of_detail: Option<Cow<'a, str>>,
+
+    /// Model
+    #[serde(
+        rename = "@model",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub model: Option<Cow<'a, str>>,
+
+    /// Account alias
+    #[serde(
+        rename = "@acctAlias",
+        default,
+        borrow,
+        deserialize_with = "deserialize_optional_cow"
+    )]
+    pub acct_alias: Option<Cow<'a, str>>,
+}
+
+impl CashTransactionRef<'_> {
+    /// Convert into the owned [`CashTransaction`]
+    pub fn into_owned(self) -> CashTransaction {
+        CashTransaction {
+            account_id: self.account_id.into_owned(),
+            transaction_id: self.transaction_id.map(Cow::into_owned),
+            transaction_type: self.transaction_type,
+            description: self.description.map(Cow::into_owned),
+            amount: self.amount,
+            currency: self.currency.into_owned(),
+            fx_rate_to_base: self.fx_rate_to_base,
+            date: self.date,
+            settle_date: self.settle_date,
+            ex_date: self.ex_date,
+            conid: self.conid.map(Cow::into_owned),
+            symbol: self.symbol.map(Cow::into_owned),
+            asset_category: self.asset_category,
+            cusip: self.cusip.map(Cow::into_owned),
+            isin: self.isin.map(Cow::into_owned),
+            figi: self.figi.map(Cow::into_owned),
+            security_id: self.security_id.map(Cow::into_owned),
+            security_id_type: self.security_id_type,
+            multiplier: self.multiplier,
+            strike: self.strike,
+            expiry: self.expiry,
+            put_call: self.put_call,
+            underlying_conid: self.underlying_conid.map(Cow::into_owned),
+            underlying_symbol: self.underlying_symbol.map(Cow::into_owned),
+            code: self.code.map(Cow::into_owned),
+            date_time: self.date_time.map(Cow::into_owned),
+            report_date: self.report_date,
+            available_for_trading_date: self.available_for_trading_date,
+            action_id: self.action_id.map(Cow::into_owned),
+            trade_id: self.trade_id.map(Cow::into_owned),
+            client_reference: self.client_reference.map(Cow::into_owned),
+            issuer: self.issuer.map(Cow::into_owned),
+            issuer_country_code: self.issuer_country_code.map(Cow::into_owned),
+            sub_category: self.sub_category,
+            listing_exchange: self.listing_exchange.map(Cow::into_owned),
+            underlying_listing_exchange: self.underlying_listing_exchange.map(Cow::into_owned),
+            underlying_security_id: self.underlying_security_id.map(Cow::into_owned),
+            principal_adjust_factor: self.principal_adjust_factor,
+            serial_number: self.serial_number.map(Cow::into_owned),
+            delivery_type: self.delivery_type.map(Cow::into_owned),
+            commodity_type: self.commodity_type.map(Cow::into_owned),
+            fineness: self.fineness,
+            weight: self.weight.map(Cow::into_owned),
+            level_of_detail: self.level_of_detail.map(Cow::into_owned),
+            model: self.model.map(Cow::into_owned),
+            acct_alias: self.acct_alias.map(Cow::into_owned),
+            extra_attributes: BTreeMap::new(),
+        }
+    }
+}
@@ -1,956 +1,892 @@
 //! Common enums used across FLEX statements
 
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::parsers::inventory;
 
-/// Asset category (security type)
+/// Define a FLEX enum with an added `Unknown(String)` variant for
+/// unrecognized values
 ///
-/// Maps to IB's AssetCategory field. Represents the type of financial instrument.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
-#[serde(rename_all = "UPPERCASE")]
-pub enum AssetCategory {
-    /// Stock
-    #[serde(rename = "STK")]
-    Stock,
-
-    /// Option
-    #[serde(rename = "OPT")]
-    Option,
-
-    /// Future
-    #[serde(rename = "FUT")]
-    Future,
-
-    /// Future Option
-    #[serde(rename = "FOP")]
-    FutureOption,
-
-    /// Cash/Forex
-    #[serde(rename = "CASH")]
-    Cash,
-
-    /// Bond
-    #[serde(rename = "BOND")]
-    Bond,
-
-    /// Treasury Bill (maturity < 1 year)
-    #[serde(rename = "BILL")]
-    Bill,
-
-    /// Commodity
-    #[serde(rename = "CMDTY")]
-    Commodity,
-
-    /// Contract for Difference
-    #[serde(rename = "CFD")]
-    Cfd,
-
-    /// Forex CFD
-    #[serde(rename = "FXCFD")]
-    ForexCfd,
+/// Each variant maps to its XML value and optional aliases. Serialization
+/// writes the XML value (or the raw `Unknown` string), so values round-trip.
+/// Unknown values seen while a statement is parsed are recorded in
+/// [`ActivityFlexStatement::unknown_values`](crate::types::ActivityFlexStatement::unknown_values).
+macro_rules! flex_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident => $value:literal $(| $alias:literal)*,
+            )+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )+
+            /// Value not recognized by this crate, as it appeared in the XML
+            Unknown(String),
+        }
+
+        impl $name {
+            /// Value as it appears in FLEX XML
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)+
+                    $name::Unknown(raw) => raw,
+                }
+            }
+
+            /// Whether the value was not recognized
+            pub fn is_unknown(&self) -> bool {
+                matches!(self, $name::Unknown(_))
+            }
+
+            fn from_known(value: &str) -> Option<Self> {
+                match value {
+                    $($value $(| $alias)* => Some($name::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Infallible;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok($name::from_known(s).unwrap_or_else(|| $name::Unknown(s.to_string())))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct Visitor;
+
+                impl de::Visitor<'_> for Visitor {
+                    type Value = $name;
+
+                    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        f.write_str(concat!("a ", stringify!($name), " value"))
+                    }
+
+                    fn visit_str<E: de::Error>(self, v: &str) -> Result<$name, E> {
+                        Ok($name::from_known(v).unwrap_or_else(|| {
+                            inventory::record(stringify!($name), v);
+                            $name::Unknown(v.to_string())
+                        }))
+                    }
+                }
+
+                deserializer.deserialize_str(Visitor)
+            }
+        }
+    };
+}
 
-    /// Warrant
-    #[serde(rename = "WAR")]
-    Warrant,
+/// An enum value this crate does not recognize
+///
+/// Collected per statement in
+/// [`ActivityFlexStatement::unknown_values`](crate::types::ActivityFlexStatement::unknown_values)
+/// so that new IB values can be reported upstream.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnknownValue {
+    /// Enum type name (e.g. `CashTransactionType`)
+    pub enum_name: &'static str,
+
+    /// Raw value as it appeared in the XML
+    pub value: String,
+
+    /// Number of times the value was seen
+    pub occurrences: usize,
+}
 
-    /// Mutual Fund
-    #[serde(rename = "FUND")]
-    Fund,
+flex_enum! {
+    /// Asset category (security type)
+    ///
+    /// Maps to IB's AssetCategory field. Represents the type of financial instrument.
+    pub enum AssetCategory {
+        /// Stock
+        Stock => "STK",
 
-    /// Structured Product / Dutch Warrant / Indexed Option
-    #[serde(rename = "IOPT")]
-    StructuredProduct,
+        /// Option
+        Option => "OPT",
 
-    /// Combination / Basket order (spread, combo legs)
-    #[serde(rename = "BAG")]
-    Bag,
+        /// Future
+        Future => "FUT",
 
-    /// Cryptocurrency
-    #[serde(rename = "CRYPTO")]
-    Cryptocurrency,
+        /// Future Option
+        FutureOption => "FOP",
 
-    /// Physical metals (gold, silver, etc.)
-    #[serde(rename = "METAL")]
-    Metal,
+        /// Cash/Forex
+        Cash => "CASH",
 
-    /// Exchange for Physical
-    #[serde(rename = "EFP")]
-    ExchangeForPhysical,
+        /// Bond
+        Bond => "BOND",
 
-    /// Event Contract
-    #[serde(rename = "EC")]
-    EventContract,
+        /// Treasury Bill (maturity < 1 year)
+        Bill => "BILL",
 
-    /// Index
-    #[serde(rename = "IND")]
-    Index,
+        /// Commodity
+        Commodity => "CMDTY",
 
-    /// Unknown or unrecognized asset category
-    #[serde(other)]
-    Unknown,
-}
+        /// Contract for Difference
+        Cfd => "CFD",
 
-/// Buy or Sell side
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
-#[serde(rename_all = "UPPERCASE")]
-pub enum BuySell {
-    /// Buy
-    #[serde(rename = "BUY")]
-    Buy,
-
-    /// Sell
-    #[serde(rename = "SELL")]
-    Sell,
-
-    /// Cancelled buy
-    #[serde(rename = "BUY (Ca.)")]
-    CancelBuy,
-
-    /// Cancelled sell
-    #[serde(rename = "SELL (Ca.)")]
-    CancelSell,
-
-    /// Unknown
-    #[serde(other)]
-    Unknown,
-}
+        /// Forex CFD
+        ForexCfd => "FXCFD",
+
+        /// Warrant
+        Warrant => "WAR",
+
+        /// Mutual Fund
+        Fund => "FUND",
+
+        /// Structured Product / Dutch Warrant / Indexed Option
+        StructuredProduct => "IOPT",
 
-/// Open or Close indicator (for options/futures)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub enum OpenClose {
-    /// Opening trade
-    #[serde(rename = "O")]
-    Open,
+        /// Combination / Basket order (spread, combo legs)
+        Bag => "BAG",
 
-    /// Closing trade
-    #[serde(rename = "C")]
-    Close,
+        /// Cryptocurrency
+        Cryptocurrency => "CRYPTO",
 
-    /// Close and open (same-day round trip)
-    #[serde(rename = "C;O")]
-    CloseOpen,
+        /// Physical metals (gold, silver, etc.)
+        Metal => "METAL",
 
-    /// Unknown
-    #[serde(other)]
-    Unknown,
+        /// Exchange for Physical
+        ExchangeForPhysical => "EFP",
+
+        /// Event Contract
+        EventContract => "EC",
+
+        /// Index
+        Index => "IND",
+    }
 }
 
-/// Order type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
-#[serde(rename_all = "UPPERCASE")]
-pub enum OrderType {
-    /// Market order
-    #[serde(rename = "MKT")]
-    Market,
-
-    /// Limit order
-    #[serde(rename = "LMT")]
-    Limit,
-
-    /// Stop order
-    #[serde(rename = "STP")]
-    Stop,
-
-    /// Stop limit order
-    #[serde(rename = "STP LMT")]
-    StopLimit,
-
-    /// Market on close
-    #[serde(rename = "MOC")]
-    MarketOnClose,
-
-    /// Limit on close
-    #[serde(rename = "LOC")]
-    LimitOnClose,
-
-    /// Market if touched
-    #[serde(rename = "MIT")]
-    MarketIfTouched,
-
-    /// Limit if touched
-    #[serde(rename = "LIT")]
-    LimitIfTouched,
-
-    /// Trailing stop
-    #[serde(rename = "TRAIL")]
-    TrailingStop,
-
-    /// Trailing limit
-    #[serde(rename = "TRAIL LMT")]
-    TrailingLimit,
-
-    /// Mid-price order
-    #[serde(rename = "MIDPX")]
-    MidPrice,
-
-    /// Relative order
-    #[serde(rename = "REL")]
-    Relative,
-
-    /// Multiple order types (complex orders)
-    #[serde(rename = "MULTIPLE")]
-    Multiple,
-
-    /// Unknown or unrecognized order type
-    #[serde(other)]
-    Unknown,
+flex_enum! {
+    /// Buy or Sell side
+    pub enum BuySell {
+        /// Buy
+        Buy => "BUY",
+
+        /// Sell
+        Sell => "SELL",
+
+        /// Cancelled buy
+        CancelBuy => "BUY (Ca.)",
+
+        /// Cancelled sell
+        CancelSell => "SELL (Ca.)",
+    }
 }
 
-/// Put or Call (for options)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub enum PutCall {
-    /// Put option
-    #[serde(rename = "P")]
-    Put,
+flex_enum! {
+    /// Open or Close indicator (for options/futures)
+    pub enum OpenClose {
+        /// Opening trade
+        Open => "O",
 
-    /// Call option
-    #[serde(rename = "C")]
-    Call,
+        /// Closing trade
+        Close => "C",
 
-    /// Unknown
-    #[serde(other)]
-    Unknown,
+        /// Close and open (same-day round trip)
+        CloseOpen => "C;O",
+    }
 }
 
-/// Long or Short position side
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub enum LongShort {
-    /// Long position
-    Long,
+flex_enum! {
+    /// Order type
+    pub enum OrderType {
+        /// Market order
+        Market => "MKT",
+
+        /// Limit order
+        Limit => "LMT",
+
+        /// Stop order
+        Stop => "STP",
+
+        /// Stop limit order
+        StopLimit => "STP LMT",
 
-    /// Short position
-    Short,
+        /// Market on close
+        MarketOnClose => "MOC",
 
-    /// Unknown
-    #[serde(other)]
-    Unknown,
+        /// Limit on close
+        LimitOnClose => "LOC",
+
+        /// Market if touched
+        MarketIfTouched => "MIT",
+
+        /// Limit if touched
+        LimitIfTouched => "LIT",
+
+        /// Trailing stop
+        TrailingStop => "TRAIL",
+
+        /// Trailing limit
+        TrailingLimit => "TRAIL LMT",
+
+        /// Mid-price order
+        MidPrice => "MIDPX",
+
+        /// Relative order
+        Relative => "REL",
+
+        /// Multiple order types (complex orders)
+        Multiple => "MULTIPLE",
+    }
 }
 
-/// Transaction type for trades
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub enum TradeType {
-    /// Exchange trade
-    ExchTrade,
+flex_enum! {
+    /// Put or Call (for options)
+    pub enum PutCall {
+        /// Put option
+        Put => "P",
 
-    /// Book trade
-    BookTrade,
+        /// Call option
+        Call => "C",
+    }
+}
 
-    /// Delivery vs Payment trade
-    DvpTrade,
+flex_enum! {
+    /// Long or Short position side
+    pub enum LongShort {
+        /// Long position
+        Long => "Long",
 
-    /// Fractional share trade
-    FracShare,
+        /// Short position
+        Short => "Short",
+    }
+}
 
-    /// Fractional share cancellation
-    FracShareCancel,
+flex_enum! {
+    /// Transaction type for trades
+    pub enum TradeType {
+        /// Exchange trade
+        ExchTrade => "ExchTrade",
 
-    /// Manual adjustment
-    Adjustment,
+        /// Book trade
+        BookTrade => "BookTrade",
 
-    /// Trade correction
-    TradeCorrect,
+        /// Delivery vs Payment trade
+        DvpTrade => "DvpTrade",
 
-    /// Trade cancellation
-    TradeCancel,
+        /// Fractional share trade
+        FracShare => "FracShare",
 
-    /// IBKR trade
-    IBKRTrade,
+        /// Fractional share cancellation
+        FracShareCancel => "FracShareCancel",
 
-    /// Unknown
-    #[serde(other)]
-    Unknown,
+        /// Manual adjustment
+        Adjustment => "Adjustment",
+
+        /// Trade correction
+        TradeCorrect => "TradeCorrect",
+
+        /// Trade cancellation
+        TradeCancel => "TradeCancel",
+
+        /// IBKR trade
+        IBKRTrade => "IBKRTrade",
+    }
 }
 
-/// Cash transaction action type
-///
-/// Represents the type of cash transaction (dividend, interest, fee, etc.).
-/// This enum is used by the `CashTransaction` struct to classify cash activity
-/// in the account statement.
-///
-/// **XML Mapping**: Maps to the `type` attribute in `<CashTransaction>` elements.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub enum CashTransactionType {
-    /// Deposits and withdrawals
-    #[serde(rename = "Deposits & Withdrawals")]
-    DepositsWithdrawals,
-
-    /// Dividend payments
-    Dividends,
-
-    /// Withholding tax
-    #[serde(rename = "Withholding Tax")]
-    WithholdingTax,
-
-    /// Broker interest paid
-    #[serde(rename = "Broker Interest Paid")]
-    BrokerInterestPaid,
-
-    /// Broker interest received
-    #[serde(rename = "Broker Interest Received")]
-    BrokerInterestReceived,
-
-    /// Bond interest received
-    #[serde(rename = "Bond Interest Received")]
-    BondInterestReceived,
-
-    /// Bond interest paid
-    #[serde(rename = "Bond Interest Paid")]
-    BondInterestPaid,
-
-    /// Bond interest (generic)
-    #[serde(rename = "Bond Interest")]
-    BondInterest,
-
-    /// Payment in lieu of dividends
-    #[serde(rename = "Payment In Lieu Of Dividends")]
-    PaymentInLieuOfDividends,
-
-    /// Other fees
-    #[serde(rename = "Other Fees")]
-    OtherFees,
-
-    /// Commission adjustments
-    #[serde(rename = "Commission Adjustments")]
-    CommissionAdjustments,
-
-    /// Advisor fees
-    #[serde(rename = "Advisor Fees")]
-    AdvisorFees,
-
-    /// Cash receipts
-    #[serde(rename = "Cash Receipts")]
-    CashReceipts,
-
-    /// Fees
-    Fees,
-
-    /// Unknown type
-    #[serde(other)]
-    Unknown,
+flex_enum! {
+    /// Cash transaction action type
+    ///
+    /// Represents the type of cash transaction (dividend, interest, fee, etc.).
+    /// This enum is used by the `CashTransaction` struct to classify cash activity
+    /// in the account statement.
+    ///
+    /// **XML Mapping**: Maps to the `type` attribute in `<CashTransaction>` elements.
+    pub enum CashTransactionType {
+        /// Deposits and withdrawals
+        DepositsWithdrawals => "Deposits & Withdrawals" | "Deposits/Withdrawals",
+
+        /// Dividend payments
+        Dividends => "Dividends",
+
+        /// Withholding tax
+        WithholdingTax => "Withholding Tax",
+
+        /// Broker interest paid
+        BrokerInterestPaid => "Broker Interest Paid",
+
+        /// Broker interest received
+        BrokerInterestReceived => "Broker Interest Received",
+
+        /// Bond interest received
+        BondInterestReceived => "Bond Interest Received",
+
+        /// Bond interest paid
+        BondInterestPaid => "Bond Interest Paid",
+
+        /// Bond interest (generic)
+        BondInterest => "Bond Interest",
+
+        /// Payment in lieu of dividends
+        PaymentInLieuOfDividends => "Payment In Lieu Of Dividends",
+
+        /// Other fees
+        OtherFees => "Other Fees",
+
+        /// Commission adjustments
+        CommissionAdjustments => "Commission Adjustments",
+
+        /// Advisor fees
+        AdvisorFees => "Advisor Fees",
+
+        /// Cash receipts
+        CashReceipts => "Cash Receipts",
+
+        /// Fees
+        Fees => "Fees",
+    }
 }
 
-/// Corporate action reorganization type
-///
-/// Represents the type of corporate action (split, merger, spinoff, etc.).
-/// This enum is used by the `CorporateAction` struct to classify corporate events
-/// that affect security positions and holdings.
-///
-/// **XML Mapping**: Maps to the `type` attribute in `<CorporateAction>` elements.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub enum CorporateActionType {
-    /// Stock split (forward split)
-    #[serde(rename = "FS", alias = "Stock Split")]
-    StockSplit,
+flex_enum! {
+    /// Corporate action reorganization type
+    ///
+    /// Represents the type of corporate action (split, merger, spinoff, etc.).
+    /// This enum is used by the `CorporateAction` struct to classify corporate events
+    /// that affect security positions and holdings.
+    ///
+    /// **XML Mapping**: Maps to the `type` attribute in `<CorporateAction>` elements.
+    pub enum CorporateActionType {
+        /// Stock split (forward split)
+        StockSplit => "FS" | "Stock Split",
+
+        /// Forward split (issue)
+        ForwardSplitIssue => "Forward Split (Issue)",
+
+        /// Forward split
+        ForwardSplit => "Forward Split",
+
+        /// Reverse split
+        ReverseSplit => "RS" | "Reverse Split",
+
+        /// Merger
+        Merger => "TC" | "Merger",
+
+        /// Spinoff
+        Spinoff => "SO" | "Spinoff",
+
+        /// Contract spinoff
+        ContractSpinoff => "Contract Spinoff",
+
+        /// Stock dividend
+        StockDividend => "SD" | "Stock Dividend",
+
+        /// Cash dividend
+        CashDividend => "DI" | "Cash Dividend",
 
-    /// Forward split (issue)
-    #[serde(rename = "Forward Split (Issue)")]
-    ForwardSplitIssue,
+        /// Choice dividend
+        ChoiceDividend => "CD" | "Choice Dividend",
 
-    /// Forward split
-    #[serde(rename = "Forward Split")]
-    ForwardSplit,
+        /// Choice dividend (delivery)
+        ChoiceDivDelivery => "Choice Dividend (Delivery)",
 
-    /// Reverse split
-    #[serde(rename = "RS", alias = "Reverse Split")]
-    ReverseSplit,
+        /// Choice dividend (issue)
+        ChoiceDivIssue => "Choice Dividend (Issue)",
 
-    /// Merger
-    #[serde(rename = "TC", alias = "Merger")]
-    Merger,
+        /// Dividend rights issue
+        DivRightsIssue => "Dividend Rights Issue",
 
-    /// Spinoff
-    #[serde(rename = "SO", alias = "Spinoff")]
-    Spinoff,
+        /// Expired dividend right
+        ExpiredDivRight => "Expired Dividend Right",
 
-    /// Contract spinoff
-    #[serde(rename = "Contract Spinoff")]
-    ContractSpinoff,
+        /// Delisted
+        Delisted => "DL" | "Delisted",
 
-    /// Stock dividend
-    #[serde(rename = "SD", alias = "Stock Dividend")]
-    StockDividend,
+        /// Delist (worthless)
+        DelistWorthless => "DW" | "Delist (Worthless)",
 
-    /// Cash dividend
-    #[serde(rename = "DI", alias = "Cash Dividend")]
-    CashDividend,
+        /// Name change
+        NameChange => "Name Change",
 
-    /// Choice dividend
-    #[serde(rename = "CD", alias = "Choice Dividend")]
-    ChoiceDividend,
+        /// Symbol change
+        SymbolChange => "Symbol Change",
 
-    /// Choice dividend (delivery)
-    #[serde(rename = "Choice Dividend (Delivery)")]
-    ChoiceDivDelivery,
+        /// Issue change
+        IssueChange => "Issue Change",
 
-    /// Choice dividend (issue)
-    #[serde(rename = "Choice Dividend (Issue)")]
-    ChoiceDivIssue,
-
-    /// Dividend rights issue
-    #[serde(rename = "Dividend Rights Issue")]
-    DivRightsIssue,
-
-    /// Expired dividend right
-    #[serde(rename = "Expired Dividend Right")]
-    ExpiredDivRight,
-
-    /// Delisted
-    #[serde(rename = "DL", alias = "Delisted")]
-    Delisted,
-
-    /// Delist (worthless)
-    #[serde(rename = "DW", alias = "Delist (Worthless)")]
-    DelistWorthless,
-
-    /// Name change
-    #[serde(rename = "Name Change")]
-    NameChange,
-
-    /// Symbol change
-    #[serde(rename = "Symbol Change")]
-    SymbolChange,
-
-    /// Issue change
-    #[serde(rename = "Issue Change")]
-    IssueChange,
-
-    /// Bond conversion
-    #[serde(rename = "BC", alias = "Bond Conversion")]
-    BondConversion,
-
-    /// Bond maturity
-    #[serde(rename = "BM", alias = "Bond Maturity")]
-    BondMaturity,
-
-    /// T-Bill maturity
-    #[serde(rename = "T-Bill Maturity")]
-    TBillMaturity,
-
-    /// Convertible issue
-    #[serde(rename = "Convertible Issue")]
-    ConvertibleIssue,
-
-    /// Coupon payment
-    #[serde(rename = "CP", alias = "Coupon Payment")]
-    CouponPayment,
-
-    /// Contract consolidation
-    #[serde(rename = "Contract Consolidation")]
-    ContractConsolidation,
-
-    /// Contract split
-    #[serde(rename = "Contract Split")]
-    ContractSplit,
+        /// Bond conversion
+        BondConversion => "BC" | "Bond Conversion",
 
-    /// Contract termination
-    #[serde(rename = "CFD Termination")]
-    CfdTermination,
+        /// Bond maturity
+        BondMaturity => "BM" | "Bond Maturity",
 
-    /// Fee allocation
-    #[serde(rename = "Fee Allocation")]
-    FeeAllocation,
+        /// T-Bill maturity
+        TBillMaturity => "T-Bill Maturity",
 
-    /// Rights issue
-    #[serde(rename = "RI", alias = "Rights Issue")]
-    RightsIssue,
+        /// Convertible issue
+        ConvertibleIssue => "Convertible Issue",
 
-    /// Subscribe rights
-    #[serde(rename = "SR", alias = "Subscribe Rights")]
-    SubscribeRights,
+        /// Coupon payment
+        CouponPayment => "CP" | "Coupon Payment",
 
-    /// Tender
-    #[serde(rename = "TO", alias = "Tender")]
-    Tender,
+        /// Contract consolidation
+        ContractConsolidation => "Contract Consolidation",
 
-    /// Tender (issue)
-    #[serde(rename = "Tender (Issue)")]
-    TenderIssue,
+        /// Contract split
+        ContractSplit => "Contract Split",
 
-    /// Proxy vote
-    #[serde(rename = "Proxy Vote")]
-    ProxyVote,
+        /// Contract termination
+        CfdTermination => "CFD Termination",
 
-    /// Generic voluntary
-    #[serde(rename = "Generic Voluntary")]
-    GenericVoluntary,
+        /// Fee allocation
+        FeeAllocation => "Fee Allocation",
 
-    /// Asset purchase
-    #[serde(rename = "Asset Purchase")]
-    AssetPurchase,
+        /// Rights issue
+        RightsIssue => "RI" | "Rights Issue",
 
-    /// Purchase (issue)
-    #[serde(rename = "Purchase (Issue)")]
-    PurchaseIssue,
+        /// Subscribe rights
+        SubscribeRights => "SR" | "Subscribe Rights",
 
-    /// Unknown
-    #[serde(other)]
-    Unknown,
+        /// Tender
+        Tender => "TO" | "Tender",
+
+        /// Tender (issue)
+        TenderIssue => "Tender (Issue)",
+
+        /// Proxy vote
+        ProxyVote => "Proxy Vote",
+
+        /// Generic voluntary
+        GenericVoluntary => "Generic Voluntary",
+
+        /// Asset purchase
+        AssetPurchase => "Asset Purchase",
+
+        /// Purchase (issue)
+        PurchaseIssue => "Purchase (Issue)",
+    }
 }
 
-/// Option action type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub enum OptionAction {
-    /// Assignment
-    Assignment,
+flex_enum! {
+    /// Option action type
+    pub enum OptionAction {
+        /// Assignment
+        Assignment => "Assignment",
 
-    /// Exercise
-    Exercise,
+        /// Exercise
+        Exercise => "Exercise",
 
-    /// Expiration
-    Expiration,
+        /// Expiration
+        Expiration => "Expiration",
+
+        /// Expire (alternate form)
+        Expire => "Expire",
+
+        /// Cash settlement
+        CashSettlement => "Cash Settlement",
+
+        /// Buy to open/close
+        Buy => "Buy",
+
+        /// Sell to open/close
+        Sell => "Sell",
+    }
+}
 
-    /// Expire (alternate form)
-    Expire,
+flex_enum! {
+    /// Transfer type
+    pub enum TransferType {
+        /// ACATS transfer
+        ACATS => "ACATS",
 
-    /// Cash settlement
-    #[serde(rename = "Cash Settlement")]
-    CashSettlement,
+        /// ATON transfer
+        ATON => "ATON",
 
-    /// Buy to open/close
-    Buy,
+        /// Free of payment
+        FOP => "FOP",
 
-    /// Sell to open/close
-    Sell,
+        /// Internal transfer
+        INTERNAL => "INTERNAL",
 
-    /// Unknown
-    #[serde(other)]
-    Unknown,
+        /// Delivery vs payment
+        DVP => "DVP",
+
+        /// Direct registration
+        DRS => "DRS",
+    }
 }
 
-/// Transfer type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub enum TransferType {
-    /// ACATS transfer
-    ACATS,
+flex_enum! {
+    /// Transaction code
+    ///
+    /// Comprehensive list of IB transaction classification codes.
+    /// These codes appear in `notes` fields and can be combined (e.g., "C;W" for closing + wash sale).
+    /// They provide critical context for tax reporting and trade classification.
+    pub enum TransactionCode {
+        /// Assignment - Option assignment triggering stock delivery
+        Assignment => "A",
+
+        /// Adjustment - Manual adjustment affecting cost basis
+        Adjustment => "Adj",
+
+        /// Allocation - Trade allocation to sub-account (master/sub allocation)
+        Allocation => "Al",
+
+        /// Auto Exercise - Automatic exercise (dividend-related, exercise before ex-div)
+        AutoExercise => "Ae",
+
+        /// Auto FX - AutoFX currency conversion for settlement
+        AutoFx => "Af",
+
+        /// Away Trade - Trade executed away from IB (third-party execution)
+        AwayTrade => "Aw",
+
+        /// Buy-In - Forced purchase to cover failed delivery (forced short cover)
+        BuyIn => "B",
+
+        /// Borrow - Securities borrowing fee (lending charge)
+        BorrowFee => "Bo",
+
+        /// Cancellation - Trade cancelled/busted (trade reversed)
+        Cancelled => "Ca",
 
-    /// ATON transfer
-    ATON,
+        /// Closing - Closing trade (reduces position)
+        Closing => "C",
 
-    /// Free of payment
-    FOP,
+        /// Cash Delivery - Cash delivery for exercise (cash vs physical)
+        CashDelivery => "Cd",
 
-    /// Internal transfer
-    INTERNAL,
+        /// Complex Position - Complex/combo position (multi-leg strategy)
+        ComplexPosition => "Cp",
 
-    /// Delivery vs payment
-    DVP,
+        /// Correction - Trade correction (amended execution)
+        Correction => "Cr",
 
-    /// Direct registration
-    DRS,
+        /// Crossing - Internal IB cross (matched internally)
+        Crossing => "Cs",
 
-    /// Unknown
-    #[serde(other)]
-    Unknown,
+        /// Dual Agent - IB dual agent capacity (disclosed dual role)
+        DualAgent => "D",
+
+        /// ETF - ETF creation/redemption (in-kind basket)
+        Etf => "Et",
+
+        /// Expired Position - Resulting from an expired position
+        ExpiredPosition => "Ep",
+
+        /// Exercise - Option exercise (long option exercised)
+        /// Source: https://guides.interactivebrokers.com/reportingguide/reportguide/activity_flex_query_notes.htm
+        Exercise => "Ex",
+
+        /// Opening - Opening trade (new position)
+        /// Source: https://guides.interactivebrokers.com/reportingguide/reportguide/activity_flex_query_notes.htm
+        Opening => "O",
+
+        /// Guaranteed - Guaranteed account segment (special margin)
+        Guaranteed => "G",
+
+        /// Highest Cost - Highest cost tax lot (tax lot selection)
+        HighestCost => "Hc",
+
+        /// HF Investment - Hedge fund investment (fund subscription)
+        HfInvestment => "Hi",
+
+        /// HF Redemption - Hedge fund redemption (fund redemption)
+        HfRedemption => "Hr",
+
+        /// Internal - Internal transfer (between IB accounts)
+        InternalTransfer => "I",
+
+        /// Affiliate - Affiliate execution (related party trade)
+        Affiliate => "Ia" | "IA",
+
+        /// Investor - Investment from investor (capital contribution)
+        Investor => "Iv",
+
+        /// Margin Violation - Liquidation due to margin (forced liquidation)
+        MarginLiquidation => "L",
+
+        /// LIFO - LIFO tax lot (tax lot selection)
+        Lifo => "Li",
+
+        /// Loan - Securities lending income (lending income)
+        Loan => "Ln",
+
+        /// Long-Term - Long-term gain/loss (holding > 1 year)
+        LongTermGain => "Lt",
+
+        /// Manual - Manual IB entry (manual adjustment)
+        ManualEntry => "M",
+
+        /// Max Loss - Maximize losses (tax optimization)
+        MaxLoss => "Ml",
+
+        /// Min LT Gain - Minimize long-term gain (tax optimization)
+        MinLongTermGain => "Mn",
+
+        /// Max ST Gain - Maximize short-term gain (tax optimization)
+        MaxShortTermGain => "Ms",
+
+        /// Min ST Gain - Minimize short-term gain (tax optimization)
+        MinShortTermGain => "Mi",
+
+        /// Manual Exercise - Manual exercise (discretionary exercise)
+        ManualExercise => "Mx",
+
+        /// Partial - Partial execution (partial fill)
+        /// Source: https://guides.interactivebrokers.com/reportingguide/reportguide/activity_flex_query_notes.htm
+        Partial => "P" | "Pt",
+
+        /// Frac Riskless - Fractional riskless principal (fractional share method)
+        FracRiskless => "Fr",
+
+        /// Frac Principal - Fractional principal (fractional share method)
+        FracPrincipal => "Fp",
+
+        /// Price Improvement - Better than quoted (price improvement)
+        PriceImprovement => "Pi",
+
+        /// Post Accrual - Accrual posting (accrual entry)
+        PostAccrual => "Pa",
+
+        /// Principal - IB principal execution (principal trade)
+        Principal => "Pr",
+
+        /// Reinvestment - Dividend reinvestment (DRIP)
+        Reinvestment => "Re",
+
+        /// Redemption - Capital distribution (fund redemption)
+        Redemption => "Rd",
+
+        /// Reopen - Position reopened (wash sale reopen)
+        Reopen => "R",
+
+        /// Reverse - Accrual reversal (accounting reversal)
+        Reverse => "Rv",
+
+        /// Reimbursement - Fee refund (expense refund)
+        Reimbursement => "Ri",
+
+        /// Solicited IB - IB solicited order (IB-initiated)
+        SolicitedIb => "Si",
+
+        /// Specific Lot - Specific tax lot (tax lot selection)
+        SpecificLot => "Sp",
+
+        /// Solicited Other - Third-party solicited (broker-solicited)
+        SolicitedOther => "So",
+
+        /// Short Settlement - T+0 or T+1 settlement (accelerated settle)
+        ShortSettlement => "Ss",
+
+        /// Short-Term - Short-term gain/loss (holding <= 1 year)
+        ShortTermGain => "St",
+
+        /// Stock Yield - Stock yield eligible (lending eligible)
+        StockYield => "Sy",
+
+        /// Transfer - Position transfer
+        Transfer => "T",
+
+        /// Wash Sale - Wash sale (loss disallowed)
+        WashSale => "W",
+    }
 }
 
-/// Transaction code
-///
-/// Comprehensive list of IB transaction classification codes.
-/// These codes appear in `notes` fields and can be combined (e.g., "C;W" for closing + wash sale).
-/// They provide critical context for tax reporting and trade classification.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub enum TransactionCode {
-    /// Assignment - Option assignment triggering stock delivery
-    #[serde(rename = "A")]
-    Assignment,
-
-    /// Adjustment - Manual adjustment affecting cost basis
-    #[serde(rename = "Adj")]
-    Adjustment,
-
-    /// Allocation - Trade allocation to sub-account (master/sub allocation)
-    #[serde(rename = "Al")]
-    Allocation,
-
-    /// Auto Exercise - Automatic exercise (dividend-related, exercise before ex-div)
-    #[serde(rename = "Ae")]
-    AutoExercise,
-
-    /// Auto FX - AutoFX currency conversion for settlement
-    #[serde(rename = "Af")]
-    AutoFx,
-
-    /// Away Trade - Trade executed away from IB (third-party execution)
-    #[serde(rename = "Aw")]
-    AwayTrade,
-
-    /// Buy-In - Forced purchase to cover failed delivery (forced short cover)
-    #[serde(rename = "B")]
-    BuyIn,
-
-    /// Borrow - Securities borrowing fee (lending charge)
-    #[serde(rename = "Bo")]
-    BorrowFee,
-
-    /// Cancellation - Trade cancelled/busted (trade reversed)
-    #[serde(rename = "Ca")]
-    Cancelled,
-
-    /// Closing - Closing trade (reduces position)
-    #[serde(rename = "C")]
-    Closing,
-
-    /// Cash Delivery - Cash delivery for exercise (cash vs physical)
-    #[serde(rename = "Cd")]
-    CashDelivery,
-
-    /// Complex Position - Complex/combo position (multi-leg strategy)
-    #[serde(rename = "Cp")]
-    ComplexPosition,
-
-    /// Correction - Trade correction (amended execution)
-    #[serde(rename = "Cr")]
-    Correction,
-
-    /// Crossing - Internal IB cross (matched internally)
-    #[serde(rename = "Cs")]
-    Crossing,
-
-    /// Dual Agent - IB dual agent capacity (disclosed dual role)
-    #[serde(rename = "D")]
-    DualAgent,
-
-    /// ETF - ETF creation/redemption (in-kind basket)
-    #[serde(rename = "Et")]
-    Etf,
-
-    /// Expired Position - Resulting from an expired position
-    #[serde(rename = "Ep")]
-    ExpiredPosition,
-
-    /// Exercise - Option exercise (long option exercised)
-    /// Source: https://guides.interactivebrokers.com/reportingguide/reportguide/activity_flex_query_notes.htm
-    #[serde(rename = "Ex")]
-    Exercise,
-
-    /// Opening - Opening trade (new position)
-    /// Source: https://guides.interactivebrokers.com/reportingguide/reportguide/activity_flex_query_notes.htm
-    #[serde(rename = "O")]
-    Opening,
-
-    /// Guaranteed - Guaranteed account segment (special margin)
-    #[serde(rename = "G")]
-    Guaranteed,
-
-    /// Highest Cost - Highest cost tax lot (tax lot selection)
-    #[serde(rename = "Hc")]
-    HighestCost,
-
-    /// HF Investment - Hedge fund investment (fund subscription)
-    #[serde(rename = "Hi")]
-    HfInvestment,
-
-    /// HF Redemption - Hedge fund redemption (fund redemption)
-    #[serde(rename = "Hr")]
-    HfRedemption,
-
-    /// Internal - Internal transfer (between IB accounts)
-    #[serde(rename = "I")]
-    InternalTransfer,
-
-    /// Affiliate - Affiliate execution (related party trade)
-    #[serde(rename = "Ia", alias = "IA")]
-    Affiliate,
-
-    /// Investor - Investment from investor (capital contribution)
-    #[serde(rename = "Iv")]
-    Investor,
-
-    /// Margin Violation - Liquidation due to margin (forced liquidation)
-    #[serde(rename = "L")]
-    MarginLiquidation,
-
-    /// LIFO - LIFO tax lot (tax lot selection)
-    #[serde(rename = "Li")]
-    Lifo,
-
-    /// Loan - Securities lending income (lending income)
-    #[serde(rename = "Ln")]
-    Loan,
-
-    /// Long-Term - Long-term gain/loss (holding > 1 year)
-    #[serde(rename = "Lt")]
-    LongTermGain,
-
-    /// Manual - Manual IB entry (manual adjustment)
-    #[serde(rename = "M")]
-    ManualEntry,
-
-    /// Max Loss - Maximize losses (tax optimization)
-    #[serde(rename = "Ml")]
-    MaxLoss,
-
-    /// Min LT Gain - Minimize long-term gain (tax optimization)
-    #[serde(rename = "Mn")]
-    MinLongTermGain,
-
-    /// Max ST Gain - Maximize short-term gain (tax optimization)
-    #[serde(rename = "Ms")]
-    MaxShortTermGain,
-
-    /// Min ST Gain - Minimize short-term gain (tax optimization)
-    #[serde(rename = "Mi")]
-    MinShortTermGain,
-
-    /// Manual Exercise - Manual exercise (discretionary exercise)
-    #[serde(rename = "Mx")]
-    ManualExercise,
-
-    /// Partial - Partial execution (partial fill)
-    /// Source: https://guides.interactivebrokers.com/reportingguide/reportguide/activity_flex_query_notes.htm
-    #[serde(rename = "P", alias = "Pt")]
-    Partial,
-
-    /// Frac Riskless - Fractional riskless principal (fractional share method)
-    #[serde(rename = "Fr")]
-    FracRiskless,
-
-    /// Frac Principal - Fractional principal (fractional share method)
-    #[serde(rename = "Fp")]
-    FracPrincipal,
-
-    /// Price Improvement - Better than quoted (price improvement)
-    #[serde(rename = "Pi")]
-    PriceImprovement,
-
-    /// Post Accrual - Accrual posting (accrual entry)
-    #[serde(rename = "Pa")]
-    PostAccrual,
-
-    /// Principal - IB principal execution (principal trade)
-    #[serde(rename = "Pr")]
-    Principal,
-
-    /// Reinvestment - Dividend reinvestment (DRIP)
-    #[serde(rename = "Re")]
-    Reinvestment,
-
-    /// Redemption - Capital distribution (fund redemption)
-    #[serde(rename = "Rd")]
-    Redemption,
-
-    /// Reopen - Position reopened (wash sale reopen)
-    #[serde(rename = "R")]
-    Reopen,
-
-    /// Reverse - Accrual reversal (accounting reversal)
-    #[serde(rename = "Rv")]
-    Reverse,
-
-    /// Reimbursement - Fee refund (expense refund)
-    #[serde(rename = "Ri")]
-    Reimbursement,
-
-    /// Solicited IB - IB solicited order (IB-initiated)
-    #[serde(rename = "Si")]
-    SolicitedIb,
-
-    /// Specific Lot - Specific tax lot (tax lot selection)
-    #[serde(rename = "Sp")]
-    SpecificLot,
-
-    /// Solicited Other - Third-party solicited (broker-solicited)
-    #[serde(rename = "So")]
-    SolicitedOther,
-
-    /// Short Settlement - T+0 or T+1 settlement (accelerated settle)
-    #[serde(rename = "Ss")]
-    ShortSettlement,
-
-    /// Short-Term - Short-term gain/loss (holding <= 1 year)
-    #[serde(rename = "St")]
-    ShortTermGain,
-
-    /// Stock Yield - Stock yield eligible (lending eligible)
-    #[serde(rename = "Sy")]
-    StockYield,
-
-    /// Transfer - Position transfer
-    #[serde(rename = "T")]
-    Transfer,
-
-    /// Wash Sale - Wash sale (loss disallowed)
-    #[serde(rename = "W")]
-    WashSale,
-
-    /// Unknown code
-    #[serde(other)]
-    Unknown,
+flex_enum! {
+    /// Direction (To/From)
+    pub enum ToFrom {
+        /// To
+        To => "To",
+
+        /// From
+        From => "From",
+    }
 }
 
-/// Direction (To/From)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub enum ToFrom {
-    /// To
-    To,
+flex_enum! {
+    /// Direction (In/Out)
+    pub enum InOut {
+        /// Incoming
+        IN => "IN",
 
-    /// From
-    From,
+        /// Outgoing
+        OUT => "OUT",
+    }
+}
 
-    /// Unknown
-    #[serde(other)]
-    Unknown,
+flex_enum! {
+    /// Delivered or Received
+    pub enum DeliveredReceived {
+        /// Delivered
+        Delivered => "Delivered",
+
+        /// Received
+        Received => "Received",
+    }
 }
 
-/// Direction (In/Out)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub enum InOut {
-    /// Incoming
-    IN,
+flex_enum! {
+    /// Event of a stock grant (RSU) activity row
+    ///
+    /// **XML Mapping**: Maps to the `type` attribute in `<StockGrantActivity>` elements.
+    pub enum StockGrantActivityType {
+        /// Shares awarded
+        Grant => "Grant" | "Award",
+
+        /// Shares vested
+        Vest => "Vest" | "Vesting",
 
-    /// Outgoing
-    OUT,
+        /// Vested shares released to the account
+        Release => "Release",
 
-    /// Unknown
-    #[serde(other)]
-    Unknown,
+        /// Unvested shares forfeited
+        Forfeit => "Forfeit" | "Forfeiture",
+    }
 }
 
-/// Delivered or Received
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub enum DeliveredReceived {
-    /// Delivered
-    Delivered,
+flex_enum! {
+    /// Event of a Hong Kong IPO subscription activity row
+    ///
+    /// **XML Mapping**: Maps to the `type` attribute in `<HKIPOSubscriptionActivity>` elements.
+    pub enum HKIPOActivityType {
+        /// Subscription applied for (cash frozen)
+        Applied => "Applied" | "Application" | "Subscribed",
 
-    /// Received
-    Received,
+        /// Shares allotted
+        Allotted => "Allotted" | "Allotment",
 
-    /// Unknown
-    #[serde(other)]
-    Unknown,
+        /// Unallotted cash refunded
+        Refunded => "Refunded" | "Refund",
+    }
 }
 
-/// Level of detail for reporting
-///
-/// Specifies the granularity of data in FLEX reports.
-/// Used by `Trade`, `Position`, and `CashTransaction` structs to indicate
-/// the level of detail requested in the FLEX query.
-///
-/// **XML Mapping**: Maps to the `levelOfDetail` attribute in various elements.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub enum LevelOfDetail {
-    /// Summary level - aggregated data with minimal details
-    Summary,
+flex_enum! {
+    /// Level of detail for reporting
+    ///
+    /// Specifies the granularity of data in FLEX reports.
+    /// Used by `Trade`, `Position`, and `CashTransaction` structs to indicate
+    /// the level of detail requested in the FLEX query.
+    ///
+    /// **XML Mapping**: Maps to the `levelOfDetail` attribute in various elements.
+    pub enum LevelOfDetail {
+        /// Summary level - aggregated data with minimal details
+        Summary => "Summary" | "SUMMARY",
 
-    /// Detail level - standard reporting with all key fields
-    Detail,
+        /// Detail level - standard reporting with all key fields
+        Detail => "Detail" | "DETAIL",
 
-    /// Execution level - detailed execution information including time and venue
-    Execution,
+        /// Execution level - detailed execution information including time and venue
+        Execution => "Execution" | "EXECUTION",
 
-    /// Lot level - tax lot level details for cost basis tracking
-    Lot,
+        /// Lot level - tax lot level details for cost basis tracking
+        Lot => "Lot" | "LOT",
 
-    /// Unknown or unrecognized level of detail
-    #[serde(other)]
-    Unknown,
+        /// Closed lot level - the lots closed by a trade
+        ClosedLot => "ClosedLot" | "CLOSED_LOT",
+    }
 }
 
-/// Security identifier type
-///
-/// Specifies the type of security identifier used in the `securityID` field.
-/// Different identifiers are used in different markets and contexts.
-///
-/// **XML Mapping**: Maps to the `securityIDType` attribute in various elements.
-///
-/// **Used by**: `Trade`, `SecurityInfo`
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub enum SecurityIdType {
-    /// CUSIP - Committee on Uniform Securities Identification Procedures
-    /// 9-character alphanumeric identifier for North American securities
-    #[serde(rename = "CUSIP")]
-    Cusip,
-
-    /// ISIN - International Securities Identification Number
-    /// 12-character alphanumeric code (ISO 6166 standard)
-    #[serde(rename = "ISIN")]
-    Isin,
-
-    /// FIGI - Financial Instrument Global Identifier
-    /// 12-character alphanumeric identifier (Bloomberg Open Symbology)
-    #[serde(rename = "FIGI")]
-    Figi,
-
-    /// SEDOL - Stock Exchange Daily Official List
-    /// 7-character alphanumeric identifier for UK and Irish securities
-    #[serde(rename = "SEDOL")]
-    Sedol,
-
-    /// Unknown or unrecognized security ID type
-    #[serde(other)]
-    Unknown,
+flex_enum! {
+    /// Security identifier type
+    ///
+    /// Specifies the type of security identifier used in the `securityID` field.
+    /// Different identifiers are used in different markets and contexts.
+    ///
+    /// **XML Mapping**: Maps to the `securityIDType` attribute in various elements.
+    ///
+    /// **Used by**: `Trade`, `SecurityInfo`
+    pub enum SecurityIdType {
+        /// CUSIP - Committee on Uniform Securities Identification Procedures
+        /// 9-character alphanumeric identifier for North American securities
+        Cusip => "CUSIP",
+
+        /// ISIN - International Securities Identification Number
+        /// 12-character alphanumeric code (ISO 6166 standard)
+        Isin => "ISIN",
+
+        /// FIGI - Financial Instrument Global Identifier
+        /// 12-character alphanumeric identifier (Bloomberg Open Symbology)
+        Figi => "FIGI",
+
+        /// SEDOL - Stock Exchange Daily Official List
+        /// 7-character alphanumeric identifier for UK and Irish securities
+        Sedol => "SEDOL",
+    }
 }
 
-/// Security sub-category
-///
-/// Provides additional classification for securities beyond the basic asset category.
-/// Most commonly used for stocks to distinguish between common shares, ETFs, ADRs, REITs, etc.
-///
-/// **XML Mapping**: Maps to the `subCategory` attribute in various elements.
-///
-/// **Used by**: `Trade`, `Position`, `SecurityInfo`
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub enum SubCategory {
-    /// Exchange-traded fund
-    #[serde(rename = "ETF")]
-    Etf,
-
-    /// American Depositary Receipt - represents foreign company shares traded in US
-    #[serde(rename = "ADR")]
-    Adr,
-
-    /// Real Estate Investment Trust
-    #[serde(rename = "REIT")]
-    Reit,
-
-    /// Preferred stock
-    #[serde(rename = "Preferred")]
-    Preferred,
-
-    /// Common stock
-    #[serde(rename = "Common")]
-    Common,
-
-    /// Depositary Receipt (general)
-    #[serde(rename = "DR")]
-    DepositaryReceipt,
-
-    /// Global Depositary Receipt
-    #[serde(rename = "GDR")]
-    Gdr,
-
-    /// Limited Partnership
-    #[serde(rename = "LP")]
-    LimitedPartnership,
-
-    /// Master Limited Partnership
-    #[serde(rename = "MLP")]
-    MasterLimitedPartnership,
-
-    /// Right (subscription right)
-    #[serde(rename = "Right")]
-    Right,
-
-    /// Unit (combination of securities)
-    #[serde(rename = "Unit")]
-    Unit,
-
-    /// When-Issued security
-    #[serde(rename = "WI")]
-    WhenIssued,
-
-    /// Tracking stock
-    #[serde(rename = "Tracking")]
-    Tracking,
-
-    /// Closed-end fund
-    #[serde(rename = "CEF")]
-    ClosedEndFund,
-
-    /// Unknown or unrecognized sub-category
-    #[serde(other)]
-    Unknown,
+flex_enum! {
+    /// Security sub-category
+    ///
+    /// Provides additional classification for securities beyond the basic asset category.
+    /// Most commonly used for stocks to distinguish between common shares, ETFs, ADRs, REITs, etc.
+    ///
+    /// **XML Mapping**: Maps to the `subCategory` attribute in various elements.
+    ///
+    /// **Used by**: `Trade`, `Position`, `SecurityInfo`
+    pub enum SubCategory {
+        /// Exchange-traded fund
+        Etf => "ETF",
+
+        /// American Depositary Receipt - represents foreign company shares traded in US
+        Adr => "ADR",
+
+        /// Real Estate Investment Trust
+        Reit => "REIT",
+
+        /// Preferred stock
+        Preferred => "Preferred",
+
+        /// Common stock
+        Common => "Common",
+
+        /// Depositary Receipt (general)
+        DepositaryReceipt => "DR",
+
+        /// Global Depositary Receipt
+        Gdr => "GDR",
+
+        /// Limited Partnership
+        LimitedPartnership => "LP",
+
+        /// Master Limited Partnership
+        MasterLimitedPartnership => "MLP",
+
+        /// Right (subscription right)
+        Right => "Right",
+
+        /// Unit (combination of securities)
+        Unit => "Unit",
+
+        /// When-Issued security
+        WhenIssued => "WI",
+
+        /// Tracking stock
+        Tracking => "Tracking",
+
+        /// Closed-end fund
+        ClosedEndFund => "CEF",
+    }
 }
 
 /// Derivative instrument information
@@ -1076,4 +1012,38 @@ mod tests {
         assert_eq!(call, PutCall::Call);
         assert_ne!(call, PutCall::Put);
     }
+
+    #[test]
+    fn test_unknown_value_round_trip() {
+        let parsed: CashTransactionType = serde_plain::from_str("Staking Rewards").unwrap();
+        assert_eq!(
+            parsed,
+            CashTransactionType::Unknown("Staking Rewards".to_string())
+        );
+        assert!(parsed.is_unknown());
+        assert_eq!(serde_plain::to_string(&parsed).unwrap(), "Staking Rewards");
+
+        // Aliases deserialize to the variant and serialize as the primary value
+        let split: CorporateActionType = serde_plain::from_str("Stock Split").unwrap();
+        assert_eq!(split, CorporateActionType::StockSplit);
+        assert_eq!(split.as_str(), "FS");
+        assert_eq!("FS".parse::<CorporateActionType>(), Ok(split));
+    }
+
+    #[test]
+    fn test_level_of_detail_ib_spellings() {
+        // IB's spellings are aliases; the names serialize as before
+        let lot: LevelOfDetail = serde_plain::from_str("LOT").unwrap();
+        assert_eq!(lot, LevelOfDetail::Lot);
+        assert_eq!(serde_plain::to_string(&lot).unwrap(), "Lot");
+        assert_eq!(
+            serde_plain::from_str::<LevelOfDetail>("SUMMARY").unwrap(),
+            LevelOfDetail::Summary
+        );
+
+        // Closed lots are not open lots
+        let closed: LevelOfDetail = serde_plain::from_str("CLOSED_LOT").unwrap();
+        assert_eq!(closed, LevelOfDetail::ClosedLot);
+        assert_ne!(closed, LevelOfDetail::Lot);
+    }
 }
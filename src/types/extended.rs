@@ -4,13 +4,19 @@
 //! These include account information, NAV changes, performance summaries,
 //! fee details, and more comprehensive trading data.
 
+use std::collections::BTreeMap;
+
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
-use super::common::{AssetCategory, OptionAction, TransferType};
+use super::common::{
+    AssetCategory, BuySell, HKIPOActivityType, OptionAction, PutCall, StockGrantActivityType,
+    TransferType,
+};
 use crate::parsers::xml_utils::{
-    deserialize_flex_date, deserialize_optional_date, deserialize_optional_decimal,
+    deserialize_decimal, deserialize_flex_date, deserialize_optional_date,
+    deserialize_optional_decimal,
 };
 
 /// Account information and metadata
@@ -119,6 +125,10 @@ pub struct AccountInformation {
     /// Model
     #[serde(rename = "@model", default)]
     pub model: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Change in portfolio NAV (Net Asset Value)
@@ -149,11 +159,11 @@ pub struct ChangeInNAV {
     pub to_date: NaiveDate,
 
     /// Starting NAV value
-    #[serde(rename = "@startingValue")]
+    #[serde(rename = "@startingValue", deserialize_with = "deserialize_decimal")]
     pub starting_value: Decimal,
 
     /// Ending NAV value
-    #[serde(rename = "@endingValue")]
+    #[serde(rename = "@endingValue", deserialize_with = "deserialize_decimal")]
     pub ending_value: Decimal,
 
     /// Mark-to-market P&L
@@ -283,6 +293,10 @@ pub struct ChangeInNAV {
         deserialize_with = "deserialize_optional_decimal"
     )]
     pub corporate_action_proceeds: Option<Decimal>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Equity summary by report date in base currency
@@ -599,6 +613,10 @@ pub struct EquitySummaryByReportDateInBase {
         deserialize_with = "deserialize_optional_decimal"
     )]
     pub net_liquidation_uncertainty: Option<Decimal>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Cash report by currency
@@ -629,7 +647,7 @@ pub struct CashReportCurrency {
     pub to_date: NaiveDate,
 
     /// Starting cash
-    #[serde(rename = "@startingCash")]
+    #[serde(rename = "@startingCash", deserialize_with = "deserialize_decimal")]
     pub starting_cash: Decimal,
 
     /// Starting cash (securities segment)
@@ -833,7 +851,7 @@ pub struct CashReportCurrency {
     pub soft_dollars: Option<Decimal>,
 
     /// Ending cash
-    #[serde(rename = "@endingCash")]
+    #[serde(rename = "@endingCash", deserialize_with = "deserialize_decimal")]
     pub ending_cash: Decimal,
 
     /// Ending cash (securities segment)
@@ -875,6 +893,214 @@ pub struct CashReportCurrency {
         deserialize_with = "deserialize_optional_decimal"
     )]
     pub ending_settled_cash_com: Option<Decimal>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+/// Deposit on hold
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DepositOnHold {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Currency
+    #[serde(rename = "@currency")]
+    pub currency: String,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Date the deposit was credited
+    #[serde(rename = "@date", deserialize_with = "deserialize_flex_date")]
+    pub date: NaiveDate,
+
+    /// Date the hold is released
+    #[serde(
+        rename = "@holdReleaseDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub hold_release_date: Option<NaiveDate>,
+
+    /// Deposit type (e.g. ACH, Check)
+    #[serde(rename = "@type", default)]
+    pub deposit_type: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Amount on hold
+    #[serde(rename = "@amount", deserialize_with = "deserialize_decimal")]
+    pub amount: Decimal,
+
+    /// Transaction ID
+    #[serde(rename = "@transactionID", default)]
+    pub transaction_id: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+/// Sweep deposit held at one FDIC-insured program bank
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FdicInsuredDepositByBank {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Currency
+    #[serde(rename = "@currency")]
+    pub currency: String,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Report date
+    #[serde(
+        rename = "@reportDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub report_date: Option<NaiveDate>,
+
+    /// Bank ID
+    #[serde(rename = "@bankID", default)]
+    pub bank_id: Option<String>,
+
+    /// Bank name
+    #[serde(rename = "@bankName", default)]
+    pub bank_name: Option<String>,
+
+    /// Amount deposited at the bank
+    #[serde(rename = "@amount", deserialize_with = "deserialize_decimal")]
+    pub amount: Decimal,
+
+    /// Interest rate
+    #[serde(
+        rename = "@interestRate",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub interest_rate: Option<Decimal>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+/// IBG note transaction
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct IBGNoteTransaction {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Currency
+    #[serde(rename = "@currency")]
+    pub currency: String,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Asset category
+    #[serde(rename = "@assetCategory", default)]
+    pub asset_category: Option<AssetCategory>,
+
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
+
+    /// Date
+    #[serde(rename = "@date", deserialize_with = "deserialize_flex_date")]
+    pub date: NaiveDate,
+
+    /// Date/time
+    #[serde(rename = "@dateTime", default)]
+    pub date_time: Option<String>,
+
+    /// Transaction type (e.g. Purchase, Redemption, Interest)
+    #[serde(rename = "@type", default)]
+    pub transaction_type: Option<String>,
+
+    /// Quantity
+    #[serde(
+        rename = "@quantity",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub quantity: Option<Decimal>,
+
+    /// Price
+    #[serde(
+        rename = "@price",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub price: Option<Decimal>,
+
+    /// Amount
+    #[serde(rename = "@amount", deserialize_with = "deserialize_decimal")]
+    pub amount: Decimal,
+
+    /// Transaction ID
+    #[serde(rename = "@transactionID", default)]
+    pub transaction_id: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Trade confirmation
@@ -1001,13 +1227,25 @@ pub struct TradeConfirm {
 
     // Trade details
     /// Quantity
-    #[serde(rename = "@quantity")]
+    #[serde(rename = "@quantity", deserialize_with = "deserialize_decimal")]
     pub quantity: Decimal,
 
-    /// Trade price
-    #[serde(rename = "@tradePrice")]
+    /// Trade price (`price` in Trade Confirmation FLEX)
+    #[serde(
+        rename = "@tradePrice",
+        alias = "@price",
+        deserialize_with = "deserialize_decimal"
+    )]
     pub trade_price: Decimal,
 
+    /// Amount (quantity x price x multiplier)
+    #[serde(
+        rename = "@amount",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub amount: Option<Decimal>,
+
     /// Proceeds
     #[serde(
         rename = "@proceeds",
@@ -1024,6 +1262,58 @@ pub struct TradeConfirm {
     )]
     pub commission: Option<Decimal>,
 
+    /// Commission currency
+    #[serde(rename = "@commissionCurrency", default)]
+    pub commission_currency: Option<String>,
+
+    /// Broker execution commission
+    #[serde(
+        rename = "@brokerExecutionCommission",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub broker_execution_commission: Option<Decimal>,
+
+    /// Broker clearing commission
+    #[serde(
+        rename = "@brokerClearingCommission",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub broker_clearing_commission: Option<Decimal>,
+
+    /// Third-party execution commission
+    #[serde(
+        rename = "@thirdPartyExecutionCommission",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub third_party_execution_commission: Option<Decimal>,
+
+    /// Third-party clearing commission
+    #[serde(
+        rename = "@thirdPartyClearingCommission",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub third_party_clearing_commission: Option<Decimal>,
+
+    /// Third-party regulatory commission
+    #[serde(
+        rename = "@thirdPartyRegulatoryCommission",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub third_party_regulatory_commission: Option<Decimal>,
+
+    /// Other commission
+    #[serde(
+        rename = "@otherCommission",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub other_commission: Option<Decimal>,
+
     /// Taxes
     #[serde(
         rename = "@tax",
@@ -1091,6 +1381,10 @@ pub struct TradeConfirm {
     /// Level of detail
     #[serde(rename = "@levelOfDetail", default)]
     pub level_of_detail: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Option exercise/assignment/expiration
@@ -1162,7 +1456,7 @@ pub struct OptionEAE {
     pub listing_exchange: Option<String>,
 
     /// Quantity
-    #[serde(rename = "@quantity")]
+    #[serde(rename = "@quantity", deserialize_with = "deserialize_decimal")]
     pub quantity: Decimal,
 
     /// Strike
@@ -1252,6 +1546,10 @@ pub struct OptionEAE {
     /// Level of detail
     #[serde(rename = "@levelOfDetail", default)]
     pub level_of_detail: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Foreign exchange transaction
@@ -1306,11 +1604,11 @@ pub struct FxTransaction {
     pub to_currency: String,
 
     /// Quantity
-    #[serde(rename = "@quantity")]
+    #[serde(rename = "@quantity", deserialize_with = "deserialize_decimal")]
     pub quantity: Decimal,
 
     /// Proceeds
-    #[serde(rename = "@proceeds")]
+    #[serde(rename = "@proceeds", deserialize_with = "deserialize_decimal")]
     pub proceeds: Decimal,
 
     /// Cost
@@ -1344,6 +1642,10 @@ pub struct FxTransaction {
     /// Asset category
     #[serde(rename = "@assetCategory", default)]
     pub asset_category: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Change in dividend accruals
@@ -1458,7 +1760,7 @@ pub struct ChangeInDividendAccrual {
     pub fee: Option<Decimal>,
 
     /// Gross rate
-    #[serde(rename = "@grossRate")]
+    #[serde(rename = "@grossRate", deserialize_with = "deserialize_decimal")]
     pub gross_rate: Decimal,
 
     /// Gross amount
@@ -1470,7 +1772,7 @@ pub struct ChangeInDividendAccrual {
     pub gross_amount: Option<Decimal>,
 
     /// Net amount
-    #[serde(rename = "@netAmount")]
+    #[serde(rename = "@netAmount", deserialize_with = "deserialize_decimal")]
     pub net_amount: Decimal,
 
     /// From accrual (prior period)
@@ -1492,6 +1794,10 @@ pub struct ChangeInDividendAccrual {
     /// Code
     #[serde(rename = "@code", default)]
     pub code: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Open dividend accruals
@@ -1574,7 +1880,7 @@ pub struct OpenDividendAccrual {
     pub pay_date: Option<NaiveDate>,
 
     /// Quantity
-    #[serde(rename = "@quantity")]
+    #[serde(rename = "@quantity", deserialize_with = "deserialize_decimal")]
     pub quantity: Decimal,
 
     /// Tax
@@ -1594,7 +1900,7 @@ pub struct OpenDividendAccrual {
     pub fee: Option<Decimal>,
 
     /// Gross rate
-    #[serde(rename = "@grossRate")]
+    #[serde(rename = "@grossRate", deserialize_with = "deserialize_decimal")]
     pub gross_rate: Decimal,
 
     /// Gross amount
@@ -1616,6 +1922,10 @@ pub struct OpenDividendAccrual {
     /// Code
     #[serde(rename = "@code", default)]
     pub code: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Interest accruals by currency
@@ -1638,16 +1948,26 @@ pub struct InterestAccrualsCurrency {
     pub to_date: NaiveDate,
 
     /// Starting accrual balance
-    #[serde(rename = "@startingAccrualBalance")]
+    #[serde(
+        rename = "@startingAccrualBalance",
+        deserialize_with = "deserialize_decimal"
+    )]
     pub starting_balance: Decimal,
 
     /// Interest accrued
-    #[serde(rename = "@interestAccrued")]
+    #[serde(rename = "@interestAccrued", deserialize_with = "deserialize_decimal")]
     pub interest_accrued: Decimal,
 
     /// Ending accrual balance
-    #[serde(rename = "@endingAccrualBalance")]
+    #[serde(
+        rename = "@endingAccrualBalance",
+        deserialize_with = "deserialize_decimal"
+    )]
     pub ending_balance: Decimal,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Security transfer
@@ -1707,7 +2027,7 @@ pub struct Transfer {
     pub listing_exchange: Option<String>,
 
     /// Quantity
-    #[serde(rename = "@quantity")]
+    #[serde(rename = "@quantity", deserialize_with = "deserialize_decimal")]
     pub quantity: Decimal,
 
     /// Price
@@ -1802,6 +2122,10 @@ pub struct Transfer {
     /// Transaction code (e.g. Ca for cancelled)
     #[serde(rename = "@code", default)]
     pub code: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 // =============================================================================
@@ -1975,6 +2299,10 @@ pub struct MTMPerformanceSummaryUnderlying {
     /// Level of detail
     #[serde(rename = "@levelOfDetail", default)]
     pub level_of_detail: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// FIFO performance summary by underlying security
@@ -2100,6 +2428,10 @@ pub struct FIFOPerformanceSummaryUnderlying {
     /// Level of detail
     #[serde(rename = "@levelOfDetail", default)]
     pub level_of_detail: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Month-to-date/Year-to-date performance summary
@@ -2198,6 +2530,10 @@ pub struct MTDYTDPerformanceSummary {
     /// Level of detail
     #[serde(rename = "@levelOfDetail", default)]
     pub level_of_detail: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 // =============================================================================
@@ -2313,6 +2649,10 @@ pub struct StatementOfFundsLine {
     /// Level of detail
     #[serde(rename = "@levelOfDetail", default)]
     pub level_of_detail: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 // =============================================================================
@@ -2428,6 +2768,10 @@ pub struct ChangeInPositionValue {
     /// Level of detail
     #[serde(rename = "@levelOfDetail", default)]
     pub level_of_detail: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 // =============================================================================
@@ -2555,6 +2899,10 @@ pub struct UnbundledCommissionDetail {
     /// Currency
     #[serde(rename = "@currency", default)]
     pub currency: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Client fee (advisory/service fees)
@@ -2611,6 +2959,10 @@ pub struct ClientFee {
     /// Description
     #[serde(rename = "@description", default)]
     pub description: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Client fee detail
@@ -2679,6 +3031,10 @@ pub struct ClientFeesDetail {
         deserialize_with = "deserialize_optional_decimal"
     )]
     pub fx_rate_to_base: Option<Decimal>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 // =============================================================================
@@ -2771,6 +3127,10 @@ pub struct SLBActivity {
         deserialize_with = "deserialize_optional_decimal"
     )]
     pub fx_rate_to_base: Option<Decimal>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Securities lending fee
@@ -2871,11 +3231,17 @@ pub struct SLBFee {
         deserialize_with = "deserialize_optional_decimal"
     )]
     pub fx_rate_to_base: Option<Decimal>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
-/// Hard to borrow stock details
+/// Securities lending/borrowing open contract
+///
+/// Shares currently lent out or borrowed at the end of the period.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
-pub struct HardToBorrowDetail {
+pub struct SLBOpenContract {
     /// Account ID
     #[serde(rename = "@accountId")]
     pub account_id: String,
@@ -2888,17 +3254,235 @@ pub struct HardToBorrowDetail {
     #[serde(rename = "@model", default)]
     pub model: Option<String>,
 
-    /// Symbol
-    #[serde(rename = "@symbol", default)]
-    pub symbol: Option<String>,
-
-    /// Description
-    #[serde(rename = "@description", default)]
-    pub description: Option<String>,
-
-    /// Contract ID
-    #[serde(rename = "@conid", default)]
-    pub conid: Option<String>,
+    /// Currency
+    #[serde(rename = "@currency", default)]
+    pub currency: Option<String>,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Asset category
+    #[serde(rename = "@assetCategory", default)]
+    pub asset_category: Option<AssetCategory>,
+
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
+
+    /// ISIN
+    #[serde(rename = "@isin", default)]
+    pub isin: Option<String>,
+
+    /// Report date
+    #[serde(
+        rename = "@date",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub date: Option<NaiveDate>,
+
+    /// Contract start date
+    #[serde(
+        rename = "@startDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub start_date: Option<NaiveDate>,
+
+    /// Transaction ID
+    #[serde(rename = "@transactionID", default)]
+    pub transaction_id: Option<String>,
+
+    /// Contract type (e.g. Loan, Borrow)
+    #[serde(rename = "@type", default)]
+    pub contract_type: Option<String>,
+
+    /// Exchange
+    #[serde(rename = "@exchange", default)]
+    pub exchange: Option<String>,
+
+    /// Quantity on loan or borrowed
+    #[serde(
+        rename = "@quantity",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub quantity: Option<Decimal>,
+
+    /// Mark price
+    #[serde(
+        rename = "@markPrice",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub mark_price: Option<Decimal>,
+
+    /// Collateral amount
+    #[serde(
+        rename = "@collateralAmount",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub collateral_amount: Option<Decimal>,
+
+    /// Fee rate (annual %)
+    #[serde(
+        rename = "@feeRate",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fee_rate: Option<Decimal>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+/// Securities lending/borrowing collateral
+///
+/// Cash collateral backing lent or borrowed shares.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SLBCollateral {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Currency
+    #[serde(rename = "@currency", default)]
+    pub currency: Option<String>,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Asset category
+    #[serde(rename = "@assetCategory", default)]
+    pub asset_category: Option<AssetCategory>,
+
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
+
+    /// Report date
+    #[serde(
+        rename = "@date",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub date: Option<NaiveDate>,
+
+    /// Contract start date
+    #[serde(
+        rename = "@startDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub start_date: Option<NaiveDate>,
+
+    /// Transaction ID
+    #[serde(rename = "@transactionID", default)]
+    pub transaction_id: Option<String>,
+
+    /// Collateral type
+    #[serde(rename = "@type", default)]
+    pub collateral_type: Option<String>,
+
+    /// Quantity covered
+    #[serde(
+        rename = "@quantity",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub quantity: Option<Decimal>,
+
+    /// Collateral amount
+    #[serde(
+        rename = "@collateralAmount",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub collateral_amount: Option<Decimal>,
+
+    /// Collateral rate (% of market value)
+    #[serde(
+        rename = "@collateralRate",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub collateral_rate: Option<Decimal>,
+
+    /// Fee rate (annual %)
+    #[serde(
+        rename = "@feeRate",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fee_rate: Option<Decimal>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+/// Hard to borrow stock details
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct HardToBorrowDetail {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
 
     /// Asset category
     #[serde(rename = "@assetCategory", default)]
@@ -2963,15 +3547,21 @@ pub struct HardToBorrowDetail {
         deserialize_with = "deserialize_optional_decimal"
     )]
     pub fx_rate_to_base: Option<Decimal>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 // =============================================================================
 // FX Lot Types
 // =============================================================================
 
-/// FX position lot detail
+/// FX (cash currency) position
+///
+/// Per-currency cash position with cost basis and unrealized FX P&L.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
-pub struct FxLot {
+pub struct FxPosition {
     /// Account ID
     #[serde(rename = "@accountId")]
     pub account_id: String,
@@ -2986,7 +3576,7 @@ pub struct FxLot {
 
     /// Asset category
     #[serde(rename = "@assetCategory", default)]
-    pub asset_category: Option<String>,
+    pub asset_category: Option<AssetCategory>,
 
     /// Report date
     #[serde(
@@ -2996,15 +3586,15 @@ pub struct FxLot {
     )]
     pub report_date: Option<NaiveDate>,
 
-    /// Functional currency
+    /// Functional (base) currency
     #[serde(rename = "@functionalCurrency", default)]
     pub functional_currency: Option<String>,
 
-    /// FX currency
+    /// Currency held
     #[serde(rename = "@fxCurrency", default)]
     pub fx_currency: Option<String>,
 
-    /// Quantity
+    /// Quantity of the currency
     #[serde(
         rename = "@quantity",
         default,
@@ -3020,7 +3610,7 @@ pub struct FxLot {
     )]
     pub cost_price: Option<Decimal>,
 
-    /// Cost basis
+    /// Cost basis in functional currency
     #[serde(
         rename = "@costBasis",
         default,
@@ -3036,7 +3626,7 @@ pub struct FxLot {
     )]
     pub close_price: Option<Decimal>,
 
-    /// Value
+    /// Value in functional currency
     #[serde(
         rename = "@value",
         default,
@@ -3044,7 +3634,7 @@ pub struct FxLot {
     )]
     pub value: Option<Decimal>,
 
-    /// Unrealized P&L
+    /// Unrealized FX P&L
     #[serde(
         rename = "@unrealizedPL",
         default,
@@ -3052,18 +3642,30 @@ pub struct FxLot {
     )]
     pub unrealized_pl: Option<Decimal>,
 
+    /// Notes/codes
+    #[serde(rename = "@code", default)]
+    pub code: Option<String>,
+
+    /// Lot description
+    #[serde(rename = "@lotDescription", default)]
+    pub lot_description: Option<String>,
+
+    /// Lot open date/time
+    #[serde(rename = "@lotOpenDateTime", default)]
+    pub lot_open_date_time: Option<String>,
+
     /// Level of detail
     #[serde(rename = "@levelOfDetail", default)]
     pub level_of_detail: Option<String>,
-}
 
-// =============================================================================
-// Transfer Types
-// =============================================================================
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
 
-/// Unsettled transfer
+/// FX position lot detail
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
-pub struct UnsettledTransfer {
+pub struct FxLot {
     /// Account ID
     #[serde(rename = "@accountId")]
     pub account_id: String,
@@ -3076,38 +3678,134 @@ pub struct UnsettledTransfer {
     #[serde(rename = "@model", default)]
     pub model: Option<String>,
 
-    /// Symbol
-    #[serde(rename = "@symbol", default)]
-    pub symbol: Option<String>,
-
-    /// Description
-    #[serde(rename = "@description", default)]
-    pub description: Option<String>,
-
-    /// Contract ID
-    #[serde(rename = "@conid", default)]
-    pub conid: Option<String>,
-
     /// Asset category
     #[serde(rename = "@assetCategory", default)]
-    pub asset_category: Option<AssetCategory>,
-
-    /// Direction
-    #[serde(rename = "@direction", default)]
-    pub direction: Option<String>,
+    pub asset_category: Option<String>,
 
-    /// Date
+    /// Report date
     #[serde(
-        rename = "@date",
+        rename = "@reportDate",
         default,
         deserialize_with = "deserialize_optional_date"
     )]
-    pub date: Option<NaiveDate>,
+    pub report_date: Option<NaiveDate>,
 
-    /// Expected date
-    #[serde(
-        rename = "@expectedDate",
-        default,
+    /// Functional currency
+    #[serde(rename = "@functionalCurrency", default)]
+    pub functional_currency: Option<String>,
+
+    /// FX currency
+    #[serde(rename = "@fxCurrency", default)]
+    pub fx_currency: Option<String>,
+
+    /// Quantity
+    #[serde(
+        rename = "@quantity",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub quantity: Option<Decimal>,
+
+    /// Cost price
+    #[serde(
+        rename = "@costPrice",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub cost_price: Option<Decimal>,
+
+    /// Cost basis
+    #[serde(
+        rename = "@costBasis",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub cost_basis: Option<Decimal>,
+
+    /// Close price
+    #[serde(
+        rename = "@closePrice",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub close_price: Option<Decimal>,
+
+    /// Value
+    #[serde(
+        rename = "@value",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub value: Option<Decimal>,
+
+    /// Unrealized P&L
+    #[serde(
+        rename = "@unrealizedPL",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub unrealized_pl: Option<Decimal>,
+
+    /// Level of detail
+    #[serde(rename = "@levelOfDetail", default)]
+    pub level_of_detail: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+// =============================================================================
+// Transfer Types
+// =============================================================================
+
+/// Unsettled transfer
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct UnsettledTransfer {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
+
+    /// Asset category
+    #[serde(rename = "@assetCategory", default)]
+    pub asset_category: Option<AssetCategory>,
+
+    /// Direction
+    #[serde(rename = "@direction", default)]
+    pub direction: Option<String>,
+
+    /// Date
+    #[serde(
+        rename = "@date",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub date: Option<NaiveDate>,
+
+    /// Expected date
+    #[serde(
+        rename = "@expectedDate",
+        default,
         deserialize_with = "deserialize_optional_date"
     )]
     pub expected_date: Option<NaiveDate>,
@@ -3131,6 +3829,10 @@ pub struct UnsettledTransfer {
         deserialize_with = "deserialize_optional_decimal"
     )]
     pub fx_rate_to_base: Option<Decimal>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Trade transfer between accounts/brokers
@@ -3215,6 +3917,10 @@ pub struct TradeTransfer {
         deserialize_with = "deserialize_optional_decimal"
     )]
     pub fx_rate_to_base: Option<Decimal>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 // =============================================================================
@@ -3287,6 +3993,10 @@ pub struct PriorPeriodPosition {
     /// Currency
     #[serde(rename = "@currency", default)]
     pub currency: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 // =============================================================================
@@ -3384,63 +4094,1524 @@ pub struct TierInterestDetail {
     )]
     pub total_principal: Option<Decimal>,
 
-    /// Interest rate
+    /// Interest rate
+    #[serde(
+        rename = "@rate",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub rate: Option<Decimal>,
+
+    /// Securities interest
+    #[serde(
+        rename = "@securitiesInterest",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub securities_interest: Option<Decimal>,
+
+    /// Commodities interest
+    #[serde(
+        rename = "@commoditiesInterest",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub commodities_interest: Option<Decimal>,
+
+    /// IBUKL interest
+    #[serde(
+        rename = "@ibuklInterest",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub ibukl_interest: Option<Decimal>,
+
+    /// Total interest
+    #[serde(
+        rename = "@totalInterest",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub total_interest: Option<Decimal>,
+
+    /// Code
+    #[serde(rename = "@code", default)]
+    pub code: Option<String>,
+
+    /// From account
+    #[serde(rename = "@fromAcct", default)]
+    pub from_acct: Option<String>,
+
+    /// To account
+    #[serde(rename = "@toAcct", default)]
+    pub to_acct: Option<String>,
+
+    /// Margin balance
+    #[serde(rename = "@marginBalance", default)]
+    pub margin_balance: Option<String>,
+
+    // Legacy fields (may appear in older reports)
+    /// Date
+    #[serde(
+        rename = "@date",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub date: Option<NaiveDate>,
+
+    /// From date
+    #[serde(
+        rename = "@fromDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub from_date: Option<NaiveDate>,
+
+    /// To date
+    #[serde(
+        rename = "@toDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub to_date: Option<NaiveDate>,
+
+    /// Balance (legacy)
+    #[serde(
+        rename = "@balance",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub balance: Option<Decimal>,
+
+    /// Interest rate (legacy field name)
+    #[serde(
+        rename = "@interestRate",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub interest_rate: Option<Decimal>,
+
+    /// Interest (legacy)
+    #[serde(
+        rename = "@interest",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub interest: Option<Decimal>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+// =============================================================================
+// Miscellaneous Activity Types
+// =============================================================================
+
+/// Debit card activity
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DebitCardActivity {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Date
+    #[serde(
+        rename = "@date",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub date: Option<NaiveDate>,
+
+    /// Merchant
+    #[serde(rename = "@merchant", default)]
+    pub merchant: Option<String>,
+
+    /// Category
+    #[serde(rename = "@category", default)]
+    pub category: Option<String>,
+
+    /// Status
+    #[serde(rename = "@status", default)]
+    pub status: Option<String>,
+
+    /// Transaction type
+    #[serde(rename = "@transactionType", default)]
+    pub transaction_type: Option<String>,
+
+    /// Amount
+    #[serde(
+        rename = "@amount",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub amount: Option<Decimal>,
+
+    /// Currency
+    #[serde(rename = "@currency", default)]
+    pub currency: Option<String>,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+/// Sales tax
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SalesTax {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Date
+    #[serde(
+        rename = "@date",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub date: Option<NaiveDate>,
+
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
+
+    /// Tax type
+    #[serde(rename = "@taxType", default)]
+    pub tax_type: Option<String>,
+
+    /// Tax amount
+    #[serde(
+        rename = "@taxAmount",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub tax_amount: Option<Decimal>,
+
+    /// Proceeds
+    #[serde(
+        rename = "@proceeds",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub proceeds: Option<Decimal>,
+
+    /// Currency
+    #[serde(rename = "@currency", default)]
+    pub currency: Option<String>,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+// =============================================================================
+// Position Types
+// =============================================================================
+
+/// Net stock position (shares held, borrowed and lent)
+///
+/// Used by both the `NetStockPositions` and `NetStockPositionSummary` sections.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct NetStockPosition {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Currency
+    #[serde(rename = "@currency", default)]
+    pub currency: Option<String>,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Asset category
+    #[serde(rename = "@assetCategory", default)]
+    pub asset_category: Option<AssetCategory>,
+
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
+
+    /// ISIN
+    #[serde(rename = "@isin", default)]
+    pub isin: Option<String>,
+
+    /// Listing exchange
+    #[serde(rename = "@listingExchange", default)]
+    pub listing_exchange: Option<String>,
+
+    /// Report date
+    #[serde(
+        rename = "@reportDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub report_date: Option<NaiveDate>,
+
+    /// Shares held at IB
+    #[serde(
+        rename = "@sharesAtIb",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub shares_at_ib: Option<Decimal>,
+
+    /// Shares borrowed
+    #[serde(
+        rename = "@sharesBorrowed",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub shares_borrowed: Option<Decimal>,
+
+    /// Shares lent
+    #[serde(
+        rename = "@sharesLent",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub shares_lent: Option<Decimal>,
+
+    /// Net shares
+    #[serde(
+        rename = "@netShares",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub net_shares: Option<Decimal>,
+
+    /// Level of detail
+    #[serde(rename = "@levelOfDetail", default)]
+    pub level_of_detail: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+/// Complex (combination) position
+///
+/// A combo reported as one position; its legs appear as separate rows.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ComplexPosition {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Currency
+    #[serde(rename = "@currency", default)]
+    pub currency: Option<String>,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Asset category
+    #[serde(rename = "@assetCategory", default)]
+    pub asset_category: Option<AssetCategory>,
+
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
+
+    /// Underlying contract ID
+    #[serde(rename = "@underlyingConid", default)]
+    pub underlying_conid: Option<String>,
+
+    /// Underlying symbol
+    #[serde(rename = "@underlyingSymbol", default)]
+    pub underlying_symbol: Option<String>,
+
+    /// Multiplier
+    #[serde(
+        rename = "@multiplier",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub multiplier: Option<Decimal>,
+
+    /// Report date
+    #[serde(
+        rename = "@reportDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub report_date: Option<NaiveDate>,
+
+    /// Position quantity (negative for short)
+    #[serde(
+        rename = "@position",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub quantity: Option<Decimal>,
+
+    /// Mark price
+    #[serde(
+        rename = "@markPrice",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub mark_price: Option<Decimal>,
+
+    /// Position value
+    #[serde(
+        rename = "@positionValue",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub position_value: Option<Decimal>,
+
+    /// Cost basis price
+    #[serde(
+        rename = "@costBasisPrice",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub cost_basis_price: Option<Decimal>,
+
+    /// Cost basis money
+    #[serde(
+        rename = "@costBasisMoney",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub cost_basis_money: Option<Decimal>,
+
+    /// FIFO unrealized P&L
+    #[serde(
+        rename = "@fifoPnlUnrealized",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fifo_pnl_unrealized: Option<Decimal>,
+
+    /// Side (Long/Short)
+    #[serde(rename = "@side", default)]
+    pub side: Option<String>,
+
+    /// Notes/codes
+    #[serde(rename = "@code", default)]
+    pub code: Option<String>,
+
+    /// Level of detail
+    #[serde(rename = "@levelOfDetail", default)]
+    pub level_of_detail: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+// =============================================================================
+// Income Detail Types
+// =============================================================================
+
+/// Mutual fund dividend detail
+///
+/// Link rows to their cash postings with
+/// [`ActivityFlexStatement::mutual_fund_dividends_with_cash`](crate::types::ActivityFlexStatement::mutual_fund_dividends_with_cash).
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct MutualFundDividendDetail {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Currency
+    #[serde(rename = "@currency", default)]
+    pub currency: Option<String>,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Asset category
+    #[serde(rename = "@assetCategory", default)]
+    pub asset_category: Option<AssetCategory>,
+
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
+
+    /// ISIN
+    #[serde(rename = "@isin", default)]
+    pub isin: Option<String>,
+
+    /// Ex-dividend date
+    #[serde(
+        rename = "@exDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub ex_date: Option<NaiveDate>,
+
+    /// Pay date
+    #[serde(
+        rename = "@payDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub pay_date: Option<NaiveDate>,
+
+    /// Dividend type (e.g. Reinvest, Cash)
+    #[serde(rename = "@type", default)]
+    pub dividend_type: Option<String>,
+
+    /// Shares entitled
+    #[serde(
+        rename = "@quantity",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub quantity: Option<Decimal>,
+
+    /// Gross dividend per share
+    #[serde(
+        rename = "@grossRate",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub gross_rate: Option<Decimal>,
+
+    /// Gross amount
+    #[serde(
+        rename = "@grossAmount",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub gross_amount: Option<Decimal>,
+
+    /// Withholding tax
+    #[serde(
+        rename = "@tax",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub tax: Option<Decimal>,
+
+    /// Fee
+    #[serde(
+        rename = "@fee",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fee: Option<Decimal>,
+
+    /// Net amount
+    #[serde(
+        rename = "@netAmount",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub net_amount: Option<Decimal>,
+
+    /// Price of reinvested shares
+    #[serde(
+        rename = "@reinvestPrice",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub reinvest_price: Option<Decimal>,
+
+    /// Shares bought by reinvestment
+    #[serde(
+        rename = "@reinvestQuantity",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub reinvest_quantity: Option<Decimal>,
+
+    /// Notes/codes
+    #[serde(rename = "@code", default)]
+    pub code: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+/// Incentive coupon accrual detail
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct IncentiveCouponAccrualDetail {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Currency
+    #[serde(rename = "@currency", default)]
+    pub currency: Option<String>,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Asset category
+    #[serde(rename = "@assetCategory", default)]
+    pub asset_category: Option<AssetCategory>,
+
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
+
+    /// Report date
+    #[serde(
+        rename = "@reportDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub report_date: Option<NaiveDate>,
+
+    /// Pay date
+    #[serde(
+        rename = "@payDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub pay_date: Option<NaiveDate>,
+
+    /// Quantity
+    #[serde(
+        rename = "@quantity",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub quantity: Option<Decimal>,
+
+    /// Incentive coupon rate
+    #[serde(
+        rename = "@couponRate",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub coupon_rate: Option<Decimal>,
+
+    /// Accrued amount
+    #[serde(
+        rename = "@accrual",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub accrual: Option<Decimal>,
+
+    /// Notes/codes
+    #[serde(rename = "@code", default)]
+    pub code: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+// =============================================================================
+// Stock Grant Types
+// =============================================================================
+
+/// Stock grant (RSU) activity
+///
+/// One row per grant, vest, release or forfeiture of an award; rows without an
+/// [`activity_type`](Self::activity_type) list the vesting schedule, one row
+/// per tranche. Build per-grant vesting totals with
+/// [`ActivityFlexStatement::vesting_ledger`](crate::types::ActivityFlexStatement::vesting_ledger).
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct StockGrantActivity {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Currency
+    #[serde(rename = "@currency", default)]
+    pub currency: Option<String>,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Asset category
+    #[serde(rename = "@assetCategory", default)]
+    pub asset_category: Option<AssetCategory>,
+
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
+
+    /// Security ID
+    #[serde(rename = "@securityID", default)]
+    pub security_id: Option<String>,
+
+    /// CUSIP
+    #[serde(rename = "@cusip", default)]
+    pub cusip: Option<String>,
+
+    /// ISIN
+    #[serde(rename = "@isin", default)]
+    pub isin: Option<String>,
+
+    /// Listing exchange
+    #[serde(rename = "@listingExchange", default)]
+    pub listing_exchange: Option<String>,
+
+    /// Report date
+    #[serde(
+        rename = "@reportDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub report_date: Option<NaiveDate>,
+
+    /// Activity type (grant, vest, release, forfeiture)
+    #[serde(rename = "@type", default)]
+    pub activity_type: Option<StockGrantActivityType>,
+
+    /// Grant (award) date
+    #[serde(
+        rename = "@awardDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub award_date: Option<NaiveDate>,
+
+    /// Vesting date
+    #[serde(
+        rename = "@vestingDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub vesting_date: Option<NaiveDate>,
+
+    /// Date the vested shares were released to the account
+    #[serde(
+        rename = "@releaseDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub release_date: Option<NaiveDate>,
+
+    /// Shares granted, vested, released or forfeited
+    #[serde(
+        rename = "@quantity",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub quantity: Option<Decimal>,
+
+    /// Fair market value per share at vest
+    #[serde(
+        rename = "@price",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub price: Option<Decimal>,
+
+    /// Value (quantity x price)
+    #[serde(
+        rename = "@value",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub value: Option<Decimal>,
+
+    /// Notes/codes
+    #[serde(rename = "@code", default)]
+    pub code: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+// =============================================================================
+// Hong Kong IPO Types
+// =============================================================================
+
+/// Open Hong Kong IPO subscription
+///
+/// Pair subscriptions with their activity rows with
+/// [`ActivityFlexStatement::hk_ipo_subscriptions`](crate::types::ActivityFlexStatement::hk_ipo_subscriptions).
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct HKIPOOpenSubscription {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Currency
+    #[serde(rename = "@currency", default)]
+    pub currency: Option<String>,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Asset category
+    #[serde(rename = "@assetCategory", default)]
+    pub asset_category: Option<AssetCategory>,
+
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
+
+    /// Security ID
+    #[serde(rename = "@securityID", default)]
+    pub security_id: Option<String>,
+
+    /// ISIN
+    #[serde(rename = "@isin", default)]
+    pub isin: Option<String>,
+
+    /// Listing exchange
+    #[serde(rename = "@listingExchange", default)]
+    pub listing_exchange: Option<String>,
+
+    /// Report date
+    #[serde(
+        rename = "@reportDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub report_date: Option<NaiveDate>,
+
+    /// Date the subscription was placed
+    #[serde(
+        rename = "@subscriptionDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub subscription_date: Option<NaiveDate>,
+
+    /// Expected allotment date
+    #[serde(
+        rename = "@allotmentDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub allotment_date: Option<NaiveDate>,
+
+    /// Expected listing date
+    #[serde(
+        rename = "@listingDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub listing_date: Option<NaiveDate>,
+
+    /// Shares applied for
+    #[serde(
+        rename = "@quantity",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub quantity: Option<Decimal>,
+
+    /// Offer price per share
+    #[serde(
+        rename = "@price",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub price: Option<Decimal>,
+
+    /// Subscription fees and levies
+    #[serde(
+        rename = "@fees",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fees: Option<Decimal>,
+
+    /// Cash frozen for the subscription
+    #[serde(
+        rename = "@amount",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub amount: Option<Decimal>,
+
+    /// Notes/codes
+    #[serde(rename = "@code", default)]
+    pub code: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+/// Hong Kong IPO subscription activity (application, allotment or refund)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct HKIPOSubscriptionActivity {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Currency
+    #[serde(rename = "@currency", default)]
+    pub currency: Option<String>,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Asset category
+    #[serde(rename = "@assetCategory", default)]
+    pub asset_category: Option<AssetCategory>,
+
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
+
+    /// Security ID
+    #[serde(rename = "@securityID", default)]
+    pub security_id: Option<String>,
+
+    /// ISIN
+    #[serde(rename = "@isin", default)]
+    pub isin: Option<String>,
+
+    /// Listing exchange
+    #[serde(rename = "@listingExchange", default)]
+    pub listing_exchange: Option<String>,
+
+    /// Activity date
+    #[serde(
+        rename = "@date",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub date: Option<NaiveDate>,
+
+    /// Activity type (e.g. Applied, Allotted, Refunded)
+    #[serde(rename = "@type", default)]
+    pub activity_type: Option<HKIPOActivityType>,
+
+    /// Shares applied for or allotted
+    #[serde(
+        rename = "@quantity",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub quantity: Option<Decimal>,
+
+    /// Offer price per share
+    #[serde(
+        rename = "@price",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub price: Option<Decimal>,
+
+    /// Fees and levies
+    #[serde(
+        rename = "@fees",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fees: Option<Decimal>,
+
+    /// Cash amount (frozen, debited or refunded)
+    #[serde(
+        rename = "@amount",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub amount: Option<Decimal>,
+
+    /// Notes/codes
+    #[serde(rename = "@code", default)]
+    pub code: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+// =============================================================================
+// Pending Activity Types
+// =============================================================================
+
+/// Pending option exercise or assignment
+///
+/// IB spells the section `PendingExcercises`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PendingExercise {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Currency
+    #[serde(rename = "@currency", default)]
+    pub currency: Option<String>,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Asset category
+    #[serde(rename = "@assetCategory", default)]
+    pub asset_category: Option<AssetCategory>,
+
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
+
+    /// Underlying contract ID
+    #[serde(rename = "@underlyingConid", default)]
+    pub underlying_conid: Option<String>,
+
+    /// Underlying symbol
+    #[serde(rename = "@underlyingSymbol", default)]
+    pub underlying_symbol: Option<String>,
+
+    /// Strike
+    #[serde(
+        rename = "@strike",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub strike: Option<Decimal>,
+
+    /// Expiry
+    #[serde(
+        rename = "@expiry",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub expiry: Option<NaiveDate>,
+
+    /// Put/Call
+    #[serde(rename = "@putCall", default)]
+    pub put_call: Option<PutCall>,
+
+    /// Multiplier
+    #[serde(
+        rename = "@multiplier",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub multiplier: Option<Decimal>,
+
+    /// Date the exercise or assignment takes effect
+    #[serde(
+        rename = "@date",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub date: Option<NaiveDate>,
+
+    /// Option contracts to exercise (negative when assigned on a short position)
+    #[serde(
+        rename = "@quantity",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub quantity: Option<Decimal>,
+
+    /// Mark price
+    #[serde(
+        rename = "@markPrice",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub mark_price: Option<Decimal>,
+
+    /// Notes/codes
+    #[serde(rename = "@code", default)]
+    pub code: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+/// Trade executed but not yet booked to positions
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct UnbookedTrade {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Currency
+    #[serde(rename = "@currency", default)]
+    pub currency: Option<String>,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Asset category
+    #[serde(rename = "@assetCategory", default)]
+    pub asset_category: Option<AssetCategory>,
+
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
+
+    /// Underlying contract ID
+    #[serde(rename = "@underlyingConid", default)]
+    pub underlying_conid: Option<String>,
+
+    /// Underlying symbol
+    #[serde(rename = "@underlyingSymbol", default)]
+    pub underlying_symbol: Option<String>,
+
+    /// Strike
+    #[serde(
+        rename = "@strike",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub strike: Option<Decimal>,
+
+    /// Expiry
+    #[serde(
+        rename = "@expiry",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub expiry: Option<NaiveDate>,
+
+    /// Put/Call
+    #[serde(rename = "@putCall", default)]
+    pub put_call: Option<PutCall>,
+
+    /// Multiplier
+    #[serde(
+        rename = "@multiplier",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub multiplier: Option<Decimal>,
+
+    /// Trade ID
+    #[serde(rename = "@tradeID", default)]
+    pub trade_id: Option<String>,
+
+    /// Trade date
+    #[serde(
+        rename = "@tradeDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub trade_date: Option<NaiveDate>,
+
+    /// Date/time
+    #[serde(rename = "@dateTime", default)]
+    pub date_time: Option<String>,
+
+    /// Buy/Sell
+    #[serde(rename = "@buySell", default)]
+    pub buy_sell: Option<BuySell>,
+
+    /// Quantity (negative for sells)
+    #[serde(
+        rename = "@quantity",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub quantity: Option<Decimal>,
+
+    /// Trade price
+    #[serde(
+        rename = "@tradePrice",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub trade_price: Option<Decimal>,
+
+    /// Proceeds
+    #[serde(
+        rename = "@proceeds",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub proceeds: Option<Decimal>,
+
+    /// Exchange
+    #[serde(rename = "@exchange", default)]
+    pub exchange: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+// =============================================================================
+// Cost and Credit Types
+// =============================================================================
+
+/// Transaction tax (e.g. French/Italian financial transaction tax)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TransactionTax {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Currency
+    #[serde(rename = "@currency", default)]
+    pub currency: Option<String>,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Asset category
+    #[serde(rename = "@assetCategory", default)]
+    pub asset_category: Option<AssetCategory>,
+
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
+
+    /// ISIN
+    #[serde(rename = "@isin", default)]
+    pub isin: Option<String>,
+
+    /// Date
+    #[serde(
+        rename = "@date",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub date: Option<NaiveDate>,
+
+    /// Trade ID
+    #[serde(rename = "@tradeID", default)]
+    pub trade_id: Option<String>,
+
+    /// Tax description
+    #[serde(rename = "@taxDescription", default)]
+    pub tax_description: Option<String>,
+
+    /// Quantity
     #[serde(
-        rename = "@rate",
+        rename = "@quantity",
         default,
         deserialize_with = "deserialize_optional_decimal"
     )]
-    pub rate: Option<Decimal>,
+    pub quantity: Option<Decimal>,
 
-    /// Securities interest
+    /// Trade price
     #[serde(
-        rename = "@securitiesInterest",
+        rename = "@tradePrice",
         default,
         deserialize_with = "deserialize_optional_decimal"
     )]
-    pub securities_interest: Option<Decimal>,
+    pub trade_price: Option<Decimal>,
 
-    /// Commodities interest
+    /// Tax amount
     #[serde(
-        rename = "@commoditiesInterest",
+        rename = "@taxAmount",
         default,
         deserialize_with = "deserialize_optional_decimal"
     )]
-    pub commodities_interest: Option<Decimal>,
+    pub tax_amount: Option<Decimal>,
 
-    /// IBUKL interest
+    /// Source
+    #[serde(rename = "@source", default)]
+    pub source: Option<String>,
+
+    /// Notes/codes
+    #[serde(rename = "@code", default)]
+    pub code: Option<String>,
+
+    /// Report date
     #[serde(
-        rename = "@ibuklInterest",
+        rename = "@reportDate",
         default,
-        deserialize_with = "deserialize_optional_decimal"
+        deserialize_with = "deserialize_optional_date"
     )]
-    pub ibukl_interest: Option<Decimal>,
+    pub report_date: Option<NaiveDate>,
 
-    /// Total interest
+    /// Level of detail
+    #[serde(rename = "@levelOfDetail", default)]
+    pub level_of_detail: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+/// CFD charge (financing on contracts for difference)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CFDCharge {
+    /// Account ID
+    #[serde(rename = "@accountId")]
+    pub account_id: String,
+
+    /// Account alias
+    #[serde(rename = "@acctAlias", default)]
+    pub acct_alias: Option<String>,
+
+    /// Model
+    #[serde(rename = "@model", default)]
+    pub model: Option<String>,
+
+    /// Currency
+    #[serde(rename = "@currency", default)]
+    pub currency: Option<String>,
+
+    /// FX rate to base
     #[serde(
-        rename = "@totalInterest",
+        rename = "@fxRateToBase",
         default,
         deserialize_with = "deserialize_optional_decimal"
     )]
-    pub total_interest: Option<Decimal>,
+    pub fx_rate_to_base: Option<Decimal>,
 
-    /// Code
-    #[serde(rename = "@code", default)]
-    pub code: Option<String>,
+    /// Asset category
+    #[serde(rename = "@assetCategory", default)]
+    pub asset_category: Option<AssetCategory>,
 
-    /// From account
-    #[serde(rename = "@fromAcct", default)]
-    pub from_acct: Option<String>,
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
 
-    /// To account
-    #[serde(rename = "@toAcct", default)]
-    pub to_acct: Option<String>,
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
 
-    /// Margin balance
-    #[serde(rename = "@marginBalance", default)]
-    pub margin_balance: Option<String>,
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
 
-    // Legacy fields (may appear in older reports)
     /// Date
     #[serde(
         rename = "@date",
@@ -3449,54 +5620,42 @@ pub struct TierInterestDetail {
     )]
     pub date: Option<NaiveDate>,
 
-    /// From date
-    #[serde(
-        rename = "@fromDate",
-        default,
-        deserialize_with = "deserialize_optional_date"
-    )]
-    pub from_date: Option<NaiveDate>,
-
-    /// To date
-    #[serde(
-        rename = "@toDate",
-        default,
-        deserialize_with = "deserialize_optional_date"
-    )]
-    pub to_date: Option<NaiveDate>,
-
-    /// Balance (legacy)
+    /// Amount received
     #[serde(
-        rename = "@balance",
+        rename = "@received",
         default,
         deserialize_with = "deserialize_optional_decimal"
     )]
-    pub balance: Option<Decimal>,
+    pub received: Option<Decimal>,
 
-    /// Interest rate (legacy field name)
+    /// Amount paid
     #[serde(
-        rename = "@interestRate",
+        rename = "@paid",
         default,
         deserialize_with = "deserialize_optional_decimal"
     )]
-    pub interest_rate: Option<Decimal>,
+    pub paid: Option<Decimal>,
 
-    /// Interest (legacy)
+    /// Total charge
     #[serde(
-        rename = "@interest",
+        rename = "@total",
         default,
         deserialize_with = "deserialize_optional_decimal"
     )]
-    pub interest: Option<Decimal>,
-}
+    pub total: Option<Decimal>,
 
-// =============================================================================
-// Miscellaneous Activity Types
-// =============================================================================
+    /// Transaction ID
+    #[serde(rename = "@transactionID", default)]
+    pub transaction_id: Option<String>,
 
-/// Debit card activity
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+/// Routing commission
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
-pub struct DebitCardActivity {
+pub struct RoutingCommission {
     /// Account ID
     #[serde(rename = "@accountId")]
     pub account_id: String,
@@ -3509,6 +5668,34 @@ pub struct DebitCardActivity {
     #[serde(rename = "@model", default)]
     pub model: Option<String>,
 
+    /// Currency
+    #[serde(rename = "@currency", default)]
+    pub currency: Option<String>,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
+    /// Asset category
+    #[serde(rename = "@assetCategory", default)]
+    pub asset_category: Option<AssetCategory>,
+
+    /// Symbol
+    #[serde(rename = "@symbol", default)]
+    pub symbol: Option<String>,
+
+    /// Description
+    #[serde(rename = "@description", default)]
+    pub description: Option<String>,
+
+    /// Contract ID
+    #[serde(rename = "@conid", default)]
+    pub conid: Option<String>,
+
     /// Date
     #[serde(
         rename = "@date",
@@ -3517,46 +5704,58 @@ pub struct DebitCardActivity {
     )]
     pub date: Option<NaiveDate>,
 
-    /// Merchant
-    #[serde(rename = "@merchant", default)]
-    pub merchant: Option<String>,
+    /// Date/time
+    #[serde(rename = "@dateTime", default)]
+    pub date_time: Option<String>,
 
-    /// Category
-    #[serde(rename = "@category", default)]
-    pub category: Option<String>,
+    /// Trade ID
+    #[serde(rename = "@tradeID", default)]
+    pub trade_id: Option<String>,
 
-    /// Status
-    #[serde(rename = "@status", default)]
-    pub status: Option<String>,
+    /// Execution ID
+    #[serde(rename = "@execID", default)]
+    pub exec_id: Option<String>,
 
-    /// Transaction type
-    #[serde(rename = "@transactionType", default)]
-    pub transaction_type: Option<String>,
+    /// Buy/Sell
+    #[serde(rename = "@buySell", default)]
+    pub buy_sell: Option<String>,
 
-    /// Amount
+    /// Quantity
     #[serde(
-        rename = "@amount",
+        rename = "@quantity",
         default,
         deserialize_with = "deserialize_optional_decimal"
     )]
-    pub amount: Option<Decimal>,
+    pub quantity: Option<Decimal>,
 
-    /// Currency
-    #[serde(rename = "@currency", default)]
-    pub currency: Option<String>,
+    /// Trade price
+    #[serde(
+        rename = "@tradePrice",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub trade_price: Option<Decimal>,
 
-    /// FX rate to base
+    /// Exchange
+    #[serde(rename = "@exchange", default)]
+    pub exchange: Option<String>,
+
+    /// Routing commission
     #[serde(
-        rename = "@fxRateToBase",
+        rename = "@routingCommission",
         default,
         deserialize_with = "deserialize_optional_decimal"
     )]
-    pub fx_rate_to_base: Option<Decimal>,
+    pub routing_commission: Option<Decimal>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
-/// Sales tax
+/// Commission credit (rebates and credit redemptions)
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
-pub struct SalesTax {
+pub struct CommissionCredit {
     /// Account ID
     #[serde(rename = "@accountId")]
     pub account_id: String,
@@ -3569,6 +5768,18 @@ pub struct SalesTax {
     #[serde(rename = "@model", default)]
     pub model: Option<String>,
 
+    /// Currency
+    #[serde(rename = "@currency", default)]
+    pub currency: Option<String>,
+
+    /// FX rate to base
+    #[serde(
+        rename = "@fxRateToBase",
+        default,
+        deserialize_with = "deserialize_optional_decimal"
+    )]
+    pub fx_rate_to_base: Option<Decimal>,
+
     /// Date
     #[serde(
         rename = "@date",
@@ -3577,49 +5788,25 @@ pub struct SalesTax {
     )]
     pub date: Option<NaiveDate>,
 
-    /// Symbol
-    #[serde(rename = "@symbol", default)]
-    pub symbol: Option<String>,
-
     /// Description
     #[serde(rename = "@description", default)]
     pub description: Option<String>,
 
-    /// Contract ID
-    #[serde(rename = "@conid", default)]
-    pub conid: Option<String>,
-
-    /// Tax type
-    #[serde(rename = "@taxType", default)]
-    pub tax_type: Option<String>,
-
-    /// Tax amount
-    #[serde(
-        rename = "@taxAmount",
-        default,
-        deserialize_with = "deserialize_optional_decimal"
-    )]
-    pub tax_amount: Option<Decimal>,
-
-    /// Proceeds
+    /// Credit amount
     #[serde(
-        rename = "@proceeds",
+        rename = "@amount",
         default,
         deserialize_with = "deserialize_optional_decimal"
     )]
-    pub proceeds: Option<Decimal>,
+    pub amount: Option<Decimal>,
 
-    /// Currency
-    #[serde(rename = "@currency", default)]
-    pub currency: Option<String>,
+    /// Transaction ID
+    #[serde(rename = "@transactionID", default)]
+    pub transaction_id: Option<String>,
 
-    /// FX rate to base
-    #[serde(
-        rename = "@fxRateToBase",
-        default,
-        deserialize_with = "deserialize_optional_decimal"
-    )]
-    pub fx_rate_to_base: Option<Decimal>,
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 // =============================================================================
@@ -3708,6 +5895,10 @@ pub struct SymbolSummary {
     /// Currency
     #[serde(rename = "@currency", default)]
     pub currency: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Asset summary (aggregated trading data by asset class)
@@ -3780,6 +5971,10 @@ pub struct AssetSummary {
     /// Currency
     #[serde(rename = "@currency", default)]
     pub currency: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
 
 /// Order record
@@ -3888,4 +6083,8 @@ pub struct Order {
     /// Currency
     #[serde(rename = "@currency", default)]
     pub currency: Option<String>,
+
+    /// Attributes this type does not model, keyed by attribute name
+    #[serde(skip)]
+    pub extra_attributes: BTreeMap<String, String>,
 }
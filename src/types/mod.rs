@@ -1,26 +1,33 @@
 //! FLEX data types
 
 pub mod activity;
+pub mod borrowed;
 pub mod common;
 pub mod extended;
+pub mod raw;
 pub mod trade_confirmation;
 
 // Re-export commonly used types
 pub use activity::{
     ActivityFlexStatement, CashTransaction, CashTransactionsWrapper, ConversionRate,
-    ConversionRatesWrapper, CorporateAction, CorporateActionsWrapper, FlexQueryResponse,
-    FlexStatementsWrapper, Position, PositionsWrapper, SecuritiesInfoWrapper, SecurityInfo, Trade,
-    TradesWrapper,
+    ConversionRatesWrapper, CorporateAction, CorporateActionsWrapper, EffectivePosition,
+    FlexQueryResponse, FlexStatementsWrapper, HKIPOSubscription, Position, PositionsWrapper,
+    SecuritiesInfoWrapper, SecurityInfo, Trade, TradeLot, TradesWrapper, VestingGrant,
+    VestingTranche,
 };
+pub use borrowed::{ActivityFlexStatementRef, CashTransactionRef, PositionRef, TradeRef};
 pub use common::{
     AssetCategory, BuySell, CashTransactionType, CorporateActionType, DeliveredReceived,
-    DerivativeInfo, InOut, LevelOfDetail, LongShort, OpenClose, OptionAction, OrderType, PutCall,
-    SecurityIdType, SubCategory, ToFrom, TradeType, TransactionCode, TransferType,
+    DerivativeInfo, HKIPOActivityType, InOut, LevelOfDetail, LongShort, OpenClose, OptionAction,
+    OrderType, PutCall, SecurityIdType, StockGrantActivityType, SubCategory, ToFrom, TradeType,
+    TransactionCode, TransferType, UnknownValue,
 };
 pub use extended::{
     // Account info
     AccountInformation,
     AssetSummary,
+    // Costs and credits
+    CFDCharge,
     // Cash
     CashReportCurrency,
     // Dividends and interest
@@ -31,29 +38,45 @@ pub use extended::{
     ChangeInPositionValue,
     ClientFee,
     ClientFeesDetail,
+    CommissionCredit,
+    ComplexPosition,
     // Miscellaneous
     DebitCardActivity,
+    DepositOnHold,
     EquitySummaryByReportDateInBase,
     FIFOPerformanceSummaryUnderlying,
+    FdicInsuredDepositByBank,
     // FX
     FxLot,
+    FxPosition,
     FxTransaction,
+    HKIPOOpenSubscription,
+    HKIPOSubscriptionActivity,
     HardToBorrowDetail,
+    IBGNoteTransaction,
+    IncentiveCouponAccrualDetail,
     InterestAccrualsCurrency,
     MTDYTDPerformanceSummary,
     // Performance summaries
     MTMPerformanceSummaryUnderlying,
+    MutualFundDividendDetail,
+    NetStockPosition,
     OpenDividendAccrual,
     OptionEAE,
     Order,
+    PendingExercise,
     // Prior period
     PriorPeriodPosition,
+    RoutingCommission,
     // Securities lending
     SLBActivity,
+    SLBCollateral,
     SLBFee,
+    SLBOpenContract,
     SalesTax,
     // Statement of funds
     StatementOfFundsLine,
+    StockGrantActivity,
     // Summaries
     SymbolSummary,
     // Interest details
@@ -61,10 +84,13 @@ pub use extended::{
     // Trading
     TradeConfirm,
     TradeTransfer,
+    TransactionTax,
     // Transfers
     Transfer,
     // Fee details
+    UnbookedTrade,
     UnbundledCommissionDetail,
     UnsettledTransfer,
 };
+pub use raw::RawElement;
 pub use trade_confirmation::TradeConfirmationStatement;
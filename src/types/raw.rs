@@ -0,0 +1,36 @@
+//! Generic XML elements for data the crate does not model
+
+/// An XML element kept as raw data
+///
+/// Sections of a statement that have no typed counterpart are stored as
+/// `RawElement` trees in
+/// [`ActivityFlexStatement::unknown_sections`](crate::types::ActivityFlexStatement::unknown_sections),
+/// so new IB sections can be read before this crate models them. Parse with
+/// [`ParseOptions::with_raw_capture`](crate::parsers::ParseOptions::with_raw_capture)
+/// to keep them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RawElement {
+    /// Element name (e.g. `SoftDollars`)
+    pub name: String,
+
+    /// Attributes in document order, with entities unescaped
+    pub attributes: Vec<(String, String)>,
+
+    /// Child elements in document order
+    pub children: Vec<RawElement>,
+}
+
+impl RawElement {
+    /// Value of the attribute `name`, if present
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Child elements named `name`
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a RawElement> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+}
@@ -1,18 +1,90 @@
 //! Trade Confirmation FLEX statement types
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
+use crate::parsers::xml_utils::deserialize_optional_date;
+
+/// Top-level Trade Confirmation FLEX query response
+///
+/// Root element of a Trade Confirmation FLEX file
+/// (`<FlexQueryResponse type="TCF">`). It wraps one
+/// [`TradeConfirmationStatement`] per account.
+///
+/// **Note**: When using [`crate::parse_trade_confirmation`] or
+/// [`crate::parse_trade_confirmation_all`], this wrapper is handled
+/// automatically.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "FlexQueryResponse")]
+pub struct TradeConfirmationResponse {
+    /// Query name
+    #[serde(rename = "@queryName", default)]
+    pub query_name: Option<String>,
+
+    /// Query type (`TCF`)
+    #[serde(rename = "@type", default)]
+    pub query_type: Option<String>,
+
+    /// FlexStatements wrapper
+    #[serde(rename = "FlexStatements")]
+    pub statements: TradeConfirmationStatementsWrapper,
+}
+
+/// Wrapper for Trade Confirmation FlexStatements
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TradeConfirmationStatementsWrapper {
+    /// Count
+    #[serde(rename = "@count", default)]
+    pub count: Option<String>,
+
+    /// Flex statement(s)
+    #[serde(rename = "FlexStatement", default)]
+    pub statements: Vec<TradeConfirmationStatement>,
+}
+
 /// Trade Confirmation FLEX statement
 ///
 /// Contains real-time trade execution data from a Trade Confirmation FLEX query.
 /// This is refreshed immediately after each trade execution.
+///
+/// Statements from a `type="TCF"` query carry their executions in
+/// [`trade_confirms`](Self::trade_confirms); the legacy
+/// `<TradeConfirmationStatement>` layout uses [`trades`](Self::trades).
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct TradeConfirmationStatement {
     /// IB account number
     #[serde(rename = "@accountId")]
     pub account_id: String,
 
+    /// Statement start date
+    #[serde(
+        rename = "@fromDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub from_date: Option<NaiveDate>,
+
+    /// Statement end date
+    #[serde(
+        rename = "@toDate",
+        default,
+        deserialize_with = "deserialize_optional_date"
+    )]
+    pub to_date: Option<NaiveDate>,
+
+    /// Reporting period (e.g. `Today`)
+    #[serde(rename = "@period", default)]
+    pub period: Option<String>,
+
+    /// When the statement was generated
+    #[serde(rename = "@whenGenerated", default)]
+    pub when_generated: Option<String>,
+
     /// Trade executions
     #[serde(rename = "Trades", default)]
     pub trades: super::activity::TradesWrapper,
+
+    /// Trade confirmations (`type="TCF"` queries)
+    #[serde(rename = "TradeConfirms", default)]
+    pub trade_confirms: super::activity::TradeConfirmsWrapper,
 }
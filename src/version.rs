@@ -1,6 +1,10 @@
 //! FLEX schema version detection
 
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
 use crate::error::{ParseError, Result};
+use crate::parsers::location::{self, Origin};
 use crate::StatementType;
 
 /// FLEX schema versions supported by this library
@@ -12,10 +16,42 @@ pub enum FlexSchemaVersion {
     Unknown,
 }
 
+impl FlexSchemaVersion {
+    /// Registry of the schema versions the parsers accept
+    pub const SUPPORTED: &'static [FlexSchemaVersion] = &[FlexSchemaVersion::V3];
+
+    /// Map a `version` attribute value to a registered version
+    ///
+    /// Returns `None` for values not in [`FlexSchemaVersion::SUPPORTED`].
+    pub fn from_attribute(value: &str) -> Option<Self> {
+        Self::SUPPORTED
+            .iter()
+            .copied()
+            .find(|v| v.as_str() == Some(value.trim()))
+    }
+
+    /// The `version` attribute value for this version
+    pub fn as_str(&self) -> Option<&'static str> {
+        match self {
+            FlexSchemaVersion::V3 => Some("3"),
+            FlexSchemaVersion::Unknown => None,
+        }
+    }
+}
+
+/// Elements whose `version` attribute declares the schema version
+const VERSIONED_ELEMENTS: [&[u8]; 3] = [
+    b"FlexQueryResponse",
+    b"FlexStatement",
+    b"TradeConfirmationStatement",
+];
+
 /// Detect FLEX schema version from XML
 ///
-/// Examines the XML to identify the FLEX schema version attribute.
-/// If no version is specified or cannot be detected, defaults to V3.
+/// Reads the `version` attribute of the `FlexQueryResponse` root element or,
+/// failing that, of the first `FlexStatement`. The XML declaration's
+/// `version="1.0"` is not mistaken for a schema version. If no version is
+/// specified, defaults to V3.
 ///
 /// # Arguments
 ///
@@ -23,41 +59,143 @@ pub enum FlexSchemaVersion {
 ///
 /// # Returns
 ///
-/// * `Ok(FlexSchemaVersion)` - Detected schema version (or Unknown if undetectable)
+/// * `Ok(FlexSchemaVersion)` - Detected schema version (or Unknown if not registered)
 ///
 /// # Errors
 ///
-/// Returns `ParseError` if the XML cannot be parsed or is malformed.
+/// Returns `ParseError` if the XML is malformed before the version is found.
 ///
 /// # Example
 ///
 /// ```rust
-/// use ib_flex::version::detect_version;
+/// use ib_flex::version::{detect_version, FlexSchemaVersion};
 ///
-/// let xml = r#"<FlexQueryResponse queryName="test" type="AF" version="3">"#;
+/// let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <FlexQueryResponse queryName="test" type="AF" version="3">"#;
 /// let version = detect_version(xml).unwrap();
+/// assert_eq!(version, FlexSchemaVersion::V3);
 /// ```
 pub fn detect_version(xml: &str) -> Result<FlexSchemaVersion> {
-    // Look for version attribute in FlexQueryResponse or FlexStatement
-    if let Some(pos) = xml.find("version=\"") {
-        let version_start = pos + 9; // length of "version=\""
-        if let Some(version_end) = xml[version_start..].find('"') {
-            let version_str = &xml[version_start..version_start + version_end];
-            return match version_str {
-                "3" => Ok(FlexSchemaVersion::V3),
-                _ => Ok(FlexSchemaVersion::Unknown),
-            };
+    Ok(match declared_version(xml)? {
+        Some(value) => {
+            FlexSchemaVersion::from_attribute(&value).unwrap_or(FlexSchemaVersion::Unknown)
         }
+        // If no version attribute found, assume V3 (most common)
+        None => FlexSchemaVersion::V3,
+    })
+}
+
+/// Check that the schema version declared by the XML is supported
+///
+/// The parsers call this before deserializing; a document without a
+/// `version` attribute is treated as V3.
+///
+/// # Errors
+///
+/// Returns [`ParseError::UnsupportedSchemaVersion`] if the declared version
+/// is not in [`FlexSchemaVersion::SUPPORTED`], or `ParseError` if the XML is
+/// malformed before the version is found.
+///
+/// # Example
+///
+/// ```rust
+/// use ib_flex::version::check_version;
+/// use ib_flex::ParseError;
+///
+/// let xml = r#"<FlexQueryResponse queryName="test" type="AF" version="2">"#;
+/// assert!(matches!(check_version(xml), Err(ParseError::UnsupportedSchemaVersion(_))));
+/// ```
+pub fn check_version(xml: &str) -> Result<FlexSchemaVersion> {
+    match declared_version(xml)? {
+        Some(value) => check_attribute(&value),
+        None => Ok(FlexSchemaVersion::V3),
     }
+}
+
+/// Check the `version` attribute of a start tag, if it has one
+pub(crate) fn check_element(element: &BytesStart) -> Result<()> {
+    match version_attribute(element) {
+        Some(value) => check_attribute(&value).map(|_| ()),
+        None => Ok(()),
+    }
+}
 
-    // If no version attribute found, assume V3 (most common)
-    Ok(FlexSchemaVersion::V3)
+/// Look a declared version up in the registry
+fn check_attribute(value: &str) -> Result<FlexSchemaVersion> {
+    FlexSchemaVersion::from_attribute(value)
+        .ok_or_else(|| ParseError::UnsupportedSchemaVersion(value.to_string()))
+}
+
+/// Read the first `version` attribute of the root or first statement element
+fn declared_version(xml: &str) -> Result<Option<String>> {
+    let mut reader = Reader::from_str(xml);
+
+    loop {
+        let element = match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => e,
+            Ok(Event::Eof) => return Ok(None),
+            Ok(_) => continue,
+            Err(e) => return Err(read_error(xml, e)),
+        };
+
+        let name = element.name();
+        if !VERSIONED_ELEMENTS.contains(&name.as_ref()) {
+            continue;
+        }
+        if let Some(version) = version_attribute(&element) {
+            return Ok(Some(version));
+        }
+        // Statements come after the root; stop at the first one
+        if name.as_ref() != b"FlexQueryResponse" {
+            return Ok(None);
+        }
+    }
+}
+
+/// Unescaped `version` attribute of an element
+fn version_attribute(element: &BytesStart) -> Option<String> {
+    attribute(element, "version")
+}
+
+/// Unescaped value of an attribute, if present
+fn attribute(element: &BytesStart, name: &str) -> Option<String> {
+    element
+        .try_get_attribute(name)
+        .ok()
+        .flatten()
+        .and_then(|a| a.unescape_value().ok())
+        .map(|v| v.into_owned())
+}
+
+/// First element of the document, skipping the XML declaration and comments
+pub(crate) fn root_element(xml: &str) -> Result<Option<BytesStart<'_>>> {
+    let mut reader = Reader::from_str(xml);
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => return Ok(Some(e)),
+            Ok(Event::Eof) => return Ok(None),
+            Ok(_) => continue,
+            Err(e) => return Err(read_error(xml, e)),
+        }
+    }
+}
+
+/// Located error for a document the reader cannot get through
+fn read_error(xml: &str, error: quick_xml::Error) -> ParseError {
+    let location = location::syntax_error_location(xml, &Origin::default());
+    ParseError::XmlError {
+        message: format!("Failed to read FLEX XML: {}", error),
+        location: Some(Box::new(location)),
+    }
 }
 
 /// Detect FLEX statement type from XML
 ///
-/// Examines the XML structure to determine whether it's an Activity FLEX
-/// or Trade Confirmation FLEX statement by looking at the root element.
+/// Examines the root element to determine whether it's an Activity FLEX
+/// or Trade Confirmation FLEX statement. A `FlexQueryResponse` root is
+/// classified by its `type` attribute: `AF` is Activity and `TCF` is Trade
+/// Confirmation; a response without `type` is treated as Activity.
 ///
 /// # Arguments
 ///
@@ -73,36 +211,37 @@ pub fn detect_version(xml: &str) -> Result<FlexSchemaVersion> {
 /// ```rust
 /// use ib_flex::{detect_statement_type, StatementType};
 ///
-/// let xml = r#"<FlexQueryResponse><FlexStatements><FlexStatement ... /></FlexStatements></FlexQueryResponse>"#;
+/// let xml = r#"<FlexQueryResponse queryName="Confirms" type="TCF"><FlexStatements count="0" /></FlexQueryResponse>"#;
 /// let stmt_type = detect_statement_type(xml).unwrap();
-/// assert_eq!(stmt_type, StatementType::Activity);
+/// assert_eq!(stmt_type, StatementType::TradeConfirmation);
 /// ```
 pub fn detect_statement_type(xml: &str) -> Result<StatementType> {
-    // Remove XML declaration and whitespace for easier parsing
-    let xml_trimmed = xml
-        .trim_start()
-        .trim_start_matches("<?xml")
-        .trim_start()
-        .trim_start_matches(|c: char| c != '<');
-
-    // Check root element
-    if xml_trimmed.starts_with("<FlexQueryResponse") {
-        // Activity FLEX uses FlexQueryResponse wrapper
-        Ok(StatementType::Activity)
-    } else if xml_trimmed.starts_with("<TradeConfirmationStatement") {
-        // Trade Confirmation uses TradeConfirmationStatement
-        Ok(StatementType::TradeConfirmation)
-    } else if xml_trimmed.starts_with("<FlexStatement") {
+    let root = root_element(xml)?.ok_or_else(|| ParseError::XmlError {
+        message: "Cannot detect statement type: document has no root element".to_string(),
+        location: None,
+    })?;
+
+    match root.name().as_ref() {
+        b"FlexQueryResponse" => match attribute(&root, "type").as_deref().map(str::trim) {
+            // Activity FLEX uses FlexQueryResponse type="AF"
+            Some("AF") | None => Ok(StatementType::Activity),
+            Some("TCF") => Ok(StatementType::TradeConfirmation),
+            Some(other) => Err(ParseError::XmlError {
+                message: format!("Unknown FlexQueryResponse type: {}", other),
+                location: None,
+            }),
+        },
+        // Legacy Trade Confirmation layout
+        b"TradeConfirmationStatement" => Ok(StatementType::TradeConfirmation),
         // Direct FlexStatement is Activity FLEX
-        Ok(StatementType::Activity)
-    } else {
-        Err(ParseError::XmlError {
+        b"FlexStatement" => Ok(StatementType::Activity),
+        other => Err(ParseError::XmlError {
             message: format!(
                 "Cannot detect statement type from XML root element: {}",
-                &xml_trimmed.chars().take(100).collect::<String>()
+                String::from_utf8_lossy(other)
             ),
             location: None,
-        })
+        }),
     }
 }
 
@@ -132,6 +271,37 @@ mod tests {
         assert_eq!(version, FlexSchemaVersion::Unknown);
     }
 
+    #[test]
+    fn test_detect_version_ignores_xml_declaration() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<FlexQueryResponse queryName="test" type="AF">
+    <FlexStatements count="1">
+        <FlexStatement accountId="U123" version="3" />
+    </FlexStatements>
+</FlexQueryResponse>"#;
+        assert_eq!(detect_version(xml).unwrap(), FlexSchemaVersion::V3);
+
+        let no_version = xml.replace(r#" version="3""#, "");
+        assert_eq!(detect_version(&no_version).unwrap(), FlexSchemaVersion::V3);
+    }
+
+    #[test]
+    fn test_check_version_registry() {
+        let xml = r#"<?xml version="1.0"?><FlexQueryResponse version="4"><FlexStatements /></FlexQueryResponse>"#;
+        match check_version(xml) {
+            Err(ParseError::UnsupportedSchemaVersion(v)) => assert_eq!(v, "4"),
+            other => panic!("expected UnsupportedSchemaVersion, got {:?}", other),
+        }
+
+        let xml = xml.replace(r#"version="4""#, r#"version="3""#);
+        assert_eq!(check_version(&xml).unwrap(), FlexSchemaVersion::V3);
+        assert_eq!(
+            FlexSchemaVersion::from_attribute("3"),
+            Some(FlexSchemaVersion::V3)
+        );
+        assert_eq!(FlexSchemaVersion::from_attribute("1.0"), None);
+    }
+
     #[test]
     fn test_detect_statement_type_activity() {
         let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -152,6 +322,28 @@ mod tests {
         assert_eq!(stmt_type, StatementType::TradeConfirmation);
     }
 
+    #[test]
+    fn test_detect_statement_type_from_type_attribute() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- generated -->
+<FlexQueryResponse queryName="Confirms" type="TCF">
+    <FlexStatements count="0" />
+</FlexQueryResponse>"#;
+        assert_eq!(
+            detect_statement_type(xml).unwrap(),
+            StatementType::TradeConfirmation
+        );
+
+        let untyped = xml.replace(r#" type="TCF""#, "");
+        assert_eq!(
+            detect_statement_type(&untyped).unwrap(),
+            StatementType::Activity
+        );
+
+        let unknown = xml.replace("TCF", "XYZ");
+        assert!(detect_statement_type(&unknown).is_err());
+    }
+
     #[test]
     fn test_detect_statement_type_direct_flex_statement() {
         let xml = r#"<FlexStatement accountId="U123" fromDate="2025-01-01" toDate="2025-01-31">"#;
@@ -0,0 +1,104 @@
+//! Integration tests for multi-statement backfill files
+
+use ib_flex::{
+    parse_activity_flex_all, parse_activity_flex_isolated, stream_activity_flex, ParseError,
+};
+use std::fs::File;
+use std::io::BufReader;
+
+const BACKFILL_FIXTURE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/activity_backfill.xml"
+);
+
+/// Test streaming statements straight from a file
+#[test]
+fn test_stream_backfill_from_file() {
+    let reader = BufReader::new(File::open(BACKFILL_FIXTURE).unwrap());
+    let statements: Vec<_> = stream_activity_flex(reader)
+        .collect::<Result<_, _>>()
+        .expect("Failed to stream backfill");
+
+    let xml = std::fs::read_to_string(BACKFILL_FIXTURE).unwrap();
+    assert_eq!(statements, parse_activity_flex_all(&xml).unwrap());
+
+    let dates: Vec<String> = statements.iter().map(|s| s.to_date.to_string()).collect();
+    assert_eq!(dates, ["2025-01-01", "2025-01-02", "2025-01-03"]);
+}
+
+/// Test that a small BufReader capacity does not affect statement boundaries
+#[test]
+fn test_stream_backfill_small_buffer() {
+    let file = File::open(BACKFILL_FIXTURE).unwrap();
+    let reader = BufReader::with_capacity(16, file);
+
+    let count = stream_activity_flex(reader)
+        .inspect(|r| assert!(r.is_ok(), "Statement failed: {:?}", r))
+        .count();
+    assert_eq!(count, 3);
+}
+
+/// Test that one bad statement does not discard the rest of the backfill
+#[test]
+fn test_isolated_backfill_quarantines_bad_day() {
+    let xml = std::fs::read_to_string(BACKFILL_FIXTURE).unwrap();
+    // Corrupt the second day's trade price
+    let second = xml.match_indices("<FlexStatement ").nth(1).unwrap().0;
+    let trade = second + xml[second..].find("tradePrice=\"").unwrap() + "tradePrice=\"".len();
+    let corrupted = format!("{}oops{}", &xml[..trade], &xml[trade..]);
+
+    assert!(parse_activity_flex_all(&corrupted).is_err());
+
+    let results = parse_activity_flex_isolated(&corrupted);
+    assert_eq!(results.len(), 3);
+    assert!(results[0].result.is_ok());
+    assert!(results[2].result.is_ok());
+
+    let bad = &results[1];
+    assert!(bad.result.is_err());
+    assert_eq!(bad.index, 1);
+    assert_eq!(bad.account_id.as_deref(), Some("U1234567"));
+    assert_eq!(bad.from_date.unwrap().to_string(), "2025-01-02");
+    assert_eq!(bad.to_date.unwrap().to_string(), "2025-01-02");
+}
+
+/// Test that errors point at the offending attribute in the whole document
+#[test]
+fn test_backfill_error_location() {
+    let xml = std::fs::read_to_string(BACKFILL_FIXTURE).unwrap();
+    let second = xml.match_indices("<FlexStatement ").nth(1).unwrap().0;
+    let attr = second + xml[second..].find("tradePrice=\"").unwrap();
+    let value = attr + "tradePrice=\"".len();
+    let corrupted = format!("{}oops{}", &xml[..value], &xml[value..]);
+
+    let line = corrupted[..attr].matches('\n').count() + 1;
+    let column = attr - corrupted[..attr].rfind('\n').unwrap();
+
+    let whole = parse_activity_flex_all(&corrupted).unwrap_err();
+    let streamed = parse_activity_flex_isolated(&corrupted)
+        .remove(1)
+        .result
+        .unwrap_err();
+
+    for err in [&whole, &streamed] {
+        assert!(
+            matches!(err, ParseError::InvalidDecimal { .. }),
+            "{:?}",
+            err
+        );
+        let location = err.location().expect("error should be located");
+        assert_eq!(location.byte_offset, attr);
+        assert_eq!(location.line, line);
+        assert_eq!(location.column, column);
+        assert!(
+            location
+                .element_path
+                .starts_with("FlexStatement[1]/Trades/Trade["),
+            "{}",
+            location.element_path
+        );
+        assert_eq!(location.attribute.as_deref(), Some("tradePrice"));
+        assert!(location.raw_value.as_deref().unwrap().starts_with("oops"));
+    }
+    assert_eq!(whole.location(), streamed.location());
+}
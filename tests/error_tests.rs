@@ -1,6 +1,6 @@
 //! Error handling tests
 
-use ib_flex::parse_activity_flex;
+use ib_flex::{parse_activity_flex, parse_activity_flex_with_options, ParseError, ParseOptions};
 
 #[test]
 fn test_malformed_xml_missing_closing_tag() {
@@ -147,6 +147,12 @@ fn test_unescaped_ampersand() {
 
     let result = parse_activity_flex(xml);
     assert!(result.is_err(), "Should fail with unescaped ampersand");
+
+    let err = result.unwrap_err();
+    let location = err.location().expect("escape errors should be located");
+    assert_eq!(location.element_path, "FlexStatement[0]/Trades/Trade[0]");
+    assert_eq!(location.attribute.as_deref(), Some("symbol"));
+    assert_eq!(location.raw_value.as_deref(), Some("S&P"));
 }
 
 #[test]
@@ -208,3 +214,177 @@ fn test_very_large_number() {
     // depending on Decimal limits
     let _ = result; // Just test that it doesn't panic
 }
+
+#[test]
+fn test_lenient_mode_recovers_invalid_values() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<FlexQueryResponse queryName="Activity" type="AF">
+  <FlexStatements count="1">
+    <FlexStatement accountId="U1234567" fromDate="2025-01-15" toDate="2025-01-15"
+                   period="LastBusinessDay" whenGenerated="2025-01-15;150000">
+      <Trades>
+        <Trade accountId="U1234567" conid="123" symbol="TEST" assetCategory="STK"
+               currency="USD" tradeDate="2025-01-15" quantity="10"
+               ibCommission="not-a-number" />
+      </Trades>
+      <CashTransactions>
+        <CashTransaction accountId="U1234567" currency="USD" amount="5.00"
+                         settleDate="2025-13-45" />
+      </CashTransactions>
+    </FlexStatement>
+  </FlexStatements>
+</FlexQueryResponse>"#;
+
+    assert!(parse_activity_flex(xml).is_err());
+    assert!(parse_activity_flex_with_options(xml, &ParseOptions::strict()).is_err());
+
+    let parsed = parse_activity_flex_with_options(xml, &ParseOptions::lenient())
+        .expect("Lenient mode should recover");
+
+    let trade = &parsed.statement.trades.items[0];
+    assert_eq!(trade.commission, None);
+    assert_eq!(trade.quantity.unwrap().to_string(), "10");
+
+    let summary: Vec<String> = parsed
+        .warnings
+        .iter()
+        .map(|w| {
+            format!(
+                "{}/{}[{}]@{}={}",
+                w.section, w.element, w.element_index, w.attribute, w.raw_value
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            "Trades/Trade[0]@ibCommission=not-a-number",
+            "CashTransactions/CashTransaction[0]@settleDate=2025-13-45",
+        ]
+    );
+}
+
+#[test]
+fn test_errors_report_location() {
+    let statement = |attrs: &str, body: &str| {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<FlexQueryResponse queryName="Activity" type="AF">
+  <FlexStatements count="1">
+    <FlexStatement {} period="LastBusinessDay" whenGenerated="2025-01-15;150000">
+{}
+    </FlexStatement>
+  </FlexStatements>
+</FlexQueryResponse>"#,
+            attrs, body
+        )
+    };
+    let dates = r#"fromDate="2025-01-15" toDate="2025-01-15""#;
+
+    // Invalid date on the statement itself
+    let xml = statement(
+        r#"accountId="U1" fromDate="2025-01-xx" toDate="2025-01-15""#,
+        "",
+    );
+    let err = parse_activity_flex(&xml).unwrap_err();
+    assert!(matches!(err, ParseError::InvalidDate { .. }), "{:?}", err);
+    let location = err.location().unwrap();
+    assert_eq!(location.element_path, "FlexStatement[0]");
+    assert_eq!(location.attribute.as_deref(), Some("fromDate"));
+    assert_eq!(location.raw_value.as_deref(), Some("2025-01-xx"));
+    assert_eq!((location.line, location.column), (4, 35));
+
+    // Invalid decimal on the second trade
+    let trades = r#"      <Trades>
+        <Trade accountId="U1" conid="1" symbol="A" assetCategory="STK" currency="USD" />
+        <Trade accountId="U1" conid="2" symbol="B" assetCategory="STK" currency="USD" fxRateToBase="1,5" />
+      </Trades>"#;
+    let xml = statement(&format!(r#"accountId="U1" {}"#, dates), trades);
+    let err = parse_activity_flex(&xml).unwrap_err();
+    assert!(
+        matches!(err, ParseError::InvalidDecimal { .. }),
+        "{:?}",
+        err
+    );
+    let location = err.location().unwrap();
+    assert_eq!(location.element_path, "FlexStatement[0]/Trades/Trade[1]");
+    assert_eq!(location.attribute.as_deref(), Some("fxRateToBase"));
+    assert_eq!(location.line, 7);
+    assert_eq!(location.byte_offset, xml.find("fxRateToBase").unwrap());
+    assert!(
+        err.to_string().contains("Trade[1] @fxRateToBase=\"1,5\""),
+        "{}",
+        err
+    );
+
+    // Missing required attribute
+    let xml = statement(dates, "");
+    match parse_activity_flex(&xml).unwrap_err() {
+        ParseError::MissingField {
+            field,
+            context,
+            location,
+        } => {
+            assert_eq!(field, "accountId");
+            assert_eq!(context, "FlexStatement[0]");
+            assert_eq!(location.unwrap().line, 4);
+        }
+        other => panic!("expected MissingField, got {:?}", other),
+    }
+
+    // Mismatched closing tag
+    let xml = statement(&format!(r#"accountId="U1" {}"#, dates), "      <Trades>");
+    let err = parse_activity_flex(&xml).unwrap_err();
+    assert!(matches!(err, ParseError::XmlError { .. }), "{:?}", err);
+    let location = err.location().unwrap();
+    assert_eq!(location.element_path, "FlexStatement[0]/Trades");
+    assert_eq!(location.line, 6);
+
+    // The variant follows the failing field's type, not words in the message
+    let trades = r#"      <Trades>
+        <Trade accountId="U1" conid="1" symbol="A" assetCategory="STK" currency="USD" isAPIOrder="decimal date" />
+      </Trades>"#;
+    let xml = statement(&format!(r#"accountId="U1" {}"#, dates), trades);
+    let err = parse_activity_flex(&xml).unwrap_err();
+    assert!(matches!(err, ParseError::XmlError { .. }), "{:?}", err);
+    assert_eq!(
+        err.location().unwrap().attribute.as_deref(),
+        Some("isAPIOrder")
+    );
+
+    // Required decimals are typed as well
+    let positions = r#"      <OpenPositions>
+        <OpenPosition accountId="U1" conid="1" symbol="A" assetCategory="STK" currency="USD" reportDate="2025-01-15" position="1,5" markPrice="1" positionValue="1" />
+      </OpenPositions>"#;
+    let xml = statement(&format!(r#"accountId="U1" {}"#, dates), positions);
+    let err = parse_activity_flex(&xml).unwrap_err();
+    assert!(
+        matches!(err, ParseError::InvalidDecimal { .. }),
+        "{:?}",
+        err
+    );
+    assert_eq!(
+        err.location().unwrap().attribute.as_deref(),
+        Some("position")
+    );
+}
+
+#[test]
+fn test_unsupported_schema_version() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<FlexQueryResponse queryName="Activity" type="AF" version="2">
+  <FlexStatements count="1">
+    <FlexStatement accountId="U1234567" fromDate="2025-01-15" toDate="2025-01-15"
+                   period="LastBusinessDay" whenGenerated="2025-01-15;150000" />
+  </FlexStatements>
+</FlexQueryResponse>"#;
+
+    match parse_activity_flex(xml) {
+        Err(ParseError::UnsupportedSchemaVersion(version)) => assert_eq!(version, "2"),
+        other => panic!("expected UnsupportedSchemaVersion, got {:?}", other),
+    }
+
+    // The XML declaration's version is not a schema version
+    let supported = xml.replace(r#" version="2""#, r#" version="3""#);
+    assert!(parse_activity_flex(&supported).is_ok());
+}
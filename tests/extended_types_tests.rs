@@ -275,3 +275,519 @@ fn test_extended_types_default_to_empty() {
     assert_eq!(statement.interest_accruals.items.len(), 0);
     assert_eq!(statement.transfers.items.len(), 0);
 }
+
+#[test]
+fn test_cost_and_credit_sections() {
+    let xml = include_str!("fixtures/activity_costs.xml");
+    let statement = parse_activity_flex(xml).expect("Failed to parse");
+
+    assert_eq!(statement.transaction_taxes.items.len(), 2);
+    let ftt = &statement.transaction_taxes.items[1];
+    assert_eq!(ftt.symbol.as_deref(), Some("ENI"));
+    assert_eq!(
+        ftt.tax_description.as_deref(),
+        Some("Italian Financial Transaction Tax")
+    );
+    assert_eq!(ftt.tax_amount, Some(Decimal::from_str("-6.90").unwrap()));
+    assert_eq!(ftt.date.unwrap().to_string(), "2025-01-21");
+
+    let total_tax: Decimal = statement
+        .transaction_taxes
+        .items
+        .iter()
+        .filter_map(|t| t.tax_amount)
+        .sum();
+    assert_eq!(total_tax, Decimal::from_str("-26.41").unwrap());
+
+    let cfd = &statement.cfd_charges.items[0];
+    assert_eq!(cfd.currency.as_deref(), Some("GBP"));
+    assert_eq!(cfd.paid, Some(Decimal::from_str("-1.84").unwrap()));
+    assert_eq!(cfd.total, Some(Decimal::from_str("-1.84").unwrap()));
+
+    let routing = &statement.routing_commissions.items[0];
+    assert_eq!(routing.exchange.as_deref(), Some("ARCA"));
+    assert_eq!(
+        routing.routing_commission,
+        Some(Decimal::from_str("-0.60").unwrap())
+    );
+
+    let credit = &statement.commission_credits.items[0];
+    assert_eq!(credit.amount, Some(Decimal::from_str("12.40").unwrap()));
+    assert_eq!(credit.transaction_id.as_deref(), Some("6660002"));
+}
+
+#[test]
+fn test_slb_open_contracts_and_collateral() {
+    let xml = include_str!("fixtures/activity_slb.xml");
+    let statement = parse_activity_flex(xml).expect("Failed to parse");
+
+    assert_eq!(statement.slb_open_contracts.items.len(), 2);
+    let contract = &statement.slb_open_contracts.items[0];
+    assert_eq!(contract.conid.as_deref(), Some("265598"));
+    assert_eq!(contract.quantity, Some(Decimal::from(300)));
+    assert_eq!(contract.fee_rate, Some(Decimal::from_str("0.25").unwrap()));
+    assert_eq!(contract.start_date.unwrap().to_string(), "2024-12-02");
+    assert_eq!(contract.currency.as_deref(), Some("USD"));
+
+    let collateral = &statement.slb_collaterals.items[0];
+    assert_eq!(
+        collateral.collateral_amount,
+        Some(Decimal::from_str("56000.00").unwrap())
+    );
+    assert_eq!(collateral.collateral_rate, Some(Decimal::from(102)));
+
+    let joined = statement.slb_open_contract_positions();
+    assert_eq!(joined.len(), 2);
+    assert_eq!(joined[0].1.len(), 1);
+    assert_eq!(joined[0].1[0].symbol, "AAPL");
+    // The fixture has no GME position to join to
+    assert_eq!(joined[1].0.symbol.as_deref(), Some("GME"));
+    assert!(joined[1].1.is_empty());
+}
+
+/// Replace the AAPL summary row of `xml` with one lot row per quantity,
+/// keeping the summary row ahead of the lots if `keep_summary`
+fn aapl_lots(xml: &str, quantities: &[&str], keep_summary: bool) -> String {
+    let start = xml.find("<OpenPosition ").unwrap();
+    let end = start + xml[start..].find("/>\n").unwrap() + 3;
+    let summary = &xml[start..end];
+    assert!(summary.contains(r#"conid="265598""#));
+
+    let lots: String = quantities
+        .iter()
+        .map(|quantity| {
+            summary
+                .replace(r#"levelOfDetail="SUMMARY""#, r#"levelOfDetail="LOT""#)
+                .replace(r#"position="500""#, &format!(r#"position="{}""#, quantity))
+        })
+        .collect();
+    let summary = if keep_summary { summary } else { "" };
+    format!("{}{}{}{}", &xml[..start], summary, lots, &xml[end..])
+}
+
+#[test]
+fn test_slb_open_contract_joins_every_lot() {
+    let xml = aapl_lots(
+        include_str!("fixtures/activity_slb.xml"),
+        &["300", "200"],
+        false,
+    );
+    let statement = parse_activity_flex(&xml).expect("Failed to parse");
+
+    let joined = statement.slb_open_contract_positions();
+    let lots: Vec<_> = joined[0].1.iter().map(|p| p.quantity).collect();
+    assert_eq!(lots, [Decimal::from(300), Decimal::from(200)]);
+    assert!(joined[0]
+        .1
+        .iter()
+        .all(|p| p.level_of_detail == Some(ib_flex::types::LevelOfDetail::Lot)));
+}
+
+#[test]
+fn test_pending_exercises_and_unbooked_trades() {
+    let xml = include_str!("fixtures/activity_pending.xml");
+    let statement = parse_activity_flex(xml).expect("Failed to parse");
+
+    let exercise = &statement.pending_exercises.items[0];
+    assert_eq!(exercise.underlying_conid.as_deref(), Some("265598"));
+    assert_eq!(exercise.put_call, Some(ib_flex::PutCall::Call));
+    assert_eq!(exercise.quantity, Some(Decimal::from(20)));
+    assert_eq!(statement.unbooked_trades.items.len(), 2);
+    assert_eq!(
+        statement.unbooked_trades.items[0].buy_sell,
+        Some(ib_flex::BuySell::Sell)
+    );
+
+    let positions = statement.effective_next_day_positions();
+    assert_eq!(positions.len(), 3);
+
+    // 500 shares + 20 calls x 100 exercised - 300 sold overnight
+    let aapl = &positions[0];
+    assert_eq!(aapl.conid, "265598");
+    assert_eq!(aapl.reported_quantity, Decimal::from(500));
+    assert_eq!(aapl.quantity, Decimal::from(2200));
+
+    // 50 calls, 20 of them exercised
+    assert_eq!(positions[1].quantity, Decimal::from(30));
+
+    // Bought overnight, not yet in OpenPositions
+    let nvda = &positions[2];
+    assert_eq!(nvda.symbol, "NVDA");
+    assert_eq!(nvda.reported_quantity, Decimal::ZERO);
+    assert_eq!(nvda.quantity, Decimal::from(25));
+}
+
+#[test]
+fn test_effective_positions_sum_lots() {
+    let fixture = include_str!("fixtures/activity_pending.xml");
+    let expected = parse_activity_flex(fixture)
+        .unwrap()
+        .effective_next_day_positions();
+
+    // Lots only: summed per conid
+    let xml = aapl_lots(fixture, &["300", "200"], false);
+    let statement = parse_activity_flex(&xml).expect("Failed to parse");
+    assert_eq!(statement.positions.items.len(), 3);
+    assert_eq!(statement.effective_next_day_positions(), expected);
+
+    // Summary and lots: the summary already holds the total
+    let xml = aapl_lots(fixture, &["300", "200"], true);
+    let statement = parse_activity_flex(&xml).expect("Failed to parse");
+    assert_eq!(statement.positions.items.len(), 4);
+    assert_eq!(statement.effective_next_day_positions(), expected);
+}
+
+#[test]
+fn test_position_style_sections() {
+    let xml = include_str!("fixtures/activity_position_sections.xml");
+    let statement = parse_activity_flex(xml).expect("Failed to parse");
+
+    let net = &statement.net_stock_positions.items[0];
+    assert_eq!(net.shares_lent, Some(Decimal::from(300)));
+    assert_eq!(net.net_shares, Some(Decimal::from(200)));
+    assert_eq!(statement.net_stock_position_summary.items.len(), 1);
+
+    let combo = &statement.complex_positions.items[0];
+    assert_eq!(combo.underlying_symbol.as_deref(), Some("SPY"));
+    assert_eq!(combo.quantity, Some(Decimal::from(5)));
+    assert_eq!(
+        combo.fifo_pnl_unrealized,
+        Some(Decimal::from_str("250.00").unwrap())
+    );
+
+    assert_eq!(statement.fx_positions.items.len(), 2);
+    let eur = &statement.fx_positions.items[0];
+    assert_eq!(eur.asset_category, Some(ib_flex::AssetCategory::Cash));
+    assert_eq!(eur.fx_currency.as_deref(), Some("EUR"));
+    assert_eq!(
+        eur.cost_basis,
+        Some(Decimal::from_str("-26030.00").unwrap())
+    );
+    let unrealized: Decimal = statement
+        .fx_positions
+        .items
+        .iter()
+        .filter_map(|p| p.unrealized_pl)
+        .sum();
+    assert_eq!(unrealized, Decimal::from_str("-447.00").unwrap());
+}
+
+#[test]
+fn test_deposit_sections() {
+    let xml = include_str!("fixtures/activity_deposits.xml");
+    let statement = parse_activity_flex(xml).expect("Failed to parse");
+
+    let hold = &statement.deposits_on_hold.items[0];
+    assert_eq!(hold.currency, "USD");
+    assert_eq!(hold.amount, Decimal::from_str("25000.00").unwrap());
+    assert_eq!(hold.date.to_string(), "2025-01-14");
+    assert_eq!(hold.hold_release_date.unwrap().to_string(), "2025-01-21");
+
+    let swept: Decimal = statement
+        .fdic_insured_deposits
+        .items
+        .iter()
+        .map(|d| d.amount)
+        .sum();
+    assert_eq!(swept, Decimal::from_str("350000.00").unwrap());
+    assert_eq!(
+        statement.fdic_insured_deposits.items[1]
+            .bank_name
+            .as_deref(),
+        Some("SECOND PROGRAM BANK")
+    );
+
+    let note = &statement.ibg_note_transactions.items[0];
+    assert_eq!(note.transaction_type.as_deref(), Some("Purchase"));
+    assert_eq!(note.amount, Decimal::from_str("-10000.00").unwrap());
+    assert_eq!(note.date.to_string(), "2025-01-15");
+}
+
+#[test]
+fn test_mutual_fund_dividends_link_to_cash() {
+    let xml = include_str!("fixtures/activity_fund_income.xml");
+    let statement = parse_activity_flex(xml).expect("Failed to parse");
+
+    let reinvested = &statement.mutual_fund_dividends.items[0];
+    assert_eq!(reinvested.dividend_type.as_deref(), Some("Reinvest"));
+    assert_eq!(
+        reinvested.gross_rate,
+        Some(Decimal::from_str("1.7452").unwrap())
+    );
+    assert_eq!(reinvested.pay_date.unwrap().to_string(), "2025-01-28");
+
+    let linked = statement.mutual_fund_dividends_with_cash();
+    assert_eq!(linked.len(), 2);
+    // Dividend and its withholding tax
+    assert_eq!(linked[0].1.len(), 2);
+    let cash: Decimal = linked[0].1.iter().map(|c| c.amount).sum();
+    assert_eq!(Some(cash), linked[0].0.net_amount);
+    assert_eq!(linked[1].1.len(), 1);
+    assert_eq!(linked[1].1[0].transaction_id.as_deref(), Some("9990003"));
+
+    let accrual = &statement.incentive_coupon_accruals.items[0];
+    assert_eq!(
+        accrual.coupon_rate,
+        Some(Decimal::from_str("0.50").unwrap())
+    );
+    assert_eq!(accrual.accrual, Some(Decimal::from_str("4.17").unwrap()));
+}
+
+#[test]
+fn test_stock_grant_vesting_ledger() {
+    let xml = include_str!("fixtures/activity_stock_grants.xml");
+    let statement = parse_activity_flex(xml).expect("Failed to parse");
+
+    let first = &statement.stock_grant_activities.items[0];
+    assert_eq!(first.award_date.unwrap().to_string(), "2024-08-15");
+    assert_eq!(first.vesting_date.unwrap().to_string(), "2024-11-15");
+    assert_eq!(first.price, Some(Decimal::from_str("110.00").unwrap()));
+
+    let ledger = statement.vesting_ledger(statement.to_date);
+    assert_eq!(ledger.len(), 2);
+
+    let grant = &ledger[0];
+    assert_eq!(grant.tranches.len(), 3);
+    assert_eq!(grant.vested_quantity, Decimal::from(100));
+    assert_eq!(grant.unvested_quantity, Decimal::from(50));
+    // 5500 reported + 50 x 124.50 computed
+    assert_eq!(
+        grant.vested_cost_basis,
+        Decimal::from_str("11725.00").unwrap()
+    );
+    assert_eq!(
+        grant.tranches[1].fmv_at_vest(),
+        Some(Decimal::from_str("124.50").unwrap())
+    );
+    assert_eq!(grant.tranches[2].cost_basis(), None);
+
+    // The unvested tranche links to the restricted position
+    assert!(grant.tranches[0].position.is_none());
+    assert_eq!(
+        grant.tranches[2].position.map(|p| p.quantity),
+        Some(Decimal::from(50))
+    );
+
+    assert_eq!(ledger[1].vested_quantity, Decimal::ZERO);
+    assert_eq!(ledger[1].unvested_quantity, Decimal::from(200));
+
+    // Everything has vested a year later
+    let later = statement.vesting_ledger(chrono::NaiveDate::from_ymd_opt(2026, 6, 1).unwrap());
+    assert_eq!(later[1].vested_quantity, Decimal::from(200));
+}
+
+#[test]
+fn test_stock_grant_events_ledger() {
+    use ib_flex::types::StockGrantActivityType;
+
+    let xml = include_str!("fixtures/activity_stock_grant_events.xml");
+    let statement = parse_activity_flex(xml).expect("Failed to parse");
+
+    let release = &statement.stock_grant_activities.items[2];
+    assert_eq!(release.activity_type, Some(StockGrantActivityType::Release));
+    assert_eq!(release.release_date.unwrap().to_string(), "2025-03-03");
+
+    let ledger = statement.vesting_ledger(statement.to_date);
+    assert_eq!(ledger.len(), 2);
+
+    // The grant row is not a tranche and the release joins its vest row
+    let grant = &ledger[0];
+    assert_eq!(grant.grants.len(), 1);
+    assert_eq!(grant.tranches.len(), 1);
+    assert_eq!(grant.granted_quantity, Decimal::from(200));
+    assert_eq!(grant.vested_quantity, Decimal::from(50));
+    assert_eq!(grant.forfeited_quantity, Decimal::from(25));
+    assert_eq!(grant.unvested_quantity, Decimal::from(125));
+    assert_eq!(
+        grant.vested_cost_basis,
+        Decimal::from_str("6500.00").unwrap()
+    );
+    let tranche = &grant.tranches[0];
+    assert_eq!(
+        tranche.fmv_at_vest(),
+        Some(Decimal::from_str("130.00").unwrap())
+    );
+    assert_eq!(tranche.release_date().unwrap().to_string(), "2025-03-03");
+
+    // A release row listed before its vest row
+    let grant = &ledger[1];
+    assert_eq!(grant.tranches.len(), 1);
+    assert_eq!(grant.vested_quantity, Decimal::from(25));
+    assert_eq!(grant.unvested_quantity, Decimal::from(75));
+    let tranche = &grant.tranches[0];
+    assert_eq!(
+        tranche.activity.activity_type,
+        Some(StockGrantActivityType::Vest)
+    );
+    assert_eq!(
+        tranche.cost_basis(),
+        Some(Decimal::from_str("2987.50").unwrap())
+    );
+    assert_eq!(tranche.release_date().unwrap().to_string(), "2025-02-04");
+}
+
+#[test]
+fn test_hk_ipo_subscriptions() {
+    let xml = include_str!("fixtures/activity_hk_ipo.xml");
+    let statement = parse_activity_flex(xml).expect("Failed to parse");
+
+    let open = &statement.hk_ipo_open_subscriptions.items[0];
+    assert_eq!(open.listing_date.unwrap().to_string(), "2025-04-04");
+    assert_eq!(open.amount, Some(Decimal::from_str("25252.53").unwrap()));
+    assert_eq!(statement.hk_ipo_subscription_activity.items.len(), 4);
+
+    let subscriptions = statement.hk_ipo_subscriptions();
+    assert_eq!(subscriptions.len(), 2);
+
+    let pending = &subscriptions[0];
+    assert_eq!(pending.conid, "760001234");
+    assert!(pending.subscription.is_some());
+    assert_eq!(pending.applied.len(), 1);
+    assert!(pending.allotted.is_empty());
+
+    // No longer open, reconstructed from activity alone
+    let done = &subscriptions[1];
+    assert!(done.subscription.is_none());
+    assert_eq!(done.allotted_quantity(), Decimal::from(400));
+    assert_eq!(
+        done.refunded_amount(),
+        Decimal::from_str("18181.82").unwrap()
+    );
+    let frozen: Decimal = done.applied.iter().filter_map(|a| a.amount).sum();
+    let debited: Decimal = done.allotted.iter().filter_map(|a| a.amount).sum();
+    assert_eq!(-frozen, done.refunded_amount() - debited);
+}
+
+#[test]
+fn test_hk_ipo_subscriptions_unknown_type() {
+    use ib_flex::types::{HKIPOActivityType, UnknownValue};
+
+    let xml = include_str!("fixtures/activity_hk_ipo.xml")
+        .replace(r#"type="Refunded""#, r#"type="Cancelled""#);
+    let statement = parse_activity_flex(&xml).expect("Failed to parse");
+
+    let done = &statement.hk_ipo_subscriptions()[1];
+    assert!(done.refunded.is_empty());
+    assert_eq!(done.other.len(), 1);
+    assert_eq!(
+        done.other[0].activity_type,
+        Some(HKIPOActivityType::Unknown("Cancelled".to_string()))
+    );
+    assert_eq!(
+        statement.unknown_values,
+        vec![UnknownValue {
+            enum_name: "HKIPOActivityType",
+            value: "Cancelled".to_string(),
+            occurrences: 1,
+        }]
+    );
+}
+
+#[test]
+fn test_unknown_sections_and_attributes() {
+    let xml = include_str!("fixtures/activity_unknown_data.xml");
+    let options = ib_flex::ParseOptions::default().with_raw_capture();
+    let statements: Vec<_> = ib_flex::parse_activity_flex_all_with_options(xml, &options)
+        .expect("Failed to parse")
+        .into_iter()
+        .map(|parsed| parsed.statement)
+        .collect();
+    assert_eq!(statements.len(), 2);
+
+    let first = &statements[0];
+    let info = first.account_information.as_ref().unwrap();
+    assert_eq!(info.extra_attributes["riskProfile"], "Moderate");
+    assert_eq!(
+        first.trades.items[0].extra_attributes["settlementVenue"],
+        "DTC"
+    );
+    assert_eq!(
+        first.trades.lots[0].lot.extra_attributes["lotMethod"],
+        "SpecificLot"
+    );
+    // Modeled attributes are not repeated
+    assert!(!first.trades.items[0]
+        .extra_attributes
+        .contains_key("tradePrice"));
+
+    assert_eq!(first.unknown_sections.len(), 1);
+    let soft_dollars = &first.unknown_sections[0];
+    assert_eq!(soft_dollars.name, "SoftDollars");
+    assert_eq!(soft_dollars.children[0].attribute("amount"), Some("12.50"));
+
+    // Each statement keeps its own unknown data
+    let second = &statements[1];
+    assert_eq!(
+        second.cash_transactions.items[0].extra_attributes["payerName"],
+        "Smith & Co"
+    );
+    let offsets = &second.unknown_sections[0];
+    assert_eq!(offsets.name, "CarbonOffsets");
+    let certificate = offsets.children[0].children_named("Certificate").next();
+    assert_eq!(certificate.and_then(|c| c.attribute("id")), Some("CO-1"));
+
+    // Capture is opt-in
+    let plain = ib_flex::parse_activity_flex_all(xml).expect("Failed to parse");
+    assert!(plain[0].unknown_sections.is_empty());
+    assert!(plain[0].trades.items[0].extra_attributes.is_empty());
+}
+
+#[test]
+fn test_unknown_enum_values_inventory() {
+    use ib_flex::types::{AssetCategory, CashTransactionType, TransactionCode, UnknownValue};
+
+    let xml = include_str!("fixtures/activity_unknown_values.xml");
+    let statements = ib_flex::parse_activity_flex_all(xml).expect("Failed to parse");
+
+    let first = &statements[0];
+    let trade = &first.trades.items[0];
+    assert_eq!(
+        trade.asset_category,
+        AssetCategory::Unknown("TOKEN".to_string())
+    );
+    assert_eq!(
+        trade.notes.as_deref(),
+        Some(
+            &[
+                TransactionCode::Opening,
+                TransactionCode::Unknown("Zz".to_string())
+            ][..]
+        )
+    );
+    assert_eq!(
+        first.cash_transactions.items[0].transaction_type,
+        Some(CashTransactionType::Unknown("Staking Rewards".to_string()))
+    );
+
+    assert_eq!(
+        first.unknown_values,
+        vec![
+            UnknownValue {
+                enum_name: "AssetCategory",
+                value: "TOKEN".to_string(),
+                occurrences: 2,
+            },
+            UnknownValue {
+                enum_name: "TransactionCode",
+                value: "Zz".to_string(),
+                occurrences: 1,
+            },
+            UnknownValue {
+                enum_name: "CashTransactionType",
+                value: "Staking Rewards".to_string(),
+                occurrences: 1,
+            },
+        ]
+    );
+    assert!(statements[1].unknown_values.is_empty());
+
+    // Raw values survive a serialization round trip
+    let json = serde_plain::to_string(&trade.asset_category).unwrap();
+    assert_eq!(json, "TOKEN");
+
+    // The streaming parser collects the same values
+    let streamed = ib_flex::parse_activity_flex_all_with_options(xml, &Default::default())
+        .expect("Failed to parse");
+    assert_eq!(streamed[0].statement.unknown_values, first.unknown_values);
+    assert!(streamed[1].statement.unknown_values.is_empty());
+}
@@ -1,7 +1,13 @@
 //! Integration tests for ib-flex parser
 
+use ib_flex::parsers::xml_utils::exchange_time_to_utc;
+use ib_flex::parsers::{DateFormat, DateFormatProfile};
 use ib_flex::types::{CashTransactionType, CorporateActionType};
-use ib_flex::{parse_activity_flex, AssetCategory, BuySell, OpenClose, PutCall};
+use ib_flex::{
+    detect_statement_type, parse_activity_flex, parse_activity_flex_with_options, parse_flex,
+    parse_trade_confirmation_all, AssetCategory, BuySell, FlexDocument, OpenClose, ParseError,
+    ParseOptions, PutCall, StatementType,
+};
 
 #[test]
 fn test_parse_minimal_statement() {
@@ -31,6 +37,204 @@ fn test_parse_trades() {
     assert_eq!(trade.trade_price.unwrap().to_string(), "185.50");
 }
 
+#[test]
+fn test_trade_timestamps() {
+    let xml = r#"<FlexQueryResponse queryName="Activity" type="AF">
+  <FlexStatements count="1">
+    <FlexStatement accountId="U1234567" fromDate="2025-01-15" toDate="2025-01-15"
+                   whenGenerated="2025-01-31;235959">
+      <Trades>
+        <Trade accountId="U1234567" conid="265598" symbol="AAPL" assetCategory="STK"
+               currency="USD" orderTime="20250115;093000" dateTime="2025-01-15 09:30:15"
+               whenRealized="" openDateTime="" holdingPeriodDateTime="2025-01-15T09:30:15" />
+      </Trades>
+      <CashTransactions>
+        <CashTransaction accountId="U1234567" currency="USD" amount="10000.00"
+                         dateTime="2025-01-02;120000" />
+      </CashTransactions>
+    </FlexStatement>
+  </FlexStatements>
+</FlexQueryResponse>"#;
+    let statement = parse_activity_flex(xml).unwrap();
+
+    assert_eq!(
+        statement.when_generated_datetime().unwrap().to_string(),
+        "2025-01-31 23:59:59"
+    );
+
+    let profile = statement.date_format;
+    let trade = &statement.trades.items[0];
+    assert_eq!(
+        trade.trade_datetime(profile).unwrap().to_string(),
+        "2025-01-15 09:30:15"
+    );
+    assert_eq!(
+        trade.order_datetime(profile).unwrap().to_string(),
+        "2025-01-15 09:30:00"
+    );
+    assert_eq!(
+        trade.holding_period_datetime(profile),
+        trade.trade_datetime(profile)
+    );
+    // Empty attributes are absent, not errors
+    assert_eq!(trade.when_realized_datetime(profile), None);
+    assert_eq!(trade.open_datetime(profile), None);
+
+    let utc = exchange_time_to_utc(trade.trade_datetime(profile).unwrap()).unwrap();
+    assert_eq!(utc.to_rfc3339(), "2025-01-15T14:30:15+00:00");
+
+    let deposit = &statement.cash_transactions.items[0];
+    assert_eq!(
+        deposit.datetime(profile).unwrap().to_string(),
+        "2025-01-02 12:00:00"
+    );
+}
+
+/// Build a one-statement document using the given date values
+fn legacy_date_xml(from: &str, to: &str, generated: &str, trade_date: &str) -> String {
+    format!(
+        r#"<FlexQueryResponse queryName="Legacy" type="AF">
+  <FlexStatements count="1">
+    <FlexStatement accountId="U1234567" fromDate="{from}" toDate="{to}" whenGenerated="{generated}">
+      <Trades>
+        <Trade accountId="U1234567" conid="265598" symbol="AAPL" assetCategory="STK"
+               currency="USD" tradeDate="{trade_date}" dateTime="{trade_date};093015" />
+      </Trades>
+    </FlexStatement>
+  </FlexStatements>
+</FlexQueryResponse>"#
+    )
+}
+
+#[test]
+fn test_detect_legacy_date_formats() {
+    // Day above 12 gives dd/MM/yyyy away
+    let xml = legacy_date_xml(
+        "01/12/2024",
+        "31/12/2024",
+        "02/01/2025;080000",
+        "13/12/2024",
+    );
+    let statement = parse_activity_flex(&xml).unwrap();
+    assert_eq!(statement.from_date.to_string(), "2024-12-01");
+    assert_eq!(statement.to_date.to_string(), "2024-12-31");
+    assert_eq!(
+        statement.trades.items[0].trade_date.unwrap().to_string(),
+        "2024-12-13"
+    );
+
+    // No day above 12: only month-first has the statement generated after its period
+    let xml = legacy_date_xml("01/02/25", "01/05/25", "02/01/25;080000", "01/03/25");
+    let statement = parse_activity_flex(&xml).unwrap();
+    assert_eq!(statement.to_date.to_string(), "2025-01-05");
+
+    let xml = legacy_date_xml("15-OCT-24", "15-OCT-24", "16-OCT-24;080000", "15-OCT-24");
+    let statement = parse_activity_flex(&xml).unwrap();
+    assert_eq!(statement.from_date.to_string(), "2024-10-15");
+}
+
+#[test]
+fn test_ambiguous_dates_need_profile() {
+    let xml = legacy_date_xml(
+        "01/02/2025",
+        "01/02/2025",
+        "01/02/2025;200000",
+        "01/02/2025",
+    );
+    let err = parse_activity_flex(&xml).unwrap_err();
+    assert!(matches!(err, ParseError::InvalidDate { .. }), "{:?}", err);
+
+    let options =
+        ParseOptions::default().with_date_format(DateFormatProfile::new(DateFormat::DayMonthYear));
+    let parsed = parse_activity_flex_with_options(&xml, &options).unwrap();
+    assert_eq!(parsed.statement.from_date.to_string(), "2025-02-01");
+}
+
+#[test]
+fn test_non_date_failure_skips_date_detection() {
+    // The timestamp looks like an ambiguous legacy date, but the failure is
+    // the decimal, which is reported as such
+    let xml = legacy_date_xml(
+        "2025-01-02",
+        "2025-01-02",
+        "2025-01-03;080000",
+        "2025-01-02",
+    )
+    .replace(
+        r#"dateTime="2025-01-02;093015""#,
+        r#"dateTime="01/02/2025;093015" tradePrice="abc""#,
+    );
+    let err = parse_activity_flex(&xml).unwrap_err();
+    assert!(
+        matches!(err, ParseError::InvalidDecimal { .. }),
+        "{:?}",
+        err
+    );
+    assert_eq!(
+        err.location().unwrap().attribute.as_deref(),
+        Some("tradePrice")
+    );
+
+    // A date failure no profile can be detected for keeps its location
+    let xml = legacy_date_xml(
+        "01/02/2025",
+        "01/02/2025",
+        "01/02/2025;200000",
+        "01/02/2025",
+    );
+    let err = parse_activity_flex(&xml).unwrap_err();
+    assert!(matches!(err, ParseError::InvalidDate { .. }), "{:?}", err);
+    assert_eq!(
+        err.location().unwrap().attribute.as_deref(),
+        Some("fromDate")
+    );
+}
+
+#[test]
+fn test_timestamps_use_statement_date_format() {
+    let xml = legacy_date_xml(
+        "01/15/2025",
+        "01/15/2025",
+        "01/16/2025;093000",
+        "01/15/2025",
+    );
+    let statement = parse_activity_flex(&xml).unwrap();
+    assert_eq!(
+        statement.date_format.map(|p| p.date),
+        Some(DateFormat::MonthDayYear)
+    );
+    assert_eq!(
+        statement.when_generated_datetime().unwrap().to_string(),
+        "2025-01-16 09:30:00"
+    );
+    let trade = &statement.trades.items[0];
+    assert_eq!(
+        trade
+            .trade_datetime(statement.date_format)
+            .unwrap()
+            .to_string(),
+        "2025-01-15 09:30:15"
+    );
+
+    // An explicit profile is kept as well
+    let xml = legacy_date_xml(
+        "01/02/2025",
+        "01/02/2025",
+        "01/02/2025;200000",
+        "01/02/2025",
+    );
+    let options =
+        ParseOptions::default().with_date_format(DateFormatProfile::new(DateFormat::DayMonthYear));
+    let parsed = parse_activity_flex_with_options(&xml, &options).unwrap();
+    assert_eq!(
+        parsed.statement.trades.items[0]
+            .trade_datetime(parsed.statement.date_format)
+            .unwrap()
+            .to_string(),
+        "2025-02-01 09:30:15"
+    );
+}
+
 #[test]
 fn test_empty_sections() {
     let xml = include_str!("fixtures/activity_minimal.xml");
@@ -692,3 +896,178 @@ fn test_parse_rights_issue() {
         Some(CorporateActionType::SubscribeRights)
     );
 }
+
+#[test]
+fn test_parse_trade_confirmation_tcf() {
+    let xml = include_str!("fixtures/trade_confirmation_tcf.xml");
+    assert_eq!(
+        detect_statement_type(xml).unwrap(),
+        StatementType::TradeConfirmation
+    );
+
+    let statements = parse_trade_confirmation_all(xml).unwrap();
+    assert_eq!(statements.len(), 2);
+    assert_eq!(statements[0].account_id, "U1234567");
+    assert_eq!(statements[0].trade_confirms.items.len(), 2);
+    assert_eq!(statements[1].account_id, "U7654321");
+    assert_eq!(statements[1].trade_confirms.items.len(), 1);
+
+    let stock = &statements[0].trade_confirms.items[0];
+    assert_eq!(stock.asset_category, AssetCategory::Stock);
+    assert_eq!(stock.trade_price.to_string(), "150.50");
+    assert_eq!(stock.commission.unwrap().to_string(), "-1.00");
+    assert_eq!(stock.commission_currency.as_deref(), Some("USD"));
+    assert_eq!(
+        stock.broker_execution_commission.unwrap().to_string(),
+        "-0.35"
+    );
+    assert_eq!(stock.regulatory_fee.unwrap().to_string(), "-0.02");
+
+    let option = &statements[0].trade_confirms.items[1];
+    assert_eq!(option.asset_category, AssetCategory::Option);
+    assert_eq!(option.away_broker_commission.unwrap().to_string(), "-0.50");
+    assert_eq!(option.expiry.unwrap().to_string(), "2025-02-21");
+}
+
+#[test]
+fn test_parse_flex_detects_type() {
+    let document = parse_flex(include_str!("fixtures/trade_confirmation_tcf.xml")).unwrap();
+    assert_eq!(document.statement_type(), StatementType::TradeConfirmation);
+    assert_eq!(document.query_name(), Some("Daily Confirms"));
+    assert_eq!(document.query_type(), Some("TCF"));
+    assert_eq!(document.declared_count(), Some(2));
+    assert_eq!(document.len(), 2);
+
+    let document = parse_flex(include_str!("fixtures/activity_minimal.xml")).unwrap();
+    match document {
+        FlexDocument::Activity { statements, .. } => {
+            assert_eq!(statements[0].account_id, "U1234567")
+        }
+        other => panic!("expected Activity, got {:?}", other.statement_type()),
+    }
+}
+
+#[test]
+fn test_trades_section_rows_and_lots() {
+    let xml = include_str!("fixtures/activity_trade_lots.xml");
+    let trades = parse_activity_flex(xml).unwrap().trades;
+
+    assert_eq!(trades.items.len(), 2);
+    assert_eq!(trades.orders.len(), 1);
+    assert_eq!(trades.orders[0].quantity.unwrap().to_string(), "-150");
+    assert_eq!(trades.symbol_summaries.len(), 1);
+    assert_eq!(trades.asset_summaries.len(), 1);
+    assert_eq!(trades.lots.len(), 3);
+
+    // The two lots following the first execution were consumed by it
+    let first: Vec<_> = trades.lots_closed_by(0).collect();
+    assert_eq!(first.len(), 2);
+    assert_eq!(
+        first[0].lot.open_date_time.as_deref(),
+        Some("2024-03-01;100000")
+    );
+    assert_eq!(
+        trades
+            .closing_trade(first[1])
+            .unwrap()
+            .transaction_id
+            .as_deref(),
+        Some("20002")
+    );
+
+    // A lot carrying a transactionID links to that trade, not its predecessor
+    let last = &trades.lots[2];
+    assert_eq!(last.closing_trade, Some(1));
+    assert_eq!(
+        trades.closing_trade(last).unwrap().trade_id.as_deref(),
+        Some("1234567892")
+    );
+}
+
+#[test]
+fn test_schema_report_lists_unmapped_data() {
+    let xml = include_str!("fixtures/activity_unknown_data.xml");
+    let report = ib_flex::schema_report(xml).unwrap();
+
+    assert_eq!(report.statements, 2);
+    assert!(!report.is_clean());
+
+    let trade = report.element("Trades", "Trade").unwrap();
+    assert_eq!(trade.occurrences, 1);
+    assert_eq!(trade.mapped.get("tradePrice"), Some(&1));
+    assert_eq!(trade.unmapped.get("settlementVenue"), Some(&1));
+    let lot = report.element("Trades", "Lot").unwrap();
+    assert_eq!(lot.unmapped.keys().collect::<Vec<_>>(), ["lotMethod"]);
+    let info = report
+        .element("AccountInformation", "AccountInformation")
+        .unwrap();
+    assert!(info.unmapped.contains_key("riskProfile"));
+    assert!(report
+        .element("FlexStatement", "FlexStatement")
+        .unwrap()
+        .unmapped
+        .is_empty());
+
+    let sections: Vec<_> = report
+        .unknown_sections
+        .iter()
+        .map(|u| (u.element.as_str(), u.occurrences))
+        .collect();
+    assert_eq!(sections, [("SoftDollars", 1), ("CarbonOffsets", 1)]);
+    assert!(report.unknown_elements.is_empty());
+
+    let unmapped: Vec<_> = report
+        .unmapped_attributes()
+        .map(|(element, name, _)| format!("{}@{}", element.element, name))
+        .collect();
+    assert!(unmapped.contains(&"CashTransaction@payerName".to_string()));
+    assert!(report
+        .to_string()
+        .contains("unknown section CarbonOffsets (1)"));
+}
+
+#[test]
+fn test_borrowed_rows_match_owned() {
+    use std::borrow::Cow;
+
+    let fixtures = [
+        include_str!("fixtures/activity_backfill.xml"),
+        include_str!("fixtures/activity_daily_portfolio.xml"),
+        include_str!("fixtures/activity_options.xml"),
+        include_str!("fixtures/activity_cash.xml"),
+    ];
+
+    for xml in fixtures {
+        let owned = ib_flex::parse_activity_flex_all(xml).unwrap();
+        let borrowed = ib_flex::parse_activity_flex_all_borrowed(xml).unwrap();
+        assert_eq!(borrowed.len(), owned.len());
+
+        for (borrowed, owned) in borrowed.into_iter().zip(owned) {
+            assert_eq!(borrowed.account_id, owned.account_id);
+            assert_eq!(borrowed.to_date, owned.to_date);
+
+            if let Some(trade) = borrowed.trades.first() {
+                assert!(matches!(trade.symbol, Cow::Borrowed(_)));
+                assert!(matches!(trade.description, Some(Cow::Borrowed(_))));
+            }
+            let trades: Vec<_> = borrowed
+                .trades
+                .into_iter()
+                .map(|t| t.into_owned())
+                .collect();
+            let positions: Vec<_> = borrowed
+                .positions
+                .into_iter()
+                .map(|p| p.into_owned())
+                .collect();
+            let cash: Vec<_> = borrowed
+                .cash_transactions
+                .into_iter()
+                .map(|c| c.into_owned())
+                .collect();
+            assert_eq!(trades, owned.trades.items);
+            assert_eq!(positions, owned.positions.items);
+            assert_eq!(cash, owned.cash_transactions.items);
+        }
+    }
+}